@@ -0,0 +1,86 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Delimiter, TokenTree};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Ident, ItemFn, LitStr, Path, Token};
+
+struct Args {
+    macro_fn: Path,
+    attr: Option<LitStr>,
+    input: LitStr,
+    expected: LitStr,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let macro_fn = input.parse()?;
+        let (mut attr, mut source, mut expected) = (None, None, None);
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let value = match input.parse::<TokenTree>()? {
+                TokenTree::Group(g) if g.delimiter() == Delimiter::Brace => {
+                    LitStr::new(&g.stream().to_string(), g.span())
+                }
+                tt => return Err(syn::Error::new(tt.span(), "expected `{ ... }`")),
+            };
+            match key.to_string().as_str() {
+                "attr" => attr = Some(value),
+                "input" => source = Some(value),
+                "expected" => expected = Some(value),
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "expected `attr`, `input` or `expected`",
+                    ))
+                }
+            }
+        }
+
+        let missing = |name: &str| syn::Error::new(input.span(), format!("missing `{name}`"));
+        Ok(Self {
+            macro_fn,
+            attr,
+            input: source.ok_or_else(|| missing("input"))?,
+            expected: expected.ok_or_else(|| missing("expected"))?,
+        })
+    }
+}
+
+#[proc_macro_attribute]
+pub fn assert_generates(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let Args {
+        macro_fn,
+        attr,
+        input,
+        expected,
+    } = parse_macro_input!(attr as Args);
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = parse_macro_input!(item as ItemFn);
+
+    let check = match attr {
+        Some(attr) => quote! {
+            ::syn_assert::harness::generates_attribute(#macro_fn, #attr, #input, #expected)
+        },
+        None => quote! {
+            ::syn_assert::harness::generates(#macro_fn, #input, #expected)
+        },
+    };
+
+    quote! {
+        #(#attrs)*
+        #vis #sig {
+            #check.assert();
+            #block
+        }
+    }
+    .into()
+}
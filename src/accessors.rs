@@ -0,0 +1,159 @@
+use crate::crate_ast::{CrateAst, CrateItem};
+use crate::failure::{Failure, Location};
+use crate::function::HasFn;
+use crate::rules::Rule;
+use crate::type_alias::types_match;
+use crate::utils::CheckResult;
+use quote::ToTokens;
+use syn::{parse_quote, ImplItem, ImplItemMethod, Item, ItemStruct, ReturnType, Type, Visibility};
+
+impl<'s> Rule<'s> {
+    pub fn accessors(pattern: &str) -> Self {
+        let pattern = pattern.to_string();
+        Self::new("private fields have accessors")
+            .select(|item| matches!(item, Item::Struct(_)))
+            .must_crate(move |ast, items| accessors(ast, items, &pattern))
+    }
+}
+
+pub(crate) fn accessors(ast: &CrateAst, items: &[CrateItem<'_>], pattern: &str) -> CheckResult {
+    let all = ast.items();
+    CheckResult::any(items.iter().map(|item| match item.item {
+        Item::Struct(struct_) => {
+            let methods = all
+                .iter()
+                .filter_map(|i| match i.item {
+                    Item::Impl(impl_)
+                        if impl_.trait_.is_none() && is_impl_of(&impl_.self_ty, struct_) =>
+                    {
+                        Some(impl_)
+                    }
+                    _ => None,
+                })
+                .flat_map(|impl_| &impl_.items)
+                .filter_map(|i| match i {
+                    ImplItem::Method(m) => Some(m),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            item.scope(
+                struct_accessors(struct_, &methods, pattern)
+                    .in_item(Some(format!("struct {}", struct_.ident)), None),
+            )
+        }
+        _ => CheckResult::Success,
+    }))
+}
+
+fn struct_accessors(
+    struct_: &ItemStruct,
+    methods: &[&ImplItemMethod],
+    pattern: &str,
+) -> CheckResult {
+    CheckResult::any(
+        struct_
+            .fields
+            .iter()
+            .filter(|f| matches!(f.vis, Visibility::Inherited))
+            .filter_map(|f| Some((f.ident.as_ref()?, &f.ty)))
+            .map(|(field, ty)| {
+                let name = pattern.replace("{name}", &field.to_string());
+                let method = match methods.iter().find(|m| m.sig.ident == name) {
+                    Some(method) => method,
+                    None => {
+                        return CheckResult::Failure(vec![Failure::missing(&format!(
+                            "accessor '{name}' for field '{field}'"
+                        ))
+                        .at(Some(Location::of(field)))])
+                    }
+                };
+                let expected: Type = parse_quote!(&#ty);
+                let return_type = match &method.sig.output {
+                    ReturnType::Type(_, actual) if types_match(&expected, actual) => {
+                        CheckResult::Success
+                    }
+                    ReturnType::Type(_, actual) => CheckResult::Failure(vec![Failure::mismatch(
+                        "return type",
+                        &expected.to_token_stream().to_string(),
+                        &actual.to_token_stream().to_string(),
+                    )]),
+                    ReturnType::Default => CheckResult::Failure(vec![Failure::mismatch(
+                        "return type",
+                        &expected.to_token_stream().to_string(),
+                        "()",
+                    )]),
+                };
+                (method.has_receiver("&self") + return_type)
+                    .in_item(method.breadcrumb(), method.location())
+            }),
+    )
+}
+
+fn is_impl_of(self_ty: &Type, struct_: &ItemStruct) -> bool {
+    match self_ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == struct_.ident),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_accessors() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"pub struct Config {
+    name: String,
+    port: u16,
+    pub debug: bool,
+    tags: Vec<String>,
+    path: PathBuf,
+}
+mod accessors {
+    impl super::Config {
+        pub fn name(&self) -> &String { &self.name }
+        pub fn port(&self) -> u16 { self.port }
+    }
+}
+impl Config {
+    pub fn tags(&mut self) -> &Vec<String> { &self.tags }
+}
+struct Empty;
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let failures = Rule::accessors("{name}").check(&ast).into_failures();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "Missing accessor 'path' for field 'path'".to_string(),
+                    Some(6)
+                ),
+                (
+                    "Expected return type to be '& u16', got 'u16'".to_string(),
+                    Some(11)
+                ),
+                ("Mismatched receiver: & {+mut+} self".to_string(), Some(15)),
+            ]
+        );
+        assert_eq!(failures[1].path, vec!["crate", "struct Config", "fn port"]);
+        assert_eq!(Rule::accessors("get_{name}").check(&ast).failure_count(), 4);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,244 @@
+use crate::failure::Failure;
+use crate::types::compare_types;
+use crate::utils::{compare_tokens, parse_str, tokens_contain, CheckResult};
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{Block, Expr, ExprGroup, ExprParen, ExprUnary, Ident, Lit, Type, UnOp};
+
+#[derive(Debug, Clone)]
+enum ConstValue {
+    Source(String),
+    Expr(Box<Expr>),
+}
+
+#[derive(Debug, Clone)]
+pub struct AssocConst {
+    name: String,
+    ty: String,
+    value: Option<ConstValue>,
+}
+
+impl AssocConst {
+    pub fn new(name: &str, ty: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ty: ty.to_string(),
+            value: Default::default(),
+        }
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn with_value(self, value: &str) -> Self {
+        Self {
+            value: Some(ConstValue::Source(value.to_string())),
+            ..self
+        }
+    }
+
+    pub fn with_expr(self, expr: Expr) -> Self {
+        Self {
+            value: Some(ConstValue::Expr(Box::new(expr))),
+            ..self
+        }
+    }
+
+    pub(crate) fn check<'a, I>(&self, consts: I) -> CheckResult
+    where
+        I: IntoIterator<Item = (&'a Ident, &'a Type, Option<&'a Expr>)>,
+    {
+        let (_, ty, expr) = match consts
+            .into_iter()
+            .find(|(ident, _, _)| *ident == &self.name)
+        {
+            Some(c) => c,
+            None => return CheckResult::missing(&format!("assoc const '{}'", self.name)),
+        };
+
+        let ty_result = compare_types(&self.ty, ty);
+
+        let value_result = match (&self.value, expr) {
+            (None, _) => CheckResult::Success,
+            (Some(_), None) => {
+                CheckResult::missing(&format!("value for assoc const '{}'", self.name))
+            }
+            (Some(value), Some(expr)) => {
+                let expected = match value {
+                    ConstValue::Source(source) => match parse_str::<Expr>("expression", source) {
+                        Ok(expected) => expected,
+                        Err(failure) => return ty_result + failure,
+                    },
+                    ConstValue::Expr(expected) => (**expected).clone(),
+                };
+                compare_exprs(&format!("assoc const '{}'", self.name), &expected, expr)
+            }
+        };
+
+        ty_result + value_result
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AssocType {
+    name: String,
+    ty: String,
+}
+
+impl AssocType {
+    pub fn new(name: &str, ty: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ty: ty.to_string(),
+        }
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn check<'a, I>(&self, types: I) -> CheckResult
+    where
+        I: IntoIterator<Item = (&'a Ident, &'a Type)>,
+    {
+        match types.into_iter().find(|(ident, _)| *ident == &self.name) {
+            Some((_, ty)) => compare_types(&self.ty, ty),
+            None => CheckResult::missing(&format!("assoc type '{}'", self.name)),
+        }
+    }
+}
+
+pub(crate) fn compare_exprs(subject: &str, expected: &Expr, actual: &Expr) -> CheckResult {
+    let values = lit_value(expected).zip(lit_value(actual));
+    let expected = expected.to_token_stream().to_string();
+    let actual = actual.to_token_stream().to_string();
+    let matches = match values {
+        Some((expected, actual)) => expected == actual,
+        None => expected == actual,
+    };
+    if matches {
+        CheckResult::Success
+    } else {
+        CheckResult::Failure(vec![Failure::mismatch(subject, &expected, &actual)])
+    }
+}
+
+fn lit_value(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(lit) => Some(match &lit.lit {
+            Lit::Int(i) => format!("int {}", i.base10_digits()),
+            Lit::Float(f) => format!("float {}", f.base10_digits().parse::<f64>().ok()?),
+            Lit::Str(s) => format!("str {:?}", s.value()),
+            Lit::ByteStr(s) => format!("bytes {:?}", s.value()),
+            Lit::Byte(b) => format!("int {}", b.value()),
+            Lit::Char(c) => format!("char {:?}", c.value()),
+            Lit::Bool(b) => format!("bool {}", b.value),
+            Lit::Verbatim(_) => return None,
+        }),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => lit_value(expr).map(|value| format!("-{value}")),
+        Expr::Paren(ExprParen { expr, .. }) | Expr::Group(ExprGroup { expr, .. }) => {
+            lit_value(expr)
+        }
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone)]
+enum DefaultBody {
+    Any,
+    Required,
+    Provided,
+    Exact(TokenStream),
+    Containing(TokenStream),
+}
+
+#[derive(Debug, Clone)]
+pub struct AssocFn {
+    name: String,
+    default: DefaultBody,
+}
+
+impl AssocFn {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            default: DefaultBody::Any,
+        }
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn with_default(self) -> Self {
+        Self {
+            default: DefaultBody::Provided,
+            ..self
+        }
+    }
+
+    pub fn without_default(self) -> Self {
+        Self {
+            default: DefaultBody::Required,
+            ..self
+        }
+    }
+
+    pub fn with_default_block(self, block: TokenStream) -> Self {
+        Self {
+            default: DefaultBody::Exact(block),
+            ..self
+        }
+    }
+
+    pub fn with_default_containing(self, tokens: TokenStream) -> Self {
+        Self {
+            default: DefaultBody::Containing(tokens),
+            ..self
+        }
+    }
+
+    pub(crate) fn check<'a, I>(&self, fns: I) -> CheckResult
+    where
+        I: IntoIterator<Item = (&'a Ident, Option<&'a Block>)>,
+    {
+        let block = match fns.into_iter().find(|(ident, _)| *ident == &self.name) {
+            Some((_, block)) => block,
+            None => return CheckResult::missing(&format!("method '{}'", self.name)),
+        };
+
+        match (&self.default, block) {
+            (DefaultBody::Any, _) => CheckResult::Success,
+            (DefaultBody::Required, None) => CheckResult::Success,
+            (DefaultBody::Required, Some(_)) => CheckResult::Failure(vec![Failure::unexpected(
+                &format!("default body for method '{}'", self.name),
+            )]),
+            (_, None) => CheckResult::missing(&format!("default body for method '{}'", self.name)),
+            (DefaultBody::Provided, Some(_)) => CheckResult::Success,
+            (DefaultBody::Exact(expected), Some(block)) => compare_tokens(
+                &format!("default body of method '{}'", self.name),
+                expected,
+                block,
+            ),
+            (DefaultBody::Containing(expected), Some(block)) => {
+                if tokens_contain(block.to_token_stream(), expected.clone()) {
+                    CheckResult::Success
+                } else {
+                    CheckResult::Failure(vec![Failure {
+                        expected: Some(expected.to_string()),
+                        actual: Some(block.to_token_stream().to_string()),
+                        ..Failure::missing(&format!(
+                            "'{expected}' in default body of method '{}'",
+                            self.name
+                        ))
+                    }])
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use crate::failure::{Failure, Location};
+use crate::function::HasFn;
+use crate::rules::Rule;
+use crate::utils::{compare_attr_args, CheckResult};
+use syn::{Attribute, ImplItem, Item, Signature, TraitItem, Visibility};
+
+impl<'s> Rule<'s> {
+    pub fn async_fns_take(receiver: &str) -> Self {
+        let receiver = receiver.to_string();
+        Self::new(&format!("pub async fns take {receiver}"))
+            .select(|item| matches!(item, Item::Impl(_)))
+            .must(move |item| async_receiver(item, &receiver))
+    }
+
+    pub fn instrumented<I, S>(args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let args = args
+            .into_iter()
+            .map(|a| a.as_ref().to_string())
+            .collect::<Vec<_>>();
+        Self::new("pub async fns are instrumented")
+            .select(|item| matches!(item, Item::Fn(_) | Item::Impl(_)))
+            .must(move |item| instrumented(item, "tracing::instrument", &args))
+    }
+
+    pub fn no_async_fns() -> Self {
+        Self::new("no async fns").must(no_async)
+    }
+}
+
+pub(crate) fn async_receiver(item: &Item, receiver: &str) -> CheckResult {
+    let impl_ = match item {
+        Item::Impl(impl_) if impl_.trait_.is_none() => impl_,
+        _ => return CheckResult::Success,
+    };
+    CheckResult::any(impl_.items.iter().map(|i| {
+        match i {
+            ImplItem::Method(m)
+                if m.sig.asyncness.is_some() && matches!(m.vis, Visibility::Public(_)) =>
+            {
+                m.has_receiver(receiver)
+                    .in_item(m.breadcrumb(), m.location())
+            }
+            _ => CheckResult::Success,
+        }
+    }))
+}
+
+pub(crate) fn instrumented(item: &Item, attr: &str, args: &[String]) -> CheckResult {
+    let fns: Vec<(&Signature, &[Attribute], _)> = match item {
+        Item::Fn(func) if is_pub(&func.vis) => vec![(&func.sig, &func.attrs, func.location())],
+        Item::Impl(impl_) if impl_.trait_.is_none() => impl_
+            .items
+            .iter()
+            .filter_map(|i| match i {
+                ImplItem::Method(m) if is_pub(&m.vis) => Some((&m.sig, &m.attrs[..], m.location())),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    CheckResult::any(
+        fns.into_iter()
+            .filter(|(sig, _, _)| sig.asyncness.is_some())
+            .map(|(sig, attrs, location)| {
+                compare_attr_args(attrs, attr, args)
+                    .in_item(Some(format!("fn {}", sig.ident)), location)
+            }),
+    )
+}
+
+pub(crate) fn no_async(item: &Item) -> CheckResult {
+    let sigs = match item {
+        Item::Fn(func) => vec![&func.sig],
+        Item::Impl(impl_) => impl_
+            .items
+            .iter()
+            .filter_map(|i| match i {
+                ImplItem::Method(m) => Some(&m.sig),
+                _ => None,
+            })
+            .collect(),
+        Item::Trait(trait_) => trait_
+            .items
+            .iter()
+            .filter_map(|i| match i {
+                TraitItem::Method(m) => Some(&m.sig),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    sigs.into_iter()
+        .filter_map(|sig: &Signature| {
+            sig.asyncness.map(|asyncness| {
+                Failure::unexpected(&format!("async fn '{}'", sig.ident))
+                    .at(Some(Location::of(&asyncness)))
+            })
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+fn is_pub(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crate_ast::CrateAst;
+    use crate::function::FnSpec;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_async_conventions() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"pub async fn connect() -> Client { todo!() }
+pub struct Client;
+impl Client {
+    pub async fn get(&self) {}
+    pub async fn put(&mut self) {}
+    async fn flush(&mut self) {}
+    pub fn close(self) {}
+}
+mod sync {
+    pub fn read() {}
+    pub async fn write() {}
+    trait Store { async fn load(&self); }
+}
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let messages = |rule: Rule| {
+            rule.check(&ast)
+                .into_failures()
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            messages(Rule::async_fns_take("&self")),
+            vec![("Mismatched receiver: & {+mut+} self".to_string(), Some(5))]
+        );
+        assert_eq!(
+            messages(Rule::no_async_fns().in_module("crate::sync")),
+            vec![
+                ("Unexpected async fn 'write'".to_string(), Some(11)),
+                ("Unexpected async fn 'load'".to_string(), Some(12)),
+            ]
+        );
+        assert_eq!(
+            messages(
+                Rule::new("pub fns are sync")
+                    .in_module("crate::sync")
+                    .select(|item| matches!(item, Item::Fn(_)))
+                    .must_match(FnSpec::new().with_async(false))
+            ),
+            vec![(
+                "Expected async to be 'false', got 'true'".to_string(),
+                Some(11)
+            )]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_instrumented() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"#[tracing::instrument(skip(db))]
+pub async fn connect(db: Db) {}
+#[instrument]
+pub async fn ping() {}
+pub async fn close() {}
+pub fn sync() {}
+pub struct Client;
+impl Client {
+    #[tracing::instrument(skip(self, req), fields(id))]
+    pub async fn send(&self, req: Request) {}
+    #[tracing::instrument(level = "debug")]
+    pub async fn recv(&self) {}
+}
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let messages = |rule: Rule| {
+            rule.check(&ast)
+                .into_failures()
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            messages(Rule::instrumented(Vec::<&str>::new())),
+            vec![(
+                "Missing attribute 'tracing::instrument'".to_string(),
+                Some(5)
+            )]
+        );
+        assert_eq!(
+            messages(Rule::instrumented(["skip(self)"]).in_module("crate")),
+            vec![
+                (
+                    "Missing argument 'skip(self)' of attribute 'tracing::instrument'".to_string(),
+                    Some(1)
+                ),
+                (
+                    "Missing argument 'skip(self)' of attribute 'tracing::instrument'".to_string(),
+                    Some(3)
+                ),
+                (
+                    "Missing attribute 'tracing::instrument'".to_string(),
+                    Some(5)
+                ),
+                (
+                    "Missing argument 'skip(self)' of attribute 'tracing::instrument'".to_string(),
+                    Some(11)
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+}
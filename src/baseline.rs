@@ -0,0 +1,183 @@
+use crate::failure::Failure;
+use crate::report::Report;
+use crate::utils::CheckResult;
+use std::{collections::BTreeMap, env, fs, io, path::Path};
+
+pub const UPDATE_BASELINE_VAR: &str = "SYN_ASSERT_UPDATE_BASELINE";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Baseline {
+    entries: BTreeMap<String, usize>,
+}
+
+fn fingerprint(name: &str, failure: &Failure) -> String {
+    format!(
+        "{name}\t{}\t{}",
+        failure.path.join(" > "),
+        failure.message()
+    )
+    .replace('\\', "\\\\")
+    .replace('\n', "\\n")
+}
+
+impl Baseline {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn from_report(report: &Report) -> Self {
+        let mut baseline = Self::new();
+        for (name, result) in report.results() {
+            baseline.record(name, result);
+        }
+        baseline
+    }
+
+    pub fn load<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                let mut baseline = Self::new();
+                for line in content.lines().filter(|l| !l.is_empty()) {
+                    *baseline.entries.entry(line.to_string()).or_default() += 1;
+                }
+                Ok(baseline)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save<P>(&self, path: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let mut content = self
+            .entries
+            .iter()
+            .flat_map(|(entry, count)| vec![entry.as_str(); *count])
+            .collect::<Vec<_>>()
+            .join("\n");
+        if !content.is_empty() {
+            content.push('\n');
+        }
+        fs::write(path, content)
+    }
+
+    pub fn record(&mut self, name: &str, result: &CheckResult) -> &mut Self {
+        for failure in result.failures() {
+            *self.entries.entry(fingerprint(name, failure)).or_default() += 1;
+        }
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.values().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn filter(&self, name: &str, result: CheckResult) -> CheckResult {
+        let mut remaining = self.entries.clone();
+        result
+            .into_failures()
+            .into_iter()
+            .filter(|f| match remaining.get_mut(&fingerprint(name, f)) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    pub fn apply(&self, report: &mut Report) {
+        for (name, result) in report.checks.iter_mut() {
+            *result = self.filter(name, result.clone());
+        }
+    }
+
+    pub fn apply_or_update<P>(path: P, report: &mut Report) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        if env::var_os(UPDATE_BASELINE_VAR).is_some() {
+            Self::from_report(report).save(&path)?;
+        }
+        Self::load(path)?.apply(report);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_baseline() -> Result<(), TestError> {
+        let mut report = Report::new();
+        report
+            .add("docs", CheckResult::missing("doc comment"))
+            .add("names", CheckResult::Success);
+        let baseline = Baseline::from_report(&report);
+        assert_eq!(baseline.len(), 1);
+
+        let mut report = Report::new();
+        report
+            .add("docs", CheckResult::missing("doc comment"))
+            .add("names", CheckResult::missing("name"));
+        baseline.apply(&mut report);
+
+        assert_eq!((report.passed(), report.failed()), (1, 1));
+
+        let twice = || {
+            CheckResult::Failure(vec![
+                Failure::missing("doc comment"),
+                Failure::missing("doc comment"),
+            ])
+        };
+        let mut report = Report::new();
+        report.add("docs", twice());
+        assert_eq!(Baseline::from_report(&report).len(), 2);
+        let filtered = baseline.filter("docs", twice());
+        assert_eq!(filtered.failure_count(), 1);
+        assert!(Baseline::from_report(&report)
+            .filter("docs", twice())
+            .as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_load() -> Result<(), TestError> {
+        let dir = TestDir::new(&[])?;
+        let path = dir.join("baseline");
+        let mut baseline = Baseline::new();
+        baseline.record(
+            "docs",
+            &CheckResult::Failure(vec![
+                Failure::missing("doc\ncomment").within("fn main"),
+                Failure::missing("doc\ncomment").within("fn main"),
+            ]),
+        );
+        baseline.save(&path)?;
+        assert_eq!(fs::read_to_string(&path)?.lines().count(), 2);
+
+        let loaded = Baseline::load(&path)?;
+        fs::remove_file(&path)?;
+        assert_eq!(loaded, baseline);
+        assert!(Baseline::load(&path)?.is_empty());
+
+        Ok(())
+    }
+}
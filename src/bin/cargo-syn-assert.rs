@@ -0,0 +1,8 @@
+use std::{env, io, process};
+
+fn main() -> io::Result<()> {
+    let mut stdout = io::stdout();
+    let color = syn_assert::cli::color_for(&stdout);
+    let code = syn_assert::cli::run_cargo(env::args().skip(1), ".", &mut stdout, color)?;
+    process::exit(code)
+}
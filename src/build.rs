@@ -0,0 +1,50 @@
+use crate::report::Report;
+
+pub fn check_build(report: &Report) {
+    for line in cargo_warnings(report) {
+        println!("{line}");
+    }
+    if !report.as_bool() {
+        panic!("{}", report.render(false));
+    }
+}
+
+fn cargo_warnings(report: &Report) -> Vec<String> {
+    report
+        .results()
+        .flat_map(|(name, result)| {
+            result
+                .warnings()
+                .map(move |warning| format!("cargo:warning={name}: {}", warning.render(false)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::CheckResult;
+
+    #[test]
+    fn test_cargo_warnings() {
+        let mut report = Report::new();
+        report
+            .add("structural", CheckResult::Success)
+            .warn("style", CheckResult::missing("doc comment"));
+
+        assert_eq!(
+            cargo_warnings(&report),
+            vec!["cargo:warning=style: warning: Missing doc comment".to_string()]
+        );
+        check_build(&report);
+    }
+
+    #[test]
+    #[should_panic(expected = "[FAIL] structural")]
+    fn test_check_build_fail() {
+        let mut report = Report::new();
+        report.add("structural", CheckResult::missing("name"));
+
+        check_build(&report);
+    }
+}
@@ -0,0 +1,211 @@
+use crate::imports::ImportScope;
+use crate::rules::item_attrs;
+use std::collections::BTreeSet;
+use syn::{Attribute, Item, ItemMod, Lit, Meta, NestedMeta};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CfgSet {
+    features: BTreeSet<String>,
+    flags: BTreeSet<String>,
+    values: BTreeSet<(String, String)>,
+    all_features: bool,
+}
+
+impl CfgSet {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_feature(mut self, feature: &str) -> Self {
+        self.features.insert(feature.to_string());
+        self
+    }
+
+    pub fn with_features<I, S>(mut self, features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.features
+            .extend(features.into_iter().map(|f| f.as_ref().to_string()));
+        self
+    }
+
+    pub fn with_flag(mut self, flag: &str) -> Self {
+        self.flags.insert(flag.to_string());
+        self
+    }
+
+    pub fn with_value(mut self, key: &str, value: &str) -> Self {
+        if key == "feature" {
+            return self.with_feature(value);
+        }
+        self.values.insert((key.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn all_features(self) -> Self {
+        Self {
+            all_features: true,
+            ..self
+        }
+    }
+
+    #[cfg(feature = "workspace")]
+    pub(crate) fn features(&self) -> impl Iterator<Item = &str> {
+        self.features.iter().map(String::as_str)
+    }
+
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.all_features || self.features.contains(feature)
+    }
+
+    pub fn is_enabled(&self, attrs: &[Attribute]) -> bool {
+        attrs
+            .iter()
+            .filter(|a| a.path.is_ident("cfg"))
+            .all(|a| match a.parse_meta() {
+                Ok(Meta::List(list)) if list.nested.len() == 1 => self.eval(&list.nested[0]),
+                _ => true,
+            })
+    }
+
+    pub fn is_item_enabled(&self, item: &Item) -> bool {
+        item_attrs(item).is_none_or(|attrs| self.is_enabled(attrs))
+    }
+
+    pub fn walk_items<'a>(&self, items: &'a [Item]) -> Vec<&'a Item> {
+        let mut out = Vec::new();
+        self.walk(items, &mut out);
+        out
+    }
+
+    pub fn walk_scopes<'a>(&self, items: &'a [Item]) -> Vec<(ImportScope, Vec<&'a Item>)> {
+        let mut out = Vec::new();
+        self.walk_scope(items, &mut out);
+        out
+    }
+
+    fn walk_scope<'a>(&self, items: &'a [Item], out: &mut Vec<(ImportScope, Vec<&'a Item>)>) {
+        let enabled: Vec<_> = items.iter().filter(|i| self.is_item_enabled(i)).collect();
+        out.push((ImportScope::of(enabled.iter().copied()), enabled.clone()));
+        for item in enabled {
+            if let Item::Mod(ItemMod {
+                content: Some((_, items)),
+                ..
+            }) = item
+            {
+                self.walk_scope(items, out);
+            }
+        }
+    }
+
+    fn walk<'a>(&self, items: &'a [Item], out: &mut Vec<&'a Item>) {
+        for item in items.iter().filter(|i| self.is_item_enabled(i)) {
+            out.push(item);
+            if let Item::Mod(ItemMod {
+                content: Some((_, items)),
+                ..
+            }) = item
+            {
+                self.walk(items, out);
+            }
+        }
+    }
+
+    fn eval(&self, predicate: &NestedMeta) -> bool {
+        match predicate {
+            NestedMeta::Meta(Meta::Path(path)) => path
+                .get_ident()
+                .is_some_and(|flag| self.flags.contains(&flag.to_string())),
+            NestedMeta::Meta(Meta::NameValue(nv)) => match (nv.path.get_ident(), &nv.lit) {
+                (Some(key), Lit::Str(value)) if key == "feature" => {
+                    self.has_feature(&value.value())
+                }
+                (Some(key), Lit::Str(value)) => {
+                    self.values.contains(&(key.to_string(), value.value()))
+                }
+                _ => false,
+            },
+            NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("all") => {
+                list.nested.iter().all(|p| self.eval(p))
+            }
+            NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("any") => {
+                list.nested.iter().any(|p| self.eval(p))
+            }
+            NestedMeta::Meta(Meta::List(list))
+                if list.path.is_ident("not") && list.nested.len() == 1 =>
+            {
+                !self.eval(&list.nested[0])
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_is_enabled() {
+        let cfg = CfgSet::new().with_features(["std"]);
+
+        let std: Vec<Attribute> = vec![parse_quote!(#[cfg(feature = "std")])];
+        let alloc: Vec<Attribute> = vec![parse_quote!(#[cfg(feature = "alloc")])];
+        let unix: Vec<Attribute> = vec![parse_quote!(#[cfg(unix)])];
+
+        assert!(cfg.is_enabled(&std));
+        assert!(!cfg.is_enabled(&alloc));
+        assert!(!cfg.is_enabled(&unix));
+        assert!(cfg.clone().with_flag("unix").is_enabled(&unix));
+        assert!(CfgSet::new().all_features().is_enabled(&alloc));
+    }
+
+    #[test]
+    fn test_is_enabled_expr() {
+        let cfg = CfgSet::new()
+            .with_feature("std")
+            .with_value("target_os", "linux");
+
+        let enabled: Vec<Attribute> = vec![
+            parse_quote!(#[cfg(all(feature = "std", target_os = "linux"))]),
+            parse_quote!(#[cfg(any(windows, not(test)))]),
+        ];
+        let disabled: Vec<Attribute> = vec![parse_quote!(#[cfg(all(
+            feature = "std",
+            not(target_os = "linux")
+        ))])];
+
+        assert!(cfg.is_enabled(&enabled));
+        assert!(!cfg.is_enabled(&disabled));
+    }
+
+    #[test]
+    fn test_walk_items() {
+        let file: syn::File = parse_quote! {
+            fn always() {}
+            #[cfg(test)]
+            mod tests { fn helper() {} }
+            mod inner {
+                #[cfg(feature = "std")]
+                fn with_std() {}
+                #[cfg(not(feature = "std"))]
+                fn without_std() {}
+            }
+        };
+
+        let names = CfgSet::new()
+            .with_feature("std")
+            .walk_items(&file.items)
+            .into_iter()
+            .filter_map(|item| match item {
+                Item::Fn(f) => Some(f.sig.ident.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["always", "with_std"]);
+    }
+}
@@ -0,0 +1,331 @@
+use crate::cfg::CfgSet;
+use crate::color;
+use crate::crate_ast::CrateAst;
+use crate::report::Report;
+use crate::rules::Rules;
+use crate::utils::CheckResult;
+use crate::workspace::Workspace;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+pub const USAGE: &str = "usage: syn-assert --rules <FILE> [PATH]";
+pub const CARGO_USAGE: &str = "usage: cargo syn-assert [--rules <FILE>] [-p <PACKAGE>]... \
+     [-F <FEATURES>] [--all-features] [--no-default-features]";
+pub const DEFAULT_RULES: &str = "syn-assert.toml";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    pub rules: PathBuf,
+    pub path: PathBuf,
+}
+
+impl Args {
+    pub fn parse<I>(args: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut rules = None;
+        let mut path = None;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-r" | "--rules" => {
+                    rules = Some(args.next().ok_or("missing value for --rules")?.into())
+                }
+                "-h" | "--help" => return Err(USAGE.to_string()),
+                flag if flag.starts_with('-') => return Err(format!("unknown flag '{flag}'")),
+                _ if path.is_none() => path = Some(arg.into()),
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        Ok(Self {
+            rules: rules.ok_or("missing --rules")?,
+            path: path.unwrap_or_else(|| ".".into()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CargoArgs {
+    pub rules: PathBuf,
+    pub packages: Vec<String>,
+    pub cfg: CfgSet,
+    pub default_features: bool,
+}
+
+impl CargoArgs {
+    pub fn parse<I>(args: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut args = args.into_iter().peekable();
+        if args.peek().map(String::as_str) == Some("syn-assert") {
+            args.next();
+        }
+
+        let mut rules = None;
+        let mut packages = Vec::new();
+        let mut cfg = CfgSet::new();
+        let mut default_features = true;
+        while let Some(arg) = args.next() {
+            let (arg, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = |flag: &str| match inline.clone() {
+                Some(value) => Ok(value),
+                None => args.next().ok_or(format!("missing value for {flag}")),
+            };
+            match arg.as_str() {
+                "--all-features" | "--no-default-features" if inline.is_some() => {
+                    return Err(format!("unexpected value for {arg}"))
+                }
+                "-r" | "--rules" => rules = Some(value("--rules")?.into()),
+                "-p" | "--package" => packages.push(value("--package")?),
+                "-F" | "--features" => {
+                    cfg = cfg.with_features(
+                        value("--features")?
+                            .split([',', ' '])
+                            .filter(|f| !f.is_empty()),
+                    )
+                }
+                "--all-features" => cfg = cfg.all_features(),
+                "--no-default-features" => default_features = false,
+                "-h" | "--help" => return Err(CARGO_USAGE.to_string()),
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        Ok(Self {
+            rules: rules.unwrap_or_else(|| DEFAULT_RULES.into()),
+            packages,
+            cfg,
+            default_features,
+        })
+    }
+}
+
+pub fn color_for<T>(stream: &T) -> bool
+where
+    T: IsTerminal,
+{
+    color::enabled(stream)
+}
+
+pub fn run_cargo<I, W, P>(args: I, dir: P, out: &mut W, color: bool) -> io::Result<i32>
+where
+    I: IntoIterator<Item = String>,
+    W: Write,
+    P: AsRef<Path>,
+{
+    let args = match CargoArgs::parse(args) {
+        Ok(args) => args,
+        Err(e) => {
+            writeln!(out, "{e}\n{CARGO_USAGE}")?;
+            return Ok(2);
+        }
+    };
+
+    let workspace = match args.default_features {
+        true => Workspace::load_with(dir.as_ref(), &args.cfg),
+        false => Workspace::load_without_default_features(dir.as_ref(), &args.cfg),
+    }
+    .and_then(|workspace| workspace.select(&args.packages));
+    let rules = Rules::load(dir.as_ref().join(&args.rules));
+    let (rules, workspace) = match (rules, workspace) {
+        (Ok(rules), Ok(workspace)) => (rules, workspace),
+        (Err(failure), _) | (_, Err(failure)) => {
+            writeln!(out, "{}", failure.render(color))?;
+            return Ok(2);
+        }
+    };
+
+    let mut report = Report::new();
+    for member in &workspace.members {
+        for target in &member.targets {
+            let name = match member.targets.len() {
+                1 => member.name.clone(),
+                _ => format!("{} ({} {})", member.name, target.kind, target.name),
+            };
+            for (rule, result) in rules.check(&target.ast).results() {
+                report.add(&format!("{name}: {rule}"), result.clone());
+            }
+        }
+    }
+    writeln!(out, "{}", report.render(color))?;
+    Ok(if report.as_bool() { 0 } else { 1 })
+}
+
+pub fn run<I, W>(args: I, out: &mut W, color: bool) -> io::Result<i32>
+where
+    I: IntoIterator<Item = String>,
+    W: Write,
+{
+    let args = match Args::parse(args) {
+        Ok(args) => args,
+        Err(e) => {
+            writeln!(out, "{e}\n{USAGE}")?;
+            return Ok(2);
+        }
+    };
+
+    let (rules, ast) = match (Rules::load(&args.rules), load(&args.path)) {
+        (Ok(rules), Ok(ast)) => (rules, ast),
+        (Err(failure), _) | (_, Err(failure)) => {
+            writeln!(out, "{}", failure.render(color))?;
+            return Ok(2);
+        }
+    };
+
+    let report = rules.check(&ast);
+    writeln!(out, "{}", report.render(color))?;
+    Ok(if report.as_bool() { 0 } else { 1 })
+}
+
+fn load(path: &Path) -> Result<CrateAst, CheckResult> {
+    if path.is_file() {
+        CrateAst::from_root(path)
+    } else {
+        CrateAst::load(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Args::parse(args(&["--rules", "rules.toml", "src/lib.rs"])),
+            Ok(Args {
+                rules: "rules.toml".into(),
+                path: "src/lib.rs".into()
+            })
+        );
+        assert!(Args::parse(args(&["src/lib.rs"])).is_err());
+        assert!(Args::parse(args(&["-r", "a", "b", "c"])).is_err());
+    }
+
+    #[test]
+    fn test_cargo_parse() {
+        let parsed = CargoArgs::parse(args(&[
+            "syn-assert",
+            "-p",
+            "api",
+            "--features",
+            "std,serde",
+        ]))
+        .unwrap();
+
+        assert_eq!(parsed.rules, PathBuf::from(DEFAULT_RULES));
+        assert_eq!(parsed.packages, vec!["api".to_string()]);
+        assert!(parsed.cfg.has_feature("serde"));
+        assert!(!parsed.cfg.has_feature("alloc"));
+        assert!(parsed.default_features);
+        assert!(CargoArgs::parse(args(&["syn-assert", "src"])).is_err());
+
+        let parsed = CargoArgs::parse(args(&[
+            "syn-assert",
+            "--package=api",
+            "--features=a,b",
+            "--rules=lint.toml",
+            "--no-default-features",
+        ]))
+        .unwrap();
+
+        assert_eq!(parsed.rules, PathBuf::from("lint.toml"));
+        assert_eq!(parsed.packages, vec!["api".to_string()]);
+        assert!(parsed.cfg.has_feature("a") && parsed.cfg.has_feature("b"));
+        assert!(!parsed.default_features);
+        assert!(CargoArgs::parse(args(&["syn-assert", "--all-features=yes"])).is_err());
+    }
+
+    #[test]
+    fn test_run_cargo() -> Result<(), TestError> {
+        let dir = TestDir::new(&[
+            ("Cargo.toml", "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[features]\ndefault = [\"std\"]\nstd = []\nfull = [\"extra\"]\nextra = []\n\n[workspace]\n"),
+            ("src/lib.rs", "pub fn ok() {}\n#[cfg(feature = \"std\")]\nmod std_only;\n#[cfg(feature = \"extra\")]\nmod extra;\n"),
+            ("src/std_only.rs", "fn hidden() {}\n"),
+            ("src/extra.rs", "fn hidden() {}\n"),
+            (DEFAULT_RULES, "[[rule]]\nname = \"fns are pub\"\nkind = \"fn\"\nvis = \"pub\"\n"),
+        ])?;
+
+        let mut out = Vec::new();
+        let default = run_cargo(args(&["syn-assert"]), &dir, &mut Vec::new(), false)?;
+        let passed = run_cargo(
+            args(&["syn-assert", "--no-default-features"]),
+            &dir,
+            &mut out,
+            false,
+        )?;
+        let implied = run_cargo(
+            args(&["syn-assert", "--no-default-features", "--features=full"]),
+            &dir,
+            &mut Vec::new(),
+            false,
+        )?;
+        let unknown = run_cargo(
+            args(&["syn-assert", "--package=nope"]),
+            &dir,
+            &mut Vec::new(),
+            false,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "1 of 1 check(s) passed\n[ok]   demo: fns are pub\n"
+        );
+        assert_eq!((default, passed, implied, unknown), (1, 0, 1, 2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_run() -> Result<(), TestError> {
+        let dir = TestDir::new(&[
+            ("lib.rs", "pub fn ok() {}\nfn hidden() {}\n"),
+            (
+                "rules.toml",
+                "[[rule]]\nname = \"fns are pub\"\nkind = \"fn\"\nvis = \"pub\"\n",
+            ),
+        ])?;
+
+        let cli_args = || {
+            args(&[
+                "--rules",
+                &dir.join("rules.toml").display().to_string(),
+                &dir.join("lib.rs").display().to_string(),
+            ])
+        };
+        let mut out = Vec::new();
+        let code = run(cli_args(), &mut out, false)?;
+        let mut colored = Vec::new();
+        run(cli_args(), &mut colored, true)?;
+        let missing = run(
+            args(&["--rules", "/nonexistent/rules.toml"]),
+            &mut Vec::new(),
+            false,
+        )?;
+
+        assert_eq!(code, 1);
+        let out = String::from_utf8(out)?;
+        assert!(out.starts_with("0 of 1 check(s) passed\n[FAIL] fns are pub"));
+        assert!(!out.contains('\x1b'));
+        assert!(String::from_utf8(colored)?.contains('\x1b'));
+        assert_eq!(missing, 2);
+
+        Ok(())
+    }
+}
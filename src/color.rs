@@ -0,0 +1,37 @@
+use std::io::IsTerminal;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Style {
+    Expected,
+    Actual,
+    Context,
+}
+
+pub(crate) fn paint(text: &str, style: Style, color: bool) -> String {
+    if !color {
+        return text.to_string();
+    }
+
+    let code = match style {
+        Style::Expected => "32",
+        Style::Actual => "31",
+        Style::Context => "2",
+    };
+    format!("\x1b[{code}m{text}\x1b[0m")
+}
+
+#[cfg(feature = "color")]
+pub(crate) fn enabled<T>(stream: &T) -> bool
+where
+    T: IsTerminal,
+{
+    std::env::var_os("NO_COLOR").is_none() && stream.is_terminal()
+}
+
+#[cfg(not(feature = "color"))]
+pub(crate) fn enabled<T>(_stream: &T) -> bool
+where
+    T: IsTerminal,
+{
+    false
+}
@@ -0,0 +1,154 @@
+use crate::function::HasFn;
+use crate::rules::Rule;
+use crate::utils::CheckResult;
+use syn::visit::{self, Visit};
+use syn::{
+    Arm, Block, ExprForLoop, ExprIf, ExprLoop, ExprMatch, ExprTry, ExprWhile, ImplItem, Item,
+};
+
+impl<'s> Rule<'s> {
+    pub fn max_complexity(max: usize) -> Self {
+        Self::new(&format!("fns have complexity of at most {max}"))
+            .select(|item| matches!(item, Item::Fn(_) | Item::Impl(_)))
+            .must(move |item| max_complexity(item, max))
+    }
+}
+
+pub fn complexity(block: &Block) -> usize {
+    let mut visitor = BranchVisitor(1);
+    visitor.visit_block(block);
+    visitor.0
+}
+
+pub(crate) fn max_complexity(item: &Item, max: usize) -> CheckResult {
+    match item {
+        Item::Fn(func) => func
+            .has_max_complexity(max)
+            .in_item(func.breadcrumb(), func.location()),
+        Item::Impl(impl_) => CheckResult::any(impl_.items.iter().map(|i| {
+            match i {
+                ImplItem::Method(method) => method
+                    .has_max_complexity(max)
+                    .in_item(method.breadcrumb(), method.location()),
+                _ => CheckResult::Success,
+            }
+        })),
+        _ => CheckResult::Success,
+    }
+}
+
+struct BranchVisitor(usize);
+
+impl<'ast> Visit<'ast> for BranchVisitor {
+    fn visit_item(&mut self, _: &'ast Item) {}
+
+    fn visit_expr_if(&mut self, expr: &'ast ExprIf) {
+        self.0 += 1;
+        visit::visit_expr_if(self, expr);
+    }
+
+    fn visit_expr_match(&mut self, expr: &'ast ExprMatch) {
+        self.0 += expr.arms.len().saturating_sub(1);
+        visit::visit_expr_match(self, expr);
+    }
+
+    fn visit_arm(&mut self, arm: &'ast Arm) {
+        if arm.guard.is_some() {
+            self.0 += 1;
+        }
+        visit::visit_arm(self, arm);
+    }
+
+    fn visit_expr_while(&mut self, expr: &'ast ExprWhile) {
+        self.0 += 1;
+        visit::visit_expr_while(self, expr);
+    }
+
+    fn visit_expr_for_loop(&mut self, expr: &'ast ExprForLoop) {
+        self.0 += 1;
+        visit::visit_expr_for_loop(self, expr);
+    }
+
+    fn visit_expr_loop(&mut self, expr: &'ast ExprLoop) {
+        self.0 += 1;
+        visit::visit_expr_loop(self, expr);
+    }
+
+    fn visit_expr_try(&mut self, expr: &'ast ExprTry) {
+        self.0 += 1;
+        visit::visit_expr_try(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crate_ast::CrateAst;
+    use crate::test_dir::TestDir;
+    use crate::utils::Check;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_complexity() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"fn simple() -> u8 { 1 }
+
+fn branchy(x: Option<u8>) -> Result<u8, E> {
+    let y = parse()?;
+    for i in 0..y {
+        if i > 2 { break; }
+    }
+    match x {
+        Some(0) => Ok(0),
+        Some(n) if n > 3 => Ok(n),
+        _ => { fn nested() { if true {} } Ok(y) }
+    }
+}
+
+struct Server;
+impl Server {
+    fn run(&self) { loop { while ready() {} } }
+}
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let func = syn::parse_str::<syn::ItemFn>("fn f() { if a { b()? } else if c {} }")?;
+        assert_eq!(complexity(&func.block), 4);
+        assert!(func.has_fn().with_max_complexity(4).check().as_bool());
+        assert_eq!(
+            func.has_fn().with_max_complexity(3).check().into_failures()[0].message(),
+            "Expected complexity to be 'at most 3', got '4'"
+        );
+
+        assert!(Rule::max_complexity(7).check(&ast).as_bool());
+        let failures = Rule::max_complexity(2).check(&ast).into_failures();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|f| (
+                    f.path.clone(),
+                    f.message(),
+                    f.location.as_ref().map(|l| l.line)
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    vec!["crate".to_string(), "fn branchy".to_string()],
+                    "Expected complexity to be 'at most 2', got '7'".to_string(),
+                    Some(3)
+                ),
+                (
+                    vec!["crate".to_string(), "fn run".to_string()],
+                    "Expected complexity to be 'at most 2', got '3'".to_string(),
+                    Some(17)
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+}
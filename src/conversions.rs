@@ -0,0 +1,270 @@
+use crate::crate_ast::{CrateAst, CrateItem};
+use crate::failure::{Failure, Location};
+use crate::harness::item_ident;
+use crate::rules::{item_attrs, Rule};
+use crate::type_alias::types_match;
+use crate::utils::CheckResult;
+use quote::ToTokens;
+use syn::{
+    Attribute, Fields, GenericArgument, Item, ItemEnum, ItemImpl, Meta, NestedMeta, Path,
+    PathArguments, Type,
+};
+
+const ERROR_TRAITS: [(&str, &[&str]); 2] = [
+    ("std::error::Error", &["Error"]),
+    ("std::fmt::Display", &["Display", "Error"]),
+];
+
+impl<'s> Rule<'s> {
+    pub fn from_impls(error: &str) -> Self {
+        let error = error.to_string();
+        Self::new(&format!("{error} variants have From impls"))
+            .select(|item| matches!(item, Item::Enum(_)))
+            .must_crate(move |ast, items| from_impls(ast, items, &error))
+    }
+
+    pub fn error_impls(suffix: &str) -> Self {
+        let suffix = suffix.to_string();
+        Self::new(&format!("*{suffix} types implement Error and Display"))
+            .select(move |item| match item {
+                Item::Struct(s) => s.ident.to_string().ends_with(&suffix),
+                Item::Enum(e) => e.ident.to_string().ends_with(&suffix),
+                _ => false,
+            })
+            .must_crate(error_impls)
+    }
+}
+
+pub(crate) fn error_impls(ast: &CrateAst, items: &[CrateItem<'_>]) -> CheckResult {
+    let all = ast.items();
+    let impls = all
+        .iter()
+        .filter_map(|i| match i.item {
+            Item::Impl(ItemImpl {
+                trait_: Some((_, path, _)),
+                self_ty,
+                ..
+            }) => Some((path, &**self_ty)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    CheckResult::any(
+        items
+            .iter()
+            .filter(|i| matches!(i.item, Item::Struct(_) | Item::Enum(_)))
+            .filter_map(|i| Some((i, item_ident(i.item)?)))
+            .map(|(item, (kind, ident))| {
+                let derives = item_attrs(item.item).map(derives).unwrap_or_default();
+                let failures = ERROR_TRAITS
+                    .iter()
+                    .filter(|(_, derived_by)| {
+                        !derived_by
+                            .iter()
+                            .any(|d| derives.iter().any(|derive| derive == d))
+                    })
+                    .filter(|(trait_, _)| {
+                        !impls.iter().any(|(path, self_ty)| {
+                            trait_path_matches(path, trait_)
+                                && is_type_named(self_ty, &ident.to_string())
+                        })
+                    })
+                    .map(|(trait_, _)| {
+                        Failure::missing(&format!("impl {trait_} for {ident}"))
+                            .at(Some(Location::of(ident)))
+                    })
+                    .collect::<Vec<_>>();
+                item.scope(
+                    CheckResult::from(failures).in_item(Some(format!("{kind} {ident}")), None),
+                )
+            }),
+    )
+}
+
+fn derives(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|a| a.path.is_ident("derive"))
+        .filter_map(|a| match a.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|n| match n {
+            NestedMeta::Meta(meta) => meta.path().segments.last().map(|s| s.ident.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn trait_path_matches(path: &Path, expected: &str) -> bool {
+    let expected = expected.split("::").collect::<Vec<_>>();
+    path.segments.len() <= expected.len()
+        && path
+            .segments
+            .iter()
+            .rev()
+            .zip(expected.iter().rev())
+            .all(|(s, e)| s.ident == e || (*e == "std" && s.ident == "core"))
+}
+
+pub(crate) fn from_impls(ast: &CrateAst, items: &[CrateItem<'_>], error: &str) -> CheckResult {
+    let all = ast.items();
+    let impls = all
+        .iter()
+        .filter_map(|i| match i.item {
+            Item::Impl(impl_) => from_impl(impl_),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    CheckResult::any(items.iter().map(|item| match item.item {
+        Item::Enum(enum_) if enum_.ident == error => item.scope(
+            enum_from_impls(enum_, &impls).in_item(Some(format!("enum {}", enum_.ident)), None),
+        ),
+        _ => CheckResult::Success,
+    }))
+}
+
+fn enum_from_impls(enum_: &ItemEnum, impls: &[(&Type, &Type)]) -> CheckResult {
+    enum_
+        .variants
+        .iter()
+        .filter(|v| !has_from_attr(&v.attrs))
+        .filter_map(|v| match &v.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Some((v, &fields.unnamed[0])),
+            _ => None,
+        })
+        .filter(|(_, field)| !has_from_attr(&field.attrs))
+        .map(|(v, field)| (v, &field.ty))
+        .filter(|(_, inner)| {
+            !impls.iter().any(|(source, target)| {
+                types_match(source, inner) && is_type_named(target, &enum_.ident.to_string())
+            })
+        })
+        .map(|(variant, inner)| {
+            Failure::missing(&format!(
+                "impl From<{}> for {}",
+                inner.to_token_stream(),
+                enum_.ident
+            ))
+            .at(Some(Location::of(variant)))
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+fn has_from_attr(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|a| a.path.is_ident("from"))
+}
+
+fn from_impl(impl_: &ItemImpl) -> Option<(&Type, &Type)> {
+    let (_, path, _) = impl_.trait_.as_ref()?;
+    let segment = path.segments.last().filter(|s| s.ident == "From")?;
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(GenericArgument::Type(source)) => Some((source, &impl_.self_ty)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_type_named(ty: &Type, name: &str) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments.last().is_some_and(|s| s.ident == name),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_from_impls() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"pub enum Error {
+    Io(std::io::Error),
+    Parse(ParseIntError),
+    Json(#[from] serde_json::Error),
+    Http(reqwest::Error),
+    NotFound,
+    Invalid { reason: String },
+}
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self { Error::Io(e) }
+}
+mod convert {
+    impl From<reqwest::Error> for crate::Error {
+        fn from(e: reqwest::Error) -> Self { crate::Error::Http(e) }
+    }
+}
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let failures = Rule::from_impls("Error").check(&ast).into_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(
+            failures[0].message(),
+            "Missing impl From<ParseIntError> for Error"
+        );
+        assert_eq!(failures[0].path, vec!["crate", "enum Error"]);
+        assert_eq!(failures[0].location.as_ref().map(|l| l.line), Some(3));
+        assert!(Rule::from_impls("Other").check(&ast).as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_impls() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"use std::{error, fmt};
+#[derive(Debug)]
+pub struct ParseError;
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { todo!() }
+}
+impl error::Error for ParseError {}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApiError {}
+
+#[derive(Debug, derive_more::Display)]
+pub struct IoError;
+
+pub struct LoadError;
+impl core::error::Error for LoadError {}
+
+pub struct Parser;
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let failures = Rule::error_impls("Error").check(&ast).into_failures();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "Missing impl std::error::Error for IoError".to_string(),
+                    Some(13)
+                ),
+                (
+                    "Missing impl std::fmt::Display for LoadError".to_string(),
+                    Some(15)
+                ),
+            ]
+        );
+        assert_eq!(failures[0].path, vec!["crate", "struct IoError"]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use crate::cfg::CfgSet;
+use crate::crate_ast::{CrateAst, CrateItem};
+use crate::failure::{Failure, Location};
+use crate::file::HasItems;
+use crate::rules::Rule;
+use crate::utils::CheckResult;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use syn::{Attribute, ImplItem, Item, Visibility};
+
+impl<'s> Rule<'s> {
+    pub fn test_coverage(pattern: &str) -> Self {
+        let pattern = pattern.to_string();
+        Self::new("public fns are tested")
+            .must_crate(move |ast, items| test_coverage(ast, items, &pattern))
+    }
+}
+
+pub(crate) fn test_coverage(ast: &CrateAst, items: &[CrateItem<'_>], pattern: &str) -> CheckResult {
+    match test_fns(ast) {
+        Ok(tests) => coverage(ast, items, &tests, pattern),
+        Err(failure) => failure,
+    }
+}
+
+fn coverage(
+    ast: &CrateAst,
+    items: &[CrateItem<'_>],
+    tests: &BTreeSet<String>,
+    pattern: &str,
+) -> CheckResult {
+    let test_modules = test_modules(ast);
+    CheckResult::any(
+        items
+            .iter()
+            .filter(|i| !in_test_module(i, &test_modules))
+            .map(|item| {
+                let fns = match item.item {
+                    Item::Fn(f) if is_pub(&f.vis) => vec![&f.sig.ident],
+                    Item::Impl(impl_) if impl_.trait_.is_none() => impl_
+                        .items
+                        .iter()
+                        .filter_map(|i| match i {
+                            ImplItem::Method(m) if is_pub(&m.vis) => Some(&m.sig.ident),
+                            _ => None,
+                        })
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                let failures = fns
+                    .into_iter()
+                    .filter(|ident| {
+                        let expected = pattern.replace("{name}", &ident.to_string());
+                        !tests.iter().any(|test| test.contains(&expected))
+                    })
+                    .map(|ident| {
+                        Failure::missing(&format!(
+                            "test matching '{}'",
+                            pattern.replace("{name}", &ident.to_string())
+                        ))
+                        .at(Some(Location::of(ident)))
+                    })
+                    .collect::<Vec<_>>();
+                item.scope(failures.into())
+            }),
+    )
+}
+
+pub(crate) fn test_modules(ast: &CrateAst) -> Vec<String> {
+    ast.items()
+        .into_iter()
+        .filter(|i| matches!(i.item, Item::Mod(m) if is_test_only(&m.attrs)))
+        .filter_map(|i| i.path())
+        .collect()
+}
+
+pub(crate) fn in_test_module(item: &CrateItem<'_>, test_modules: &[String]) -> bool {
+    test_modules.iter().any(|m| {
+        item.module == *m
+            || item
+                .module
+                .strip_prefix(m.as_str())
+                .is_some_and(|rest| rest.starts_with("::"))
+    })
+}
+
+fn test_fns(ast: &CrateAst) -> Result<BTreeSet<String>, CheckResult> {
+    let mut tests = BTreeSet::new();
+    collect_tests(ast, &mut tests);
+
+    let dir = ast
+        .root
+        .parent()
+        .and_then(Path::parent)
+        .map(|p| p.join("tests"));
+    let entries = match dir.as_ref().map(fs::read_dir) {
+        Some(Ok(entries)) => entries,
+        _ => return Ok(tests),
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_some_and(|e| e == "rs") {
+            collect_tests(&CrateAst::from_root(&path)?, &mut tests);
+        }
+    }
+    Ok(tests)
+}
+
+fn collect_tests(ast: &CrateAst, out: &mut BTreeSet<String>) {
+    for (_, file) in ast.files() {
+        out.extend(
+            file.file
+                .walk_items()
+                .into_iter()
+                .filter_map(|item| match item {
+                    Item::Fn(f) if is_test(&f.attrs) => Some(f.sig.ident.to_string()),
+                    _ => None,
+                }),
+        );
+    }
+}
+
+fn is_test_only(attrs: &[Attribute]) -> bool {
+    !CfgSet::new().is_enabled(attrs) && CfgSet::new().with_flag("test").is_enabled(attrs)
+}
+
+pub(crate) fn is_test(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .any(|a| a.path.segments.last().is_some_and(|s| s.ident == "test"))
+}
+
+fn is_pub(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_test_coverage() -> Result<(), TestError> {
+        let dir = TestDir::new(&[
+            (
+                "src/lib.rs",
+                r#"
+pub fn parse() {}
+pub fn render() {}
+fn private() {}
+pub struct Server;
+impl Server { pub fn start(&self) {} }
+
+#[cfg(test)]
+mod tests {
+    pub fn helper() {}
+    #[test]
+    fn test_parse() {}
+}
+"#,
+            ),
+            (
+                "tests/server.rs",
+                "#[tokio::test]\nasync fn test_start() {}\n",
+            ),
+        ])?;
+
+        let rule = Rule::test_coverage("test_{name}");
+        let results = [
+            CrateAst::load(&dir).map(|ast| rule.check(&ast)),
+            CrateAst::load_with(&dir, &CfgSet::new()).map(|ast| rule.check(&ast)),
+        ];
+
+        for results in results {
+            let failures = results?.into_failures();
+            assert_eq!(failures.len(), 1);
+            assert_eq!(failures[0].message(), "Missing test matching 'test_render'");
+            assert_eq!(failures[0].location.as_ref().map(|l| l.line), Some(3));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,469 @@
+use crate::cfg::CfgSet;
+use crate::failure::{Failure, Location};
+use crate::file::{HasInnerAttrs, HasItems};
+use crate::harness::item_ident;
+use crate::imports::ImportScope;
+use crate::source::SourceFile;
+use crate::utils::CheckResult;
+use std::path::{Path, PathBuf};
+use syn::{Item, ItemMod, Lit, Meta};
+
+#[derive(Debug, Clone)]
+pub struct CrateAst {
+    pub root: PathBuf,
+    cfg: Option<CfgSet>,
+    files: Vec<(String, SourceFile)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CrateItem<'a> {
+    pub module: String,
+    pub file: &'a SourceFile,
+    pub item: &'a Item,
+}
+
+impl CrateItem<'_> {
+    pub fn location(&self) -> Location {
+        self.file.location(self.item)
+    }
+
+    pub fn scope(&self, result: CheckResult) -> CheckResult {
+        result
+            .at(Some(self.location()))
+            .in_file(&self.file.name())
+            .within(&self.module)
+    }
+
+    pub fn path(&self) -> Option<String> {
+        item_ident(self.item).map(|(_, ident)| format!("{}::{ident}", self.module))
+    }
+}
+
+impl CrateAst {
+    pub fn load<P>(dir: P) -> Result<Self, CheckResult>
+    where
+        P: AsRef<Path>,
+    {
+        Self::load_cfg(dir, None)
+    }
+
+    pub fn load_with<P>(dir: P, cfg: &CfgSet) -> Result<Self, CheckResult>
+    where
+        P: AsRef<Path>,
+    {
+        Self::load_cfg(dir, Some(cfg))
+    }
+
+    fn load_cfg<P>(dir: P, cfg: Option<&CfgSet>) -> Result<Self, CheckResult>
+    where
+        P: AsRef<Path>,
+    {
+        let src = dir.as_ref().join("src");
+        match ["lib.rs", "main.rs"]
+            .iter()
+            .map(|f| src.join(f))
+            .find(|p| p.is_file())
+        {
+            Some(root) => Self::from_root_cfg(root, cfg),
+            None => Err(CheckResult::missing(&format!(
+                "crate root in '{}'",
+                src.display()
+            ))),
+        }
+    }
+
+    pub fn from_root<P>(root: P) -> Result<Self, CheckResult>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_root_cfg(root, None)
+    }
+
+    pub fn from_root_with<P>(root: P, cfg: &CfgSet) -> Result<Self, CheckResult>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_root_cfg(root, Some(cfg))
+    }
+
+    pub(crate) fn load_sibling(&self, file: &str) -> Result<Self, CheckResult> {
+        let root = self.root.with_file_name(file);
+        match root.is_file() {
+            true => Self::from_root_cfg(root, self.cfg.as_ref()),
+            false => Err(CheckResult::missing(&format!(
+                "crate root '{}'",
+                root.display()
+            ))),
+        }
+    }
+
+    fn from_root_cfg<P>(root: P, cfg: Option<&CfgSet>) -> Result<Self, CheckResult>
+    where
+        P: AsRef<Path>,
+    {
+        let mut ast = Self {
+            root: root.as_ref().to_path_buf(),
+            cfg: cfg.cloned(),
+            files: Default::default(),
+        };
+        let mut failures = Vec::new();
+        let dir = parent(root.as_ref());
+        ast.load_file(root.as_ref(), dir, "crate".to_string(), &mut failures);
+
+        if failures.is_empty() {
+            Ok(ast)
+        } else {
+            Err(failures.into())
+        }
+    }
+
+    fn load_file(
+        &mut self,
+        path: &Path,
+        dir: PathBuf,
+        module: String,
+        failures: &mut Vec<Failure>,
+    ) {
+        let file = match SourceFile::load(path) {
+            Ok(file) => match &self.cfg {
+                Some(cfg) => file.with_cfg(cfg),
+                None => file,
+            },
+            Err(failure) => {
+                failures.extend(failure.into_failures());
+                return;
+            }
+        };
+
+        let mut children = Vec::new();
+        collect_mods(
+            &file.items,
+            &parent(path),
+            &dir,
+            &module,
+            self.cfg.as_ref(),
+            &mut children,
+        );
+        self.files.push((module, file));
+
+        for (module, candidates, name) in children {
+            match candidates.into_iter().find(|(path, _)| path.is_file()) {
+                Some((path, dir)) => self.load_file(&path, dir, module, failures),
+                None => failures
+                    .push(Failure::missing(&format!("file for module '{module}'")).within(&name)),
+            }
+        }
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = (&str, &SourceFile)> {
+        self.files
+            .iter()
+            .map(|(module, file)| (module.as_str(), file))
+    }
+
+    pub fn items(&self) -> Vec<CrateItem<'_>> {
+        let mut items = Vec::new();
+        for (module, file) in &self.files {
+            walk(&file.items, module, file, self.cfg.as_ref(), &mut items);
+        }
+        items
+    }
+
+    pub fn has_crate_docs(&self, sections: &[&str]) -> CheckResult {
+        match self.files.first() {
+            Some((_, root)) => root.has_inner_docs(sections).within(&root.name()),
+            None => CheckResult::missing("crate root"),
+        }
+    }
+
+    pub fn item_at(&self, path: &str) -> Option<CrateItem<'_>> {
+        let path = qualify(path);
+        self.items()
+            .into_iter()
+            .find(|item| item.path().as_deref() == Some(path.as_str()))
+    }
+
+    pub fn has_item_at(&self, path: &str) -> CheckResult {
+        match self.item_at(path) {
+            Some(_) => CheckResult::Success,
+            None => CheckResult::missing(&format!("item '{}'", qualify(path))),
+        }
+    }
+
+    pub fn has_fn_at(&self, path: &str) -> CheckResult {
+        self.has_kind_at("fn", path)
+    }
+
+    pub fn has_struct_at(&self, path: &str) -> CheckResult {
+        self.has_kind_at("struct", path)
+    }
+
+    pub fn has_trait_at(&self, path: &str) -> CheckResult {
+        self.has_kind_at("trait", path)
+    }
+
+    fn has_kind_at(&self, kind: &str, path: &str) -> CheckResult {
+        let path = qualify(path);
+        if let Some(item) = self.item_at(&path) {
+            let actual = item_ident(item.item).map_or("item", |(kind, _)| kind);
+            return if actual == kind {
+                CheckResult::Success
+            } else {
+                CheckResult::Failure(vec![Failure::mismatch(
+                    &format!("kind of '{path}'"),
+                    kind,
+                    actual,
+                )
+                .at(Some(item.location()))])
+            };
+        }
+
+        let name = path.rsplit("::").next().unwrap_or_default();
+        let elsewhere = self.items().into_iter().find(|item| {
+            item_ident(item.item).is_some_and(|(k, ident)| k == kind && ident == name)
+        });
+        match elsewhere {
+            Some(item) => CheckResult::Failure(vec![Failure::mismatch(
+                &format!("path of {kind} '{name}'"),
+                &path,
+                &item.path().unwrap_or_default(),
+            )
+            .at(Some(item.location()))]),
+            None => CheckResult::missing(&format!("{kind} '{path}'")),
+        }
+    }
+}
+
+fn qualify(path: &str) -> String {
+    if path == "crate" || path.starts_with("crate::") {
+        path.to_string()
+    } else {
+        format!("crate::{path}")
+    }
+}
+
+impl HasItems for CrateAst {
+    fn walk_items(&self) -> Vec<&Item> {
+        self.files
+            .iter()
+            .flat_map(|(_, file)| file.walk_items())
+            .collect()
+    }
+
+    fn walk_scopes(&self) -> Vec<(ImportScope, Vec<&Item>)> {
+        self.files
+            .iter()
+            .flat_map(|(_, file)| file.walk_scopes())
+            .collect()
+    }
+}
+
+fn walk<'a>(
+    items: &'a [Item],
+    module: &str,
+    file: &'a SourceFile,
+    cfg: Option<&CfgSet>,
+    out: &mut Vec<CrateItem<'a>>,
+) {
+    for item in items {
+        if cfg.is_some_and(|cfg| !cfg.is_item_enabled(item)) {
+            continue;
+        }
+        out.push(CrateItem {
+            module: module.to_string(),
+            file,
+            item,
+        });
+        if let Item::Mod(ItemMod {
+            ident,
+            content: Some((_, items)),
+            ..
+        }) = item
+        {
+            walk(items, &format!("{module}::{ident}"), file, cfg, out);
+        }
+    }
+}
+
+type ChildMod = (String, Vec<(PathBuf, PathBuf)>, String);
+
+fn collect_mods(
+    items: &[Item],
+    base: &Path,
+    dir: &Path,
+    module: &str,
+    cfg: Option<&CfgSet>,
+    out: &mut Vec<ChildMod>,
+) {
+    for item in items {
+        if let Item::Mod(item_mod) = item {
+            if cfg.is_some_and(|cfg| !cfg.is_enabled(&item_mod.attrs)) {
+                continue;
+            }
+            let name = item_mod.ident.to_string();
+            let child = format!("{module}::{name}");
+            match &item_mod.content {
+                Some((_, items)) => {
+                    let dir = dir.join(&name);
+                    collect_mods(items, &dir, &dir, &child, cfg, out)
+                }
+                None => {
+                    let candidates = match path_attr(item_mod) {
+                        Some(path) => {
+                            let path = base.join(path);
+                            let dir = parent(&path);
+                            vec![(path, dir)]
+                        }
+                        None => vec![
+                            (dir.join(format!("{name}.rs")), dir.join(&name)),
+                            (dir.join(&name).join("mod.rs"), dir.join(&name)),
+                        ],
+                    };
+                    out.push((child, candidates, format!("mod {name}")));
+                }
+            }
+        }
+    }
+}
+
+fn path_attr(item_mod: &ItemMod) -> Option<String> {
+    item_mod
+        .attrs
+        .iter()
+        .filter(|a| a.path.is_ident("path"))
+        .find_map(|a| match a.parse_meta() {
+            Ok(Meta::NameValue(nv)) => match nv.lit {
+                Lit::Str(s) => Some(s.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+}
+
+fn parent(path: &Path) -> PathBuf {
+    path.parent().map(Path::to_path_buf).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_load() -> Result<(), TestError> {
+        let dir = TestDir::new(&[
+            (
+                "src/lib.rs",
+                "mod api;\n#[path = \"gen/out.rs\"]\nmod generated;\nmod inline { mod nested; }\n",
+            ),
+            ("src/api/mod.rs", "pub mod v1;\n"),
+            ("src/api/v1.rs", "mod users;\n"),
+            ("src/api/v1/users.rs", "\npub fn list() {}\n"),
+            ("src/gen/out.rs", "pub struct Generated;\n"),
+            ("src/inline/nested.rs", "struct Nested;\n"),
+        ])?;
+
+        let ast = CrateAst::load(&dir)?;
+        let missing = {
+            dir.write("src/lib.rs", "#[cfg(feature = \"x\")]\nmod missing;\n")?;
+            CrateAst::load(&dir)
+        };
+        let missing_cfg = CrateAst::load_with(&dir, &CfgSet::new());
+
+        assert_eq!(
+            ast.files().map(|(module, _)| module).collect::<Vec<_>>(),
+            vec![
+                "crate",
+                "crate::api",
+                "crate::api::v1",
+                "crate::api::v1::users",
+                "crate::generated",
+                "crate::inline::nested",
+            ]
+        );
+
+        let items = ast.items();
+        let list = items
+            .iter()
+            .find(|i| matches!(i.item, Item::Fn(_)))
+            .unwrap();
+        assert_eq!(list.module, "crate::api::v1::users");
+        let location = list.location();
+        assert_eq!(location.line, 2);
+        assert!(location.file.unwrap().ends_with("users.rs"));
+        assert!(!ast.implements("Debug", "Generated").as_bool());
+
+        assert_eq!(
+            ast.item_at("crate::generated::Generated")
+                .and_then(|item| item.path()),
+            Some("crate::generated::Generated".to_string())
+        );
+        assert!(ast.has_fn_at("crate::api::v1::users::list").as_bool());
+        assert!(ast.has_struct_at("inline::nested::Nested").as_bool());
+        assert_eq!(
+            ast.has_fn_at("crate::api::list").into_failures()[0].message(),
+            "Expected path of fn 'list' to be 'crate::api::list', got 'crate::api::v1::users::list'"
+        );
+        assert_eq!(
+            ast.has_fn_at("crate::generated::Generated").into_failures()[0].message(),
+            "Expected kind of 'crate::generated::Generated' to be 'fn', got 'struct'"
+        );
+        assert!(!ast.has_item_at("crate::api::v2").as_bool());
+
+        let missing_cfg = missing_cfg.unwrap();
+        assert_eq!(missing_cfg.files().count(), 1);
+        assert!(missing_cfg.items().is_empty());
+        assert!(missing_cfg.walk_items().is_empty());
+        assert_eq!(
+            ast.has_crate_docs(&[]).into_failures()[0].message(),
+            "Missing inner doc comment"
+        );
+        assert_eq!(
+            missing.unwrap_err().into_failures()[0].message(),
+            "Missing file for module 'crate::missing'"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_path_attrs() -> Result<(), TestError> {
+        let dir = TestDir::new(&[
+            (
+                "src/lib.rs",
+                "mod outer { #[path = \"impl.rs\"] mod imp; }\n#[path = \"gen/out.rs\"]\nmod generated;\n",
+            ),
+            ("src/outer/impl.rs", "struct Imp;\n"),
+            ("src/gen/out.rs", "mod child;\n"),
+            ("src/gen/child.rs", "struct Child;\n"),
+            ("src/client.rs", "mod shared { #[path = \"wire.rs\"] mod wire; }\n"),
+        ])?;
+
+        let ast = CrateAst::load(&dir)?;
+        assert_eq!(
+            ast.files().map(|(module, _)| module).collect::<Vec<_>>(),
+            vec![
+                "crate",
+                "crate::outer::imp",
+                "crate::generated",
+                "crate::generated::child",
+            ]
+        );
+        assert!(ast.has_struct_at("crate::outer::imp::Imp").as_bool());
+        assert!(ast
+            .has_struct_at("crate::generated::child::Child")
+            .as_bool());
+
+        dir.write("src/lib.rs", "mod client;\n")?;
+        dir.write("src/client/shared/wire.rs", "struct Wire;\n")?;
+        let ast = CrateAst::load(&dir)?;
+        assert!(ast
+            .has_struct_at("crate::client::shared::wire::Wire")
+            .as_bool());
+
+        Ok(())
+    }
+}
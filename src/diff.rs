@@ -0,0 +1,339 @@
+use crate::color::{paint, Style};
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use std::fmt::{self, Display};
+
+const CONTEXT: usize = 3;
+const MAX_EDITS: isize = 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffOp {
+    Same(String),
+    Removed(String),
+    Added(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diff {
+    pub ops: Vec<DiffOp>,
+}
+
+impl Diff {
+    pub fn tokens(expected: TokenStream, actual: TokenStream) -> Self {
+        let mut expected_tokens = Vec::new();
+        diff_tokens(expected, &mut expected_tokens);
+        expected_tokens.retain(|t| !t.is_empty());
+        let mut actual_tokens = Vec::new();
+        diff_tokens(actual, &mut actual_tokens);
+        actual_tokens.retain(|t| !t.is_empty());
+
+        Self::new(&expected_tokens, &actual_tokens)
+    }
+
+    pub fn new(expected: &[String], actual: &[String]) -> Self {
+        let prefix = expected
+            .iter()
+            .zip(actual)
+            .take_while(|(e, a)| e == a)
+            .count();
+        let (expected_rest, actual_rest) = (&expected[prefix..], &actual[prefix..]);
+        let suffix = expected_rest
+            .iter()
+            .rev()
+            .zip(actual_rest.iter().rev())
+            .take_while(|(e, a)| e == a)
+            .count();
+        let expected_mid = &expected_rest[..expected_rest.len() - suffix];
+        let actual_mid = &actual_rest[..actual_rest.len() - suffix];
+
+        let mut ops = expected[..prefix]
+            .iter()
+            .cloned()
+            .map(DiffOp::Same)
+            .collect::<Vec<_>>();
+        match myers(expected_mid, actual_mid) {
+            Some(mid) => ops.extend(mid),
+            None => {
+                ops.extend(expected_mid.iter().cloned().map(DiffOp::Removed));
+                ops.extend(actual_mid.iter().cloned().map(DiffOp::Added));
+            }
+        }
+        ops.extend(
+            expected_rest[expected_mid.len()..]
+                .iter()
+                .cloned()
+                .map(DiffOp::Same),
+        );
+
+        Self { ops }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops.iter().all(|op| matches!(op, DiffOp::Same(_)))
+    }
+
+    pub fn render(&self, color: bool) -> String {
+        let mut parts = Vec::new();
+        let mut visible = self.visible().into_iter().peekable();
+        while let Some(op) = visible.next() {
+            match op {
+                None => parts.push(paint("...", Style::Context, color)),
+                Some(DiffOp::Same(t)) => parts.push(paint(t, Style::Context, color)),
+                Some(DiffOp::Removed(t)) => {
+                    let mut group = vec![t.as_str()];
+                    while let Some(Some(DiffOp::Removed(t))) = visible.peek() {
+                        group.push(t);
+                        visible.next();
+                    }
+                    parts.push(paint(
+                        &format!("[-{}-]", group.join(" ")),
+                        Style::Expected,
+                        color,
+                    ));
+                }
+                Some(DiffOp::Added(t)) => {
+                    let mut group = vec![t.as_str()];
+                    while let Some(Some(DiffOp::Added(t))) = visible.peek() {
+                        group.push(t);
+                        visible.next();
+                    }
+                    parts.push(paint(
+                        &format!("{{+{}+}}", group.join(" ")),
+                        Style::Actual,
+                        color,
+                    ));
+                }
+            }
+        }
+        parts.join(" ")
+    }
+
+    pub fn render_sides(&self, color: bool) -> (String, String) {
+        let (mut expected, mut actual) = (Vec::new(), Vec::new());
+        for op in self.visible() {
+            match op {
+                None => {
+                    expected.push(paint("...", Style::Context, color));
+                    actual.push(paint("...", Style::Context, color));
+                }
+                Some(DiffOp::Same(t)) => {
+                    expected.push(paint(t, Style::Context, color));
+                    actual.push(paint(t, Style::Context, color));
+                }
+                Some(DiffOp::Removed(t)) => expected.push(paint(t, Style::Expected, color)),
+                Some(DiffOp::Added(t)) => actual.push(paint(t, Style::Actual, color)),
+            }
+        }
+        (expected.join(" "), actual.join(" "))
+    }
+
+    pub(crate) fn visible(&self) -> Vec<Option<&DiffOp>> {
+        let changed = self
+            .ops
+            .iter()
+            .enumerate()
+            .filter(|(_, op)| !matches!(op, DiffOp::Same(_)))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        let near_change = |i: usize| {
+            changed
+                .iter()
+                .any(|&c| i + CONTEXT >= c && i <= c + CONTEXT)
+        };
+
+        let mut visible = Vec::new();
+        for (i, op) in self.ops.iter().enumerate() {
+            if near_change(i) {
+                visible.push(Some(op));
+            } else if visible.last().is_none_or(Option::is_some) {
+                visible.push(None);
+            }
+        }
+        visible
+    }
+}
+
+fn diff_tokens(tokens: TokenStream, out: &mut Vec<String>) {
+    let mut joint = false;
+    for tt in tokens {
+        let next_joint = matches!(&tt, TokenTree::Punct(p) if p.spacing() == Spacing::Joint);
+        let token = match tt {
+            TokenTree::Group(g) => {
+                let (open, close) = match g.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::None => ("", ""),
+                };
+                out.push(open.to_string());
+                diff_tokens(g.stream(), out);
+                out.push(close.to_string());
+                joint = false;
+                continue;
+            }
+            tt => tt.to_string(),
+        };
+        match out.last_mut() {
+            Some(last) if joint => last.push_str(&token),
+            _ => out.push(token),
+        }
+        joint = next_joint;
+    }
+}
+
+fn myers(expected: &[String], actual: &[String]) -> Option<Vec<DiffOp>> {
+    let (n, m) = (expected.len() as isize, actual.len() as isize);
+    let limit = (n + m).min(MAX_EDITS);
+    let mut v = vec![0isize; 2 * limit as usize + 3];
+    let at = |k: isize| (k + limit + 1) as usize;
+    let mut trace = Vec::new();
+
+    for d in 0..=limit {
+        trace.push(v[at(-d - 1)..=at(d + 1)].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[at(k - 1)] < v[at(k + 1)]) {
+                v[at(k + 1)]
+            } else {
+                v[at(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && expected[x as usize] == actual[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[at(k)] = x;
+            if x >= n && y >= m {
+                return Some(backtrack(expected, actual, &trace));
+            }
+        }
+    }
+    None
+}
+
+fn backtrack(expected: &[String], actual: &[String], trace: &[Vec<isize>]) -> Vec<DiffOp> {
+    let mut ops = Vec::new();
+    let (mut x, mut y) = (expected.len() as isize, actual.len() as isize);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let at = |k: isize| (k + d + 1) as usize;
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && v[at(k - 1)] < v[at(k + 1)]) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = v[at(prev_k)];
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            ops.push(DiffOp::Same(expected[x as usize - 1].clone()));
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            if x == prev_x {
+                ops.push(DiffOp::Added(actual[y as usize - 1].clone()));
+            } else {
+                ops.push(DiffOp::Removed(expected[x as usize - 1].clone()));
+            }
+        }
+        (x, y) = (prev_x, prev_y);
+    }
+    ops.reverse();
+    ops
+}
+
+impl Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let diff = Diff::tokens(
+            quote::quote! { { let a = 1; let b = 2; let c = a + b; println!("{}", c); } },
+            quote::quote! { { let a = 1; let b = 3; let c = a + b; println!("{}", c); } },
+        );
+
+        assert!(!diff.is_empty());
+        assert_eq!(diff.to_string(), "... let b = [-2-] {+3+} ; let c ...");
+    }
+
+    #[test]
+    fn test_diff_color() {
+        let diff = Diff::tokens(quote::quote! { a + b }, quote::quote! { a - b });
+
+        assert_eq!(
+            diff.render(true),
+            "\x1b[2ma\x1b[0m \x1b[32m[-+-]\x1b[0m \x1b[31m{+-+}\x1b[0m \x1b[2mb\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_render_sides() {
+        let diff = Diff::tokens(
+            quote::quote! { fn fetch(&self) -> u32 },
+            quote::quote! { async fn fetch(&self) -> u64 },
+        );
+
+        assert_eq!(
+            diff.render_sides(false),
+            (
+                "fn fetch ( ... self ) -> u32".to_string(),
+                "async fn fetch ( ... self ) -> u64".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_diff_ops() {
+        let tokens = |s: &str| s.split(' ').map(String::from).collect::<Vec<_>>();
+        let diff = Diff::new(&tokens("a b c a b b a"), &tokens("c b a b a c"));
+
+        let expected = diff
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                DiffOp::Same(t) | DiffOp::Removed(t) => Some(t.as_str()),
+                DiffOp::Added(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let actual = diff
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                DiffOp::Same(t) | DiffOp::Added(t) => Some(t.as_str()),
+                DiffOp::Removed(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let same = diff
+            .ops
+            .iter()
+            .filter(|op| matches!(op, DiffOp::Same(_)))
+            .count();
+        assert_eq!(expected.join(" "), "a b c a b b a");
+        assert_eq!(actual.join(" "), "c b a b a c");
+        assert_eq!(same, 4);
+    }
+
+    #[test]
+    fn test_diff_large() {
+        let expected = (0..5_000).map(|i| format!("e{i}")).collect::<Vec<_>>();
+        let actual = (0..5_000).map(|i| format!("a{i}")).collect::<Vec<_>>();
+        let diff = Diff::new(&expected, &actual);
+
+        assert_eq!(diff.ops.len(), 10_000);
+        assert_eq!(diff.ops[0], DiffOp::Removed("e0".to_string()));
+        assert_eq!(diff.ops[5_000], DiffOp::Added("a0".to_string()));
+    }
+
+    #[test]
+    fn test_diff_empty() {
+        let diff = Diff::tokens(quote::quote! { { a } }, quote::quote! { { a } });
+
+        assert!(diff.is_empty());
+    }
+}
@@ -0,0 +1,211 @@
+use crate::crate_ast::CrateItem;
+use crate::failure::{Failure, Location};
+use crate::harness::item_ident;
+use crate::rules::{item_attrs, Rule};
+use crate::utils::{attr_paths, CheckResult};
+use syn::{Attribute, Fields, Ident, ImplItem, Item, Meta, NestedMeta, Visibility};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocPolicy {
+    members: bool,
+    exempt_attrs: Vec<String>,
+    exempt_paths: Vec<String>,
+}
+
+impl DocPolicy {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_members(self) -> Self {
+        Self {
+            members: true,
+            ..self
+        }
+    }
+
+    pub fn exempt_attr(mut self, path: &str) -> Self {
+        self.exempt_attrs.push(path.to_string());
+        self
+    }
+
+    pub fn exempt_path(mut self, path: &str) -> Self {
+        self.exempt_paths.push(path.to_string());
+        self
+    }
+
+    pub fn check(&self, item: &CrateItem<'_>) -> CheckResult {
+        let mut failures = Vec::new();
+        let mut require = |kind: &str, ident: &Ident, attrs: &[Attribute], path: String| {
+            if !self.is_exempt(attrs, &path) && !has_docs(attrs) {
+                failures.push(
+                    Failure::missing(&format!("doc comment on {kind} '{ident}'"))
+                        .at(Some(Location::of(ident))),
+                );
+            }
+        };
+
+        let path = item.path().unwrap_or_else(|| item.module.clone());
+        if is_pub_item(item.item) {
+            if let Some((kind, ident)) = item_ident(item.item) {
+                require(kind, ident, attrs_of(item.item), path.clone());
+            }
+        }
+        if self.members && !self.is_exempt(attrs_of(item.item), &path) {
+            match item.item {
+                Item::Struct(s) if is_pub(&s.vis) => {
+                    if let Fields::Named(fields) = &s.fields {
+                        for field in fields.named.iter().filter(|f| is_pub(&f.vis)) {
+                            if let Some(ident) = &field.ident {
+                                require("field", ident, &field.attrs, format!("{path}::{ident}"));
+                            }
+                        }
+                    }
+                }
+                Item::Enum(e) if is_pub(&e.vis) => {
+                    for variant in &e.variants {
+                        let ident = &variant.ident;
+                        require("variant", ident, &variant.attrs, format!("{path}::{ident}"));
+                    }
+                }
+                _ => (),
+            }
+        }
+        if let Item::Impl(impl_) = item.item {
+            if impl_.trait_.is_none() && !self.is_exempt(&impl_.attrs, &path) {
+                for method in impl_.items.iter().filter_map(|i| match i {
+                    ImplItem::Method(m) if is_pub(&m.vis) => Some(m),
+                    _ => None,
+                }) {
+                    let ident = &method.sig.ident;
+                    require("fn", ident, &method.attrs, format!("{path}::{ident}"));
+                }
+            }
+        }
+
+        failures.into()
+    }
+
+    fn is_exempt(&self, attrs: &[Attribute], path: &str) -> bool {
+        let paths = attr_paths(attrs);
+        is_hidden(attrs)
+            || self.exempt_attrs.iter().any(|a| paths.contains(a))
+            || self.exempt_paths.iter().any(|p| {
+                path == p
+                    || path
+                        .strip_prefix(p.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+    }
+}
+
+impl<'s> Rule<'s> {
+    pub fn documented(policy: DocPolicy) -> Self {
+        Self::new("public items are documented").must_crate_item(move |item| policy.check(item))
+    }
+}
+
+fn is_pub(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+fn is_pub_item(item: &Item) -> bool {
+    match item {
+        Item::Const(i) => is_pub(&i.vis),
+        Item::Enum(i) => is_pub(&i.vis),
+        Item::Fn(i) => is_pub(&i.vis),
+        Item::Mod(i) => is_pub(&i.vis),
+        Item::Static(i) => is_pub(&i.vis),
+        Item::Struct(i) => is_pub(&i.vis),
+        Item::Trait(i) => is_pub(&i.vis),
+        Item::Type(i) => is_pub(&i.vis),
+        Item::Union(i) => is_pub(&i.vis),
+        _ => false,
+    }
+}
+
+fn attrs_of(item: &Item) -> &[Attribute] {
+    item_attrs(item).unwrap_or_default()
+}
+
+fn has_docs(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|a| a.path.is_ident("doc"))
+        .any(|a| matches!(a.parse_meta(), Ok(Meta::NameValue(_))))
+}
+
+fn is_hidden(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|a| a.path.is_ident("doc"))
+        .any(|a| match a.parse_meta() {
+            Ok(Meta::List(list)) => list
+                .nested
+                .iter()
+                .any(|n| matches!(n, NestedMeta::Meta(Meta::Path(p)) if p.is_ident("hidden"))),
+            _ => false,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crate_ast::CrateAst;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_documented() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"
+/// A server.
+pub struct Server { pub port: u16, running: bool }
+pub enum Mode { Fast }
+impl Server { pub fn start(&self) {} fn stop(&self) {} }
+#[doc(hidden)]
+pub fn hidden() {}
+#[generated]
+pub fn tagged() {}
+fn private() {}
+pub mod generated { pub fn skipped() {} }
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let messages = |policy| {
+            Rule::documented(policy)
+                .check(&ast)
+                .into_failures()
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>()
+        };
+
+        let policy = DocPolicy::new()
+            .exempt_attr("generated")
+            .exempt_path("crate::generated");
+        assert_eq!(
+            messages(policy.clone()),
+            vec![
+                ("Missing doc comment on enum 'Mode'".to_string(), Some(4)),
+                ("Missing doc comment on fn 'start'".to_string(), Some(5)),
+            ]
+        );
+        assert_eq!(
+            messages(policy.with_members()),
+            vec![
+                ("Missing doc comment on field 'port'".to_string(), Some(3)),
+                ("Missing doc comment on enum 'Mode'".to_string(), Some(4)),
+                ("Missing doc comment on variant 'Fast'".to_string(), Some(4)),
+                ("Missing doc comment on fn 'start'".to_string(), Some(5)),
+            ]
+        );
+        assert_eq!(messages(DocPolicy::new()).len(), 5);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,196 @@
+use crate::crate_ast::CrateAst;
+use crate::failure::{Failure, Location};
+use crate::imports::Import;
+use crate::rules::Rule;
+use crate::utils::CheckResult;
+use quote::ToTokens;
+use std::collections::BTreeMap;
+use syn::visit::{self, Visit};
+use syn::{Expr, ExprCall, Item, ItemFn};
+
+impl<'s> Rule<'s> {
+    pub fn thin_main(lib: &str, max_statements: usize) -> Self {
+        let lib = lib.to_string();
+        Self::new("thin main").must_crate(move |ast, _| thin_main(ast, &lib, max_statements))
+    }
+}
+
+pub(crate) fn thin_main(ast: &CrateAst, lib: &str, max_statements: usize) -> CheckResult {
+    if ast.root.ends_with("main.rs") {
+        return check_main(ast, lib, max_statements);
+    }
+    match ast.load_sibling("main.rs") {
+        Ok(bin) => check_main(&bin, lib, max_statements),
+        Err(failure) => failure,
+    }
+}
+
+fn check_main(ast: &CrateAst, lib: &str, max_statements: usize) -> CheckResult {
+    let items = ast.items();
+    let main = items.iter().find_map(|i| match i.item {
+        Item::Fn(func) if i.module == "crate" && func.sig.ident == "main" => Some((i, func)),
+        _ => None,
+    });
+    let (item, func) = match main {
+        Some(main) => main,
+        None => return CheckResult::missing("fn main"),
+    };
+
+    let imports = item
+        .file
+        .items
+        .iter()
+        .filter_map(|i| match i {
+            Item::Use(item_use) => Some(Import::of(item_use)),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|i| Some((i.name()?.to_string(), i.path.clone())))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut failures = Vec::new();
+    let statements = func.block.stmts.len();
+    if statements > max_statements {
+        failures.push(
+            Failure::mismatch(
+                "statement count of fn main",
+                &format!("at most {max_statements}"),
+                &statements.to_string(),
+            )
+            .at(Some(Location::of(&func.sig.ident))),
+        );
+    }
+
+    let mut calls = CallVisitor(Vec::new());
+    calls.visit_item_fn(func);
+    failures.extend(calls.0.into_iter().filter_map(|(path, location)| {
+        let mut segments = path.splitn(2, "::");
+        let first = segments.next().unwrap_or_default();
+        let resolved = match (imports.get(first), segments.next()) {
+            (Some(import), Some(rest)) => format!("{import}::{rest}"),
+            (Some(import), None) => import.clone(),
+            (None, _) => path,
+        };
+        let rooted = resolved == lib || resolved.starts_with(&format!("{lib}::"));
+        (!rooted).then(|| {
+            Failure::unexpected(&format!("call to '{resolved}' outside of '{lib}'"))
+                .at(Some(location))
+        })
+    }));
+
+    item.scope(failures.into())
+}
+
+struct CallVisitor(Vec<(String, Location)>);
+
+impl<'ast> Visit<'ast> for CallVisitor {
+    fn visit_item_fn(&mut self, func: &'ast ItemFn) {
+        visit::visit_block(self, &func.block);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        if let Expr::Path(path) = &*call.func {
+            let rendered = path
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_token_stream().to_string())
+                .collect::<Vec<_>>()
+                .join("::");
+            self.0.push((rendered, Location::of(call)));
+        }
+        visit::visit_expr_call(self, call);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_thin_main() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/main.rs",
+            r#"use app::{cli, run as start};
+
+fn main() {
+    let args = cli::parse(std::env::args());
+    let config = load(&args);
+    start(config).unwrap();
+}
+
+fn load(args: &[String]) -> Config { todo!() }
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        assert_eq!(Rule::thin_main("app", 3).check(&ast).failure_count(), 2);
+        let failures = Rule::thin_main("app", 2).check(&ast).into_failures();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "Expected statement count of fn main to be 'at most 2', got '3'".to_string(),
+                    Some(3)
+                ),
+                (
+                    "Unexpected call to 'std::env::args' outside of 'app'".to_string(),
+                    Some(4)
+                ),
+                (
+                    "Unexpected call to 'load' outside of 'app'".to_string(),
+                    Some(5)
+                ),
+            ]
+        );
+        assert_eq!(failures[0].path, vec!["crate"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_thin_main_with_lib() -> Result<(), TestError> {
+        let dir = TestDir::new(&[
+            ("src/lib.rs", "pub fn run() {}\n"),
+            (
+                "src/main.rs",
+                "fn main() {\n    app::run();\n    setup();\n}\n\nfn setup() {}\n",
+            ),
+        ])?;
+        let ast = CrateAst::load(&dir)?;
+
+        assert!(ast.root.ends_with("lib.rs"));
+        assert_eq!(
+            Rule::thin_main("app", 2)
+                .check(&ast)
+                .into_failures()
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>(),
+            vec![(
+                "Unexpected call to 'setup' outside of 'app'".to_string(),
+                Some(3)
+            )]
+        );
+
+        dir.write("src/main.rs", "fn start() {}\n")?;
+        let ast = CrateAst::load(&dir)?;
+        assert_eq!(
+            Rule::thin_main("app", 2).check(&ast).into_failures()[0].message(),
+            "Missing fn main"
+        );
+
+        let dir = TestDir::new(&[("src/lib.rs", "pub fn run() {}\n")])?;
+        let ast = CrateAst::load(&dir)?;
+        assert!(!Rule::thin_main("app", 2).check(&ast).as_bool());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use crate::failure::Failure;
+use crate::utils::CheckResult;
+use std::path::Path;
+use std::process::Command;
+use syn::File;
+
+pub fn of_crate<P>(path: P) -> Result<File, CheckResult>
+where
+    P: AsRef<Path>,
+{
+    let output = Command::new("cargo")
+        .arg("expand")
+        .arg("--color=never")
+        .current_dir(path.as_ref())
+        .output()
+        .map_err(|e| invalid(&e.to_string()))?;
+
+    if !output.status.success() {
+        return Err(invalid(String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    syn::parse_str(&String::from_utf8_lossy(&output.stdout))
+        .map_err(|e| CheckResult::Failure(vec![Failure::invalid("expanded crate", &e.to_string())]))
+}
+
+fn invalid(error: &str) -> CheckResult {
+    CheckResult::Failure(vec![Failure::invalid("cargo expand", error)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_of_crate_missing() {
+        let results = of_crate("/nonexistent/syn-assert-crate").unwrap_err();
+        assert!(results.failures()[0]
+            .message()
+            .starts_with("Invalid cargo expand"));
+    }
+}
@@ -0,0 +1,86 @@
+use std::cell::RefCell;
+
+thread_local! {
+    static EXPLANATIONS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Verbosity {
+    #[default]
+    Normal,
+    Explain,
+}
+
+pub(crate) fn record<F>(explanation: F)
+where
+    F: FnOnce() -> String,
+{
+    EXPLANATIONS.with(|e| {
+        if let Some(explanations) = e.borrow_mut().as_mut() {
+            explanations.push(explanation());
+        }
+    });
+}
+
+pub fn explain<F, R>(f: F) -> (R, Vec<String>)
+where
+    F: FnOnce() -> R,
+{
+    let _restore = Restore(EXPLANATIONS.with(|e| e.borrow_mut().replace(Vec::new())));
+    let result = f();
+    let explanations = EXPLANATIONS.with(|e| e.borrow_mut().take().unwrap_or_default());
+    (result, explanations)
+}
+
+struct Restore(Option<Vec<String>>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        EXPLANATIONS.with(|e| *e.borrow_mut() = previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_explain() -> Result<(), TestError> {
+        let func: syn::ItemFn = syn::parse_str(
+            r#"
+            #[inline]
+            fn main() {}
+        "#,
+        )?;
+
+        let (results, explanations) = explain(|| {
+            func.has_fn()
+                .with_name("main")
+                .with_attrs(vec!["inline".to_string()])
+                .check()
+        });
+        assert!(results.as_bool());
+        assert_eq!(
+            explanations,
+            vec![
+                "name: 'main' == 'main'".to_string(),
+                "found 'inline'".to_string()
+            ]
+        );
+
+        let (_, explanations) = explain(|| func.has_fn().with_name("main").check());
+        assert_eq!(explanations.len(), 1);
+        record(|| unreachable!());
+
+        let panicked = std::panic::catch_unwind(|| explain(|| panic!("in scope")));
+        assert!(panicked.is_err());
+        record(|| unreachable!());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,272 @@
+use crate::color::{paint, Style};
+use crate::diff::Diff;
+use crate::explain::Verbosity;
+use proc_macro2::Span;
+use std::fmt::{self, Display};
+use syn::spanned::Spanned;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub snippet: Option<String>,
+}
+
+impl Location {
+    pub fn from_span(span: Span) -> Self {
+        let start = span.start();
+        Self {
+            file: Default::default(),
+            line: start.line,
+            column: start.column + 1,
+            snippet: span
+                .source_text()
+                .and_then(|s| s.lines().next().map(|l| l.trim().to_string())),
+        }
+    }
+
+    pub fn of<T>(t: &T) -> Self
+    where
+        T: Spanned,
+    {
+        Self::from_span(t.span())
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{file}:")?;
+        }
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FailureKind {
+    Mismatch,
+    Missing,
+    Unexpected,
+    Invalid,
+    Custom,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub kind: FailureKind,
+    pub severity: Severity,
+    pub subject: String,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+    pub path: Vec<String>,
+    pub location: Option<Location>,
+    pub diff: Option<Diff>,
+}
+
+impl Failure {
+    fn new(kind: FailureKind, subject: &str) -> Self {
+        Self {
+            kind,
+            severity: Default::default(),
+            subject: subject.to_string(),
+            expected: Default::default(),
+            actual: Default::default(),
+            path: Default::default(),
+            location: Default::default(),
+            diff: Default::default(),
+        }
+    }
+
+    pub fn mismatch(subject: &str, expected: &str, actual: &str) -> Self {
+        Self {
+            expected: Some(expected.to_string()),
+            actual: Some(actual.to_string()),
+            ..Self::new(FailureKind::Mismatch, subject)
+        }
+    }
+
+    pub fn missing(subject: &str) -> Self {
+        Self::new(FailureKind::Missing, subject)
+    }
+
+    pub fn unexpected(subject: &str) -> Self {
+        Self::new(FailureKind::Unexpected, subject)
+    }
+
+    pub fn invalid(subject: &str, error: &str) -> Self {
+        Self {
+            actual: Some(error.to_string()),
+            ..Self::new(FailureKind::Invalid, subject)
+        }
+    }
+
+    pub fn custom(message: &str) -> Self {
+        Self::new(FailureKind::Custom, message)
+    }
+
+    pub fn within(mut self, segment: &str) -> Self {
+        self.path.insert(0, segment.to_string());
+        self
+    }
+
+    pub fn as_warning(self) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..self
+        }
+    }
+
+    pub fn is_warning(&self) -> bool {
+        self.severity == Severity::Warning
+    }
+
+    pub fn at(mut self, location: Option<Location>) -> Self {
+        if self.location.is_none() {
+            self.location = location;
+        }
+        self
+    }
+
+    pub fn in_file(mut self, file: &str) -> Self {
+        if let Some(location) = &mut self.location {
+            location.file.get_or_insert_with(|| file.to_string());
+        }
+        self
+    }
+
+    pub fn message(&self) -> String {
+        self.render_message(false, Verbosity::Normal)
+    }
+
+    pub fn render(&self, color: bool) -> String {
+        self.render_with(color, Verbosity::Normal)
+    }
+
+    pub fn render_with(&self, color: bool, verbosity: Verbosity) -> String {
+        let mut rendered = String::new();
+        if self.is_warning() {
+            rendered.push_str("warning: ");
+        }
+        if !self.path.is_empty() {
+            rendered.push_str(&paint(
+                &format!("{}: ", self.path.join(" > ")),
+                Style::Context,
+                color,
+            ));
+        }
+        rendered.push_str(&self.render_message(color, verbosity));
+        if let Some(location) = &self.location {
+            rendered.push_str(&paint(&format!(" (at {location})"), Style::Context, color));
+        }
+        rendered
+    }
+
+    pub(crate) fn render_sides(&self, color: bool, indent: &str) -> String {
+        match &self.diff {
+            Some(diff) => {
+                let (expected, actual) = diff.render_sides(color);
+                format!("\n{indent}< {expected}\n{indent}> {actual}")
+            }
+            None => String::new(),
+        }
+    }
+
+    fn render_message(&self, color: bool, verbosity: Verbosity) -> String {
+        let expected = paint(
+            self.expected.as_deref().unwrap_or_default(),
+            Style::Expected,
+            color,
+        );
+        let actual = paint(
+            self.actual.as_deref().unwrap_or_default(),
+            Style::Actual,
+            color,
+        );
+
+        match self.kind {
+            FailureKind::Mismatch if self.diff.is_some() && verbosity == Verbosity::Explain => {
+                format!("Mismatched {}", self.subject)
+            }
+            FailureKind::Mismatch if self.diff.is_some() => {
+                let diff = self
+                    .diff
+                    .as_ref()
+                    .map(|d| d.render(color))
+                    .unwrap_or_default();
+                format!("Mismatched {}: {diff}", self.subject)
+            }
+            FailureKind::Mismatch if self.subject.is_empty() => {
+                format!("Expected '{expected}', got '{actual}'")
+            }
+            FailureKind::Mismatch => {
+                format!(
+                    "Expected {} to be '{expected}', got '{actual}'",
+                    self.subject
+                )
+            }
+            FailureKind::Missing => format!("Missing {}", self.subject),
+            FailureKind::Unexpected => format!("Unexpected {}", self.subject),
+            FailureKind::Invalid => format!("Invalid {}: {actual}", self.subject),
+            FailureKind::Custom => self.subject.clone(),
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(false))
+    }
+}
+
+impl From<String> for Failure {
+    fn from(message: String) -> Self {
+        Self::custom(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Failure::mismatch("", "main", "other").to_string(),
+            "Expected 'main', got 'other'"
+        );
+        assert_eq!(
+            Failure::mismatch("return type", "Self", "Option<Self>").to_string(),
+            "Expected return type to be 'Self', got 'Option<Self>'"
+        );
+        assert_eq!(Failure::missing("name").to_string(), "Missing name");
+        assert_eq!(
+            Failure::missing("fn")
+                .within("fn new")
+                .within("impl Foo")
+                .to_string(),
+            "impl Foo > fn new: Missing fn"
+        );
+    }
+
+    #[test]
+    fn test_location() -> Result<(), Box<dyn std::error::Error>> {
+        let func: syn::ItemFn = syn::parse_str("\n    fn main() {}")?;
+        let failure = Failure::missing("name").at(Some(Location::of(&func)));
+
+        assert_eq!(failure.to_string(), "Missing name (at 2:5)");
+        assert_eq!(
+            failure.location.and_then(|l| l.snippet),
+            Some("fn main() {}".to_string())
+        );
+
+        Ok(())
+    }
+}
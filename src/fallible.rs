@@ -0,0 +1,240 @@
+use crate::failure::{Failure, Location};
+use crate::rules::Rule;
+use crate::type_alias::types_match;
+use crate::utils::CheckResult;
+use quote::ToTokens;
+use syn::visit::Visit;
+use syn::{parse_quote, ImplItem, Item, ReturnType, Signature, Type, TypeImplTrait, Visibility};
+
+impl<'s> Rule<'s> {
+    pub fn fallible_pub_fns<I, S>(exempt: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let exempt = exempt
+            .into_iter()
+            .map(|e| e.as_ref().to_string())
+            .collect::<Vec<_>>();
+        Self::new("pub fns return Result")
+            .select(|item| matches!(item, Item::Fn(_) | Item::Impl(_)))
+            .must(move |item| fallible(item, &exempt))
+    }
+
+    pub fn impl_trait_returns(required: bool) -> Self {
+        let name = match required {
+            true => "pub fns return impl Trait",
+            false => "pub fns return concrete types",
+        };
+        Self::new(name)
+            .select(|item| matches!(item, Item::Fn(_) | Item::Impl(_)))
+            .must(move |item| impl_trait_returns(item, required))
+    }
+}
+
+pub(crate) fn fallible(item: &Item, exempt: &[String]) -> CheckResult {
+    let expected: Type = parse_quote!(Result<_, _>);
+    pub_sigs(item)
+        .into_iter()
+        .filter(|sig| !exempt.iter().any(|e| sig.ident == e))
+        .filter_map(|sig| {
+            let actual = match &sig.output {
+                ReturnType::Type(_, ty) if types_match(&expected, ty) => return None,
+                ReturnType::Type(_, ty) => ty.to_token_stream().to_string(),
+                ReturnType::Default => "()".to_string(),
+            };
+            Some(
+                Failure::mismatch(
+                    &format!("return type of fn '{}'", sig.ident),
+                    "Result<_, _>",
+                    &actual,
+                )
+                .at(Some(location(sig))),
+            )
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+pub(crate) fn impl_trait_returns(item: &Item, required: bool) -> CheckResult {
+    pub_sigs(item)
+        .into_iter()
+        .filter_map(|sig| match &sig.output {
+            ReturnType::Type(_, ty) => Some((sig, ty)),
+            ReturnType::Default => None,
+        })
+        .filter_map(|(sig, ty)| {
+            let mut visitor = ImplTraitVisitor(Vec::new());
+            visitor.visit_type(ty);
+            match (required, visitor.0.is_empty()) {
+                (true, true) => Some(vec![Failure::mismatch(
+                    &format!("return type of fn '{}'", sig.ident),
+                    "impl Trait",
+                    &ty.to_token_stream().to_string(),
+                )
+                .at(Some(Location::of(ty)))]),
+                (false, false) => Some(
+                    visitor
+                        .0
+                        .into_iter()
+                        .map(|impl_trait| {
+                            Failure::unexpected(&format!(
+                                "'{}' in return type of fn '{}'",
+                                impl_trait.to_token_stream(),
+                                sig.ident
+                            ))
+                            .at(Some(Location::of(impl_trait)))
+                        })
+                        .collect(),
+                ),
+                _ => None,
+            }
+        })
+        .flatten()
+        .collect::<Vec<_>>()
+        .into()
+}
+
+struct ImplTraitVisitor<'ast>(Vec<&'ast TypeImplTrait>);
+
+impl<'ast> Visit<'ast> for ImplTraitVisitor<'ast> {
+    fn visit_type_impl_trait(&mut self, impl_trait: &'ast TypeImplTrait) {
+        self.0.push(impl_trait);
+    }
+}
+
+fn pub_sigs(item: &Item) -> Vec<&Signature> {
+    match item {
+        Item::Fn(func) if is_pub(&func.vis) => vec![&func.sig],
+        Item::Impl(impl_) if impl_.trait_.is_none() => impl_
+            .items
+            .iter()
+            .filter_map(|i| match i {
+                ImplItem::Method(m) if is_pub(&m.vis) => Some(&m.sig),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn location(sig: &Signature) -> Location {
+    match &sig.output {
+        ReturnType::Type(_, ty) => Location::of(ty),
+        ReturnType::Default => Location::of(&sig.ident),
+    }
+}
+
+fn is_pub(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crate_ast::CrateAst;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_fallible_pub_fns() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"pub fn version() -> &'static str { "1" }
+mod api {
+    pub fn list() -> std::result::Result<Vec<u8>, Error> { todo!() }
+    pub fn get() -> Option<u8> { None }
+    pub fn delete() {}
+    fn helper() -> u8 { 0 }
+    pub struct Client;
+    impl Client {
+        pub fn new() -> Self { Client }
+        pub fn send(&self) -> io::Result<()> { Ok(()) }
+    }
+}
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let failures = Rule::fallible_pub_fns(["new"])
+            .in_module("crate::api")
+            .check(&ast)
+            .into_failures();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "Expected return type of fn 'get' to be 'Result<_, _>', got 'Option < u8 >'"
+                        .to_string(),
+                    Some(4)
+                ),
+                (
+                    "Expected return type of fn 'delete' to be 'Result<_, _>', got '()'"
+                        .to_string(),
+                    Some(5)
+                ),
+                (
+                    "Expected return type of fn 'send' to be 'Result<_, _>', got 'io :: Result < () >'"
+                        .to_string(),
+                    Some(10)
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_trait_returns() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"pub fn ids() -> impl Iterator<Item = u8> { 0..3 }
+pub fn names() -> Result<Vec<String>, Error> { todo!() }
+pub fn run() {}
+fn private() -> impl Fn() { || () }
+pub struct Client;
+impl Client {
+    pub fn stream(&self) -> Result<impl Stream, Error> { todo!() }
+}
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let messages = |rule: Rule| {
+            rule.check(&ast)
+                .into_failures()
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            messages(Rule::impl_trait_returns(false)),
+            vec![
+                (
+                    "Unexpected 'impl Iterator < Item = u8 >' in return type of fn 'ids'"
+                        .to_string(),
+                    Some(1)
+                ),
+                (
+                    "Unexpected 'impl Stream' in return type of fn 'stream'".to_string(),
+                    Some(7)
+                ),
+            ]
+        );
+        assert_eq!(
+            messages(Rule::impl_trait_returns(true)),
+            vec![(
+                "Expected return type of fn 'names' to be 'impl Trait', got 'Result < Vec < String > , Error >'"
+                    .to_string(),
+                Some(2)
+            )]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,544 @@
+use crate::failure::{Failure, Location};
+use crate::imports::{normalize_path, Import, ImportScope};
+use crate::normalize::{normalize, sort_attrs};
+use crate::types::types_eq_in;
+use crate::unsafety::no_static_mut;
+use crate::utils::{attr_paths, compare_attrs, parse_str, CheckResult};
+use quote::ToTokens;
+use std::fmt::{self, Display};
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::{
+    AttrStyle, Attribute, File, ImplItem, Item, ItemMod, Lit, Meta, MetaNameValue, Path, Signature,
+    Token, Type, Visibility,
+};
+
+pub trait HasItems {
+    fn walk_items(&self) -> Vec<&Item>;
+    fn walk_scopes(&self) -> Vec<(ImportScope, Vec<&Item>)>;
+
+    fn implements(&self, trait_path: &str, self_ty: &str) -> CheckResult {
+        find_impl(self, trait_path, self_ty, false)
+    }
+
+    fn implements_resolved(&self, trait_path: &str, self_ty: &str) -> CheckResult {
+        find_impl(self, trait_path, self_ty, true)
+    }
+
+    fn has_item_matching(&self, template: &str) -> CheckResult {
+        let template = match syn::parse_str::<FnTemplate>(template) {
+            Ok(func) => Template::Fn(
+                func.vis.to_token_stream().to_string(),
+                normalize(func.sig.to_token_stream()).to_string(),
+            ),
+            Err(_) => match parse_str::<Item>("item", template) {
+                Ok(item) => {
+                    Template::Item(normalize(sort_attrs(item.to_token_stream())).to_string())
+                }
+                Err(failure) => return failure,
+            },
+        };
+
+        let found = self.walk_items().into_iter().any(|item| match &template {
+            Template::Item(expected) => {
+                normalize(sort_attrs(item.to_token_stream())).to_string() == *expected
+            }
+            Template::Fn(vis, sig) => match item {
+                Item::Fn(func) => fn_matches(vis, sig, &func.vis, &func.sig),
+                Item::Impl(impl_) => impl_.items.iter().any(|item| match item {
+                    ImplItem::Method(method) => fn_matches(vis, sig, &method.vis, &method.sig),
+                    _ => false,
+                }),
+                _ => false,
+            },
+        });
+
+        if found {
+            CheckResult::Success
+        } else {
+            CheckResult::missing(&format!("item matching '{template}'"))
+        }
+    }
+
+    fn has_no_static_mut(&self) -> CheckResult {
+        CheckResult::any(self.walk_items().into_iter().map(no_static_mut))
+    }
+
+    fn imports(&self, path: &str) -> CheckResult {
+        let found = self.walk_items().into_iter().any(|item| match item {
+            Item::Use(item_use) => Import::of(item_use).iter().any(|i| i.covers(path)),
+            _ => false,
+        });
+
+        if found {
+            CheckResult::Success
+        } else {
+            CheckResult::missing(&format!("import '{}'", normalize_path(path)))
+        }
+    }
+
+    fn does_not_import(&self, path: &str) -> CheckResult {
+        let mut failures = Vec::new();
+        for item in self.walk_items() {
+            if let Item::Use(item_use) = item {
+                failures.extend(
+                    Import::of(item_use)
+                        .into_iter()
+                        .filter(|i| i.is_within(path))
+                        .map(|i| {
+                            Failure::unexpected(&format!("import '{i}'"))
+                                .at(Some(Location::of(item_use)))
+                        }),
+                );
+            }
+        }
+        failures.into()
+    }
+}
+
+pub trait HasInnerAttrs {
+    fn attributes(&self) -> &[Attribute];
+
+    fn inner_attrs(&self) -> Vec<Attribute> {
+        self.attributes()
+            .iter()
+            .filter(|a| matches!(a.style, AttrStyle::Inner(_)))
+            .cloned()
+            .collect()
+    }
+
+    fn has_inner_attrs(&self, attrs: &[String]) -> CheckResult {
+        CheckResult::contains_display(attr_paths(&self.inner_attrs()), attrs)
+    }
+
+    fn inner_docs(&self) -> Option<String> {
+        let lines = self
+            .inner_attrs()
+            .iter()
+            .filter(|a| a.path.is_ident("doc"))
+            .filter_map(|a| match a.parse_meta() {
+                Ok(Meta::NameValue(MetaNameValue {
+                    lit: Lit::Str(s), ..
+                })) => Some(s.value()),
+                _ => None,
+            })
+            .map(|line| line.strip_prefix(' ').unwrap_or(&line).to_string())
+            .collect::<Vec<_>>();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    fn has_inner_docs(&self, sections: &[&str]) -> CheckResult {
+        let docs = match self.inner_docs() {
+            Some(docs) => docs,
+            None => return CheckResult::missing("inner doc comment"),
+        };
+        let mut in_code = false;
+        let headings = docs
+            .lines()
+            .map(str::trim)
+            .filter(|line| {
+                if line.starts_with("```") {
+                    in_code = !in_code;
+                }
+                !in_code && line.starts_with('#')
+            })
+            .collect::<Vec<_>>();
+        CheckResult::any(sections.iter().map(|section| {
+            let found = headings.iter().any(|heading| {
+                heading == section || heading.trim_start_matches('#').trim() == *section
+            });
+            if found {
+                CheckResult::Success
+            } else {
+                CheckResult::missing(&format!("doc section '{section}'"))
+            }
+        }))
+    }
+
+    fn has_inner_attr(&self, attr: &str) -> CheckResult {
+        match Attribute::parse_inner.parse_str(attr) {
+            Ok(expected) => compare_attrs(&self.inner_attrs(), &expected),
+            Err(e) => CheckResult::Failure(vec![Failure::invalid(
+                &format!("inner attribute '{attr}'"),
+                &e.to_string(),
+            )]),
+        }
+    }
+}
+
+impl HasInnerAttrs for File {
+    fn attributes(&self) -> &[Attribute] {
+        &self.attrs
+    }
+}
+
+impl HasInnerAttrs for ItemMod {
+    fn attributes(&self) -> &[Attribute] {
+        &self.attrs
+    }
+}
+
+enum Template {
+    Item(String),
+    Fn(String, String),
+}
+
+impl Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Template::Item(item) => write!(f, "{item}"),
+            Template::Fn(vis, sig) if vis.is_empty() => write!(f, "{sig} ;"),
+            Template::Fn(vis, sig) => write!(f, "{vis} {sig} ;"),
+        }
+    }
+}
+
+struct FnTemplate {
+    vis: Visibility,
+    sig: Signature,
+}
+
+impl Parse for FnTemplate {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        let sig = input.parse()?;
+        input.parse::<Token![;]>()?;
+        Ok(Self { vis, sig })
+    }
+}
+
+fn fn_matches(vis: &str, sig: &str, actual_vis: &Visibility, actual_sig: &Signature) -> bool {
+    actual_vis.to_token_stream().to_string() == vis
+        && normalize(actual_sig.to_token_stream()).to_string() == sig
+}
+
+fn walk<'a>(items: &'a [Item], out: &mut Vec<&'a Item>) {
+    for item in items {
+        out.push(item);
+        if let Item::Mod(ItemMod {
+            content: Some((_, items)),
+            ..
+        }) = item
+        {
+            walk(items, out);
+        }
+    }
+}
+
+fn find_impl<T>(items: &T, trait_path: &str, self_ty: &str, resolve: bool) -> CheckResult
+where
+    T: HasItems + ?Sized,
+{
+    let expected_path = match parse_str::<Path>("path", trait_path) {
+        Ok(path) => path,
+        Err(failure) => return failure,
+    };
+    let expected_ty = match parse_str::<Type>("type", self_ty) {
+        Ok(ty) => ty,
+        Err(failure) => return failure,
+    };
+
+    let found = items.walk_scopes().into_iter().any(|(scope, items)| {
+        let scope = match resolve {
+            true => scope,
+            false => ImportScope::new(),
+        };
+        items.into_iter().any(|item| match item {
+            Item::Impl(impl_) => match &impl_.trait_ {
+                Some((None, path, _)) => {
+                    path_ends_with(path, &expected_path, &scope)
+                        && types_eq_in(&expected_ty, &impl_.self_ty, &scope)
+                }
+                _ => false,
+            },
+            _ => false,
+        })
+    });
+
+    if found {
+        CheckResult::Success
+    } else {
+        CheckResult::missing(&format!("impl {trait_path} for {self_ty}"))
+    }
+}
+
+fn walk_scopes<'a>(items: &'a [Item], out: &mut Vec<(ImportScope, Vec<&'a Item>)>) {
+    out.push((ImportScope::of(items), items.iter().collect()));
+    for item in items {
+        if let Item::Mod(ItemMod {
+            content: Some((_, items)),
+            ..
+        }) = item
+        {
+            walk_scopes(items, out);
+        }
+    }
+}
+
+fn path_ends_with(path: &Path, suffix: &Path, scope: &ImportScope) -> bool {
+    let mut path = path.clone();
+    scope.resolve(&mut path);
+    if suffix.segments.len() > path.segments.len()
+        || (suffix.leading_colon.is_some() && suffix.segments.len() != path.segments.len())
+    {
+        return false;
+    }
+
+    path.segments
+        .iter()
+        .rev()
+        .zip(suffix.segments.iter().rev())
+        .all(|(a, b)| a.to_token_stream().to_string() == b.to_token_stream().to_string())
+}
+
+impl HasItems for File {
+    fn walk_items(&self) -> Vec<&Item> {
+        self.items.walk_items()
+    }
+
+    fn walk_scopes(&self) -> Vec<(ImportScope, Vec<&Item>)> {
+        self.items.walk_scopes()
+    }
+}
+
+impl HasItems for ItemMod {
+    fn walk_items(&self) -> Vec<&Item> {
+        match &self.content {
+            Some((_, items)) => items.walk_items(),
+            None => Default::default(),
+        }
+    }
+
+    fn walk_scopes(&self) -> Vec<(ImportScope, Vec<&Item>)> {
+        match &self.content {
+            Some((_, items)) => items.walk_scopes(),
+            None => Default::default(),
+        }
+    }
+}
+
+impl HasItems for Vec<Item> {
+    fn walk_items(&self) -> Vec<&Item> {
+        let mut items = Vec::new();
+        walk(self, &mut items);
+        items
+    }
+
+    fn walk_scopes(&self) -> Vec<(ImportScope, Vec<&Item>)> {
+        let mut scopes = Vec::new();
+        walk_scopes(self, &mut scopes);
+        scopes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_implements() -> Result<(), TestError> {
+        let file: syn::File = syn::parse_str(
+            r#"
+            struct MyType;
+
+            mod generated {
+                impl ::core::fmt::Debug for super::MyType {}
+                impl ::core::fmt::Debug for MyType {}
+            }
+        "#,
+        )?;
+
+        let results = file.implements("Debug", "MyType");
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        let results = file.implements("fmt::Debug", "MyType");
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_implements_with_imports() -> Result<(), TestError> {
+        let file: syn::File = syn::parse_str(
+            r#"
+            use std::collections::HashMap;
+            use std::fmt::Display;
+
+            impl Display for HashMap<String, u8> {}
+        "#,
+        )?;
+
+        let (trait_path, self_ty) = ("std::fmt::Display", "std::collections::HashMap<String, u8>");
+        assert!(!file.implements(trait_path, self_ty).as_bool());
+        assert!(file.implements_resolved(trait_path, self_ty).as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_implements_resolved_per_module() -> Result<(), TestError> {
+        let file: syn::File = syn::parse_str(
+            r#"
+            use std::fmt::Display;
+
+            mod a {
+                use x::Foo;
+            }
+
+            mod b {
+                use y::Foo;
+                impl Display for Foo {}
+            }
+        "#,
+        )?;
+
+        assert!(file.implements_resolved("Display", "y::Foo").as_bool());
+        assert!(!file.implements_resolved("Display", "x::Foo").as_bool());
+        assert!(!file
+            .implements_resolved("std::fmt::Display", "y::Foo")
+            .as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_implements_fail() -> Result<(), TestError> {
+        let file: syn::File = syn::parse_str(
+            r#"
+            struct MyType;
+            impl Clone for MyType { fn clone(&self) -> Self { MyType } }
+            impl !Debug for MyType {}
+        "#,
+        )?;
+
+        let results = file.implements("Debug", "MyType");
+        dbg!(&results);
+        assert!(!results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_has_item_matching() -> Result<(), TestError> {
+        let file: syn::File = syn::parse_str(
+            r#"
+            pub struct Server { running: bool }
+
+            impl Server {
+                pub fn shutdown(&mut self) { self.running = false; }
+            }
+        "#,
+        )?;
+
+        assert!(file
+            .has_item_matching("pub fn shutdown(&mut self);")
+            .as_bool());
+        assert!(file
+            .has_item_matching("pub struct Server { running: bool, }")
+            .as_bool());
+
+        let results = file.has_item_matching("fn shutdown(&mut self);");
+        assert_eq!(
+            results.into_failures()[0].message(),
+            "Missing item matching 'fn shutdown (& mut self) ;'"
+        );
+
+        let results = file.has_item_matching("pub fn");
+        assert!(!results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_inner_attrs() -> Result<(), TestError> {
+        let file: syn::File = syn::parse_str(
+            r#"
+            #![no_std]
+            #![deny(missing_docs)]
+            #[allow(dead_code)]
+            mod inner {
+                #![allow(unused)]
+            }
+        "#,
+        )?;
+
+        assert!(file
+            .has_inner_attrs(&["no_std".to_string(), "deny".to_string()])
+            .as_bool());
+        assert!(file.has_inner_attr("#![deny(missing_docs)]").as_bool());
+        assert!(!file.has_inner_attr("#![deny(warnings)]").as_bool());
+        assert!(!file.has_inner_attr("#[deny(missing_docs)]").as_bool());
+
+        let inner = match &file.items[0] {
+            Item::Mod(inner) => inner,
+            _ => unreachable!(),
+        };
+        assert!(inner.has_inner_attrs(&["allow".to_string()]).as_bool());
+        assert_eq!(inner.inner_attrs().len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_inner_docs() -> Result<(), TestError> {
+        let file: syn::File = syn::parse_str(
+            "//! Generated client.\n//!\n//! ## Examples\n//!\n//! ```\n//! # Usage\n//! ```\n/// Outer docs.\nfn main() {}\n",
+        )?;
+
+        assert_eq!(
+            file.inner_docs().as_deref(),
+            Some("Generated client.\n\n## Examples\n\n```\n# Usage\n```")
+        );
+        assert!(file.has_inner_docs(&["Examples", "## Examples"]).as_bool());
+        assert!(!file.has_inner_docs(&["Usage"]).as_bool());
+        assert_eq!(
+            file.has_inner_docs(&["Errors"]).into_failures()[0].message(),
+            "Missing doc section 'Errors'"
+        );
+
+        let file: syn::File = syn::parse_str("/// Outer docs.\nfn main() {}\n")?;
+        assert_eq!(
+            file.has_inner_docs(&[]).into_failures()[0].message(),
+            "Missing inner doc comment"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_imports() -> Result<(), TestError> {
+        let file: syn::File = syn::parse_str(
+            r#"
+            use crate::prelude::{Context as Ctx, *};
+            mod inner {
+                use std::process::{self, Command};
+            }
+        "#,
+        )?;
+
+        assert!(file.imports("crate::prelude::Context").as_bool());
+        assert!(file.imports("crate::prelude::Request").as_bool());
+        assert!(file.imports("std::process::Command").as_bool());
+        assert_eq!(
+            file.imports("std::fs").into_failures()[0].message(),
+            "Missing import 'std::fs'"
+        );
+
+        assert!(file.does_not_import("std::fs").as_bool());
+        let failures = file.does_not_import("std::process").into_failures();
+        assert_eq!(
+            failures.iter().map(|f| f.message()).collect::<Vec<_>>(),
+            vec![
+                "Unexpected import 'std::process'",
+                "Unexpected import 'std::process::Command'",
+            ]
+        );
+        assert_eq!(failures[0].location.as_ref().map(|l| l.line), Some(4));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+use crate::failure::Failure;
+use crate::harness::{compare_expansion, expand};
+use crate::report::Report;
+use crate::utils::CheckResult;
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use std::fs;
+use std::path::Path;
+use syn::File;
+
+pub fn run<P, F>(dir: P, macro_fn: F) -> Report
+where
+    P: AsRef<Path>,
+    F: Fn(TokenStream) -> TokenStream,
+{
+    let mut report = Report::new();
+    let mut fixtures = match fs::read_dir(dir.as_ref()) {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect::<Vec<_>>(),
+        Err(e) => {
+            report.add(
+                &dir.as_ref().display().to_string(),
+                CheckResult::Failure(vec![Failure::invalid("fixture directory", &e.to_string())]),
+            );
+            return report;
+        }
+    };
+    fixtures.sort();
+
+    for fixture in fixtures {
+        let name = fixture
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        report.add(&name, run_fixture(&fixture, &macro_fn));
+    }
+    report
+}
+
+fn run_fixture<F>(fixture: &Path, macro_fn: &F) -> CheckResult
+where
+    F: Fn(TokenStream) -> TokenStream,
+{
+    let input = match read_file(&fixture.join("input.rs")) {
+        Ok(file) => file,
+        Err(failure) => return failure,
+    };
+    let expected_path = fixture.join("expected.rs");
+    let expected = match fs::read_to_string(&expected_path) {
+        Ok(expected) => expected,
+        Err(e) => {
+            return CheckResult::Failure(vec![Failure::invalid(
+                &expected_path.display().to_string(),
+                &e.to_string(),
+            )])
+        }
+    };
+
+    let output = match expand("macro", || macro_fn(input.into_token_stream())) {
+        Ok(output) => output,
+        Err(failure) => return failure,
+    };
+    let actual = match syn::parse2::<File>(output) {
+        Ok(file) => file,
+        Err(e) => return CheckResult::Failure(vec![Failure::invalid("expansion", &e.to_string())]),
+    };
+
+    compare_expansion(&expected, &actual)
+}
+
+fn read_file(path: &Path) -> Result<File, CheckResult> {
+    let subject = path.display().to_string();
+    let source = fs::read_to_string(path)
+        .map_err(|e| CheckResult::Failure(vec![Failure::invalid(&subject, &e.to_string())]))?;
+    syn::parse_file(&source)
+        .map_err(|e| CheckResult::Failure(vec![Failure::invalid(&subject, &e.to_string())]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use quote::quote;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    fn add_debug(input: TokenStream) -> TokenStream {
+        let file: File = syn::parse2(input).unwrap();
+        let mut output = file.to_token_stream();
+        for item in &file.items {
+            if let syn::Item::Struct(s) = item {
+                let name = &s.ident;
+                output.extend(quote! { impl Debug for #name {} });
+            }
+        }
+        output
+    }
+
+    #[test]
+    fn test_run() -> Result<(), TestError> {
+        let dir = TestDir::new(&[])?;
+        for (name, input, expected) in [
+            ("a_unit", "struct Foo;", "struct Foo; impl Debug for Foo {}"),
+            (
+                "b_fields",
+                "struct Bar { id: u64 }",
+                "struct Bar {\n    id: u64,\n}\nimpl Debug for Bar {}",
+            ),
+            (
+                "c_wrong",
+                "struct Baz;",
+                "struct Baz; impl Clone for Baz {}",
+            ),
+        ] {
+            dir.write(&format!("{name}/input.rs"), input)?;
+            dir.write(&format!("{name}/expected.rs"), expected)?;
+        }
+        dir.write("d_missing/input.rs", "struct Missing;")?;
+
+        let report = run(&dir, add_debug);
+
+        let results = report.results().collect::<Vec<_>>();
+        assert_eq!(results.len(), 4);
+        assert!(results[0].1.as_bool());
+        assert!(results[1].1.as_bool());
+        assert_eq!(
+            results[2].1.failures()[0].message(),
+            "Mismatched expansion: ... Baz ; impl [-Clone-] {+Debug+} for Baz { ..."
+        );
+        assert_eq!(results[3].1.failures()[0].kind, crate::FailureKind::Invalid);
+
+        Ok(())
+    }
+}
@@ -1,8 +1,19 @@
-use crate::utils::{check_option, Check, CheckResult};
+use crate::complexity::complexity;
+use crate::failure::{Failure, Location};
+use crate::imports::ImportScope;
+use crate::names::NameOptions;
+use crate::types::compare_types_in;
+use crate::utils::{
+    attr_paths, check_option, compare_attrs, compare_tokens, generic_names, missing_names,
+    parse_str, unexpected_extras, Check, CheckResult,
+};
+use crate::visibility::compare_vis;
 use proc_macro2::TokenStream;
 use quote::ToTokens;
-use std::collections::HashSet;
-use syn::{Item, ItemFn, Visibility};
+use syn::{
+    Attribute, Block, FnArg, ImplItemMethod, Item, ItemFn, Receiver, ReturnType, Signature, Type,
+    Visibility,
+};
 
 pub trait HasFn {
     fn has_fn(&self) -> AssertFn<'_, Self>
@@ -12,42 +23,169 @@ pub trait HasFn {
         AssertFn::new(self)
     }
 
+    fn breadcrumb(&self) -> Option<String> {
+        None
+    }
+
+    fn location(&self) -> Option<Location> {
+        None
+    }
+
     fn has_name(&self, name: &str) -> CheckResult;
+    fn has_exact_name(&self, name: &str) -> CheckResult;
+    fn has_name_with(&self, name: &str, options: &NameOptions) -> CheckResult;
     fn has_vis(&self, vis: &Visibility) -> CheckResult;
     fn has_attrs(&self, attrs: &[String]) -> CheckResult;
+    fn has_attr_values(&self, attrs: &[Attribute]) -> CheckResult;
     fn has_block(&self, block: &TokenStream) -> CheckResult;
-}
 
-impl HasFn for ItemFn {
-    fn has_name(&self, name: &str) -> CheckResult {
-        CheckResult::compare(name, &self.sig.ident)
+    fn has_block_str(&self, block: &str) -> CheckResult {
+        match parse_str::<Block>("block", block) {
+            Ok(block) => self.has_block(&block.to_token_stream()),
+            Err(failure) => failure,
+        }
     }
 
-    fn has_vis(&self, vis: &Visibility) -> CheckResult {
-        CheckResult::compare(vis, &self.vis)
+    fn has_return_type(&self, return_type: &str) -> CheckResult {
+        self.has_return_type_in(return_type, &ImportScope::new())
     }
 
-    fn has_attrs(&self, attrs: &[String]) -> CheckResult {
-        let self_attrs = self
-            .attrs
-            .iter()
-            .map(|a| {
-                a.path
-                    .segments
-                    .iter()
-                    .map(|s| s.ident.to_string())
-                    .collect::<Vec<_>>()
-                    .join("::")
-            })
-            .collect::<HashSet<_>>();
-        CheckResult::contains(self_attrs, attrs)
-    }
+    fn has_return_type_in(&self, return_type: &str, imports: &ImportScope) -> CheckResult;
+    fn has_signature(&self, signature: &str) -> CheckResult;
+    fn has_max_complexity(&self, max: usize) -> CheckResult;
+    fn has_async(&self, asyncness: bool) -> CheckResult;
+    fn has_receiver(&self, receiver: &str) -> CheckResult;
+    fn has_params(&self, params: &[String]) -> CheckResult;
+    fn has_generics(&self, generics: &[String]) -> CheckResult;
+    fn has_only_attrs(&self, attrs: &[String]) -> CheckResult;
+    fn has_only_params(&self, params: &[String]) -> CheckResult;
+    fn has_only_generics(&self, generics: &[String]) -> CheckResult;
+}
 
-    fn has_block(&self, block: &TokenStream) -> CheckResult {
-        CheckResult::compare(block.to_string(), self.block.to_token_stream().to_string())
-    }
+macro_rules! hasfn_impl {
+    ($t: ty) => {
+        impl HasFn for $t {
+            fn breadcrumb(&self) -> Option<String> {
+                Some(format!("fn {}", self.sig.ident))
+            }
+
+            fn location(&self) -> Option<Location> {
+                Some(Location::of(self))
+            }
+
+            fn has_name(&self, name: &str) -> CheckResult {
+                CheckResult::compare_ident("name", name, &self.sig.ident)
+            }
+
+            fn has_exact_name(&self, name: &str) -> CheckResult {
+                CheckResult::compare_named("name", name, &self.sig.ident)
+            }
+
+            fn has_name_with(&self, name: &str, options: &NameOptions) -> CheckResult {
+                CheckResult::compare_ident_with("name", name, &self.sig.ident, options)
+            }
+
+            fn has_vis(&self, vis: &Visibility) -> CheckResult {
+                compare_vis(vis, &self.vis)
+            }
+
+            fn has_attrs(&self, attrs: &[String]) -> CheckResult {
+                CheckResult::contains_display(attr_paths(&self.attrs), attrs)
+            }
+
+            fn has_attr_values(&self, attrs: &[Attribute]) -> CheckResult {
+                compare_attrs(&self.attrs, attrs)
+            }
+
+            fn has_block(&self, block: &TokenStream) -> CheckResult {
+                compare_tokens("block", block, &self.block)
+            }
+
+            fn has_return_type_in(&self, return_type: &str, imports: &ImportScope) -> CheckResult {
+                match &self.sig.output {
+                    ReturnType::Type(_, ty) => compare_types_in(return_type, ty, imports),
+                    ReturnType::Default => {
+                        let unit: Type = syn::parse_quote!(());
+                        compare_types_in(return_type, &unit, imports)
+                    }
+                }
+            }
+
+            fn has_signature(&self, signature: &str) -> CheckResult {
+                match parse_str::<Signature>("signature", signature) {
+                    Ok(expected) => {
+                        compare_tokens("signature", &expected.to_token_stream(), &self.sig)
+                    }
+                    Err(failure) => failure,
+                }
+            }
+
+            fn has_async(&self, asyncness: bool) -> CheckResult {
+                CheckResult::compare_named("async", asyncness, self.sig.asyncness.is_some())
+            }
+
+            fn has_receiver(&self, receiver: &str) -> CheckResult {
+                let expected = match parse_str::<Receiver>("receiver", receiver) {
+                    Ok(expected) => expected,
+                    Err(failure) => return failure,
+                };
+                match self.sig.inputs.first() {
+                    Some(FnArg::Receiver(actual)) => {
+                        compare_tokens("receiver", &expected.to_token_stream(), actual)
+                    }
+                    _ => CheckResult::missing("receiver"),
+                }
+            }
+
+            fn has_only_attrs(&self, attrs: &[String]) -> CheckResult {
+                unexpected_extras("attribute", attr_paths(&self.attrs), attrs)
+            }
+
+            fn has_params(&self, params: &[String]) -> CheckResult {
+                missing_names("parameter", param_names(&self.sig), params)
+            }
+
+            fn has_generics(&self, generics: &[String]) -> CheckResult {
+                missing_names("generic", generic_names(&self.sig.generics), generics)
+            }
+
+            fn has_only_params(&self, params: &[String]) -> CheckResult {
+                unexpected_extras("parameter", param_names(&self.sig), params)
+            }
+
+            fn has_only_generics(&self, generics: &[String]) -> CheckResult {
+                unexpected_extras("generic", generic_names(&self.sig.generics), generics)
+            }
+
+            fn has_max_complexity(&self, max: usize) -> CheckResult {
+                let actual = complexity(&self.block);
+                if actual <= max {
+                    CheckResult::Success
+                } else {
+                    CheckResult::Failure(vec![Failure::mismatch(
+                        "complexity",
+                        &format!("at most {max}"),
+                        &actual.to_string(),
+                    )])
+                }
+            }
+        }
+    };
 }
 
+fn param_names(sig: &Signature) -> Vec<String> {
+    sig.inputs
+        .iter()
+        .map(|arg| match arg {
+            FnArg::Receiver(_) => "self".to_string(),
+            FnArg::Typed(arg) => arg.pat.to_token_stream().to_string(),
+        })
+        .collect()
+}
+
+hasfn_impl!(ItemFn);
+hasfn_impl!(ImplItemMethod);
+
 macro_rules! hasfn_item {
     ($v:ident, $t: ty) => {
         paste::paste! {
@@ -62,17 +200,59 @@ macro_rules! hasfn_item {
 }
 
 impl HasFn for Item {
+    fn breadcrumb(&self) -> Option<String> {
+        match self {
+            Item::Fn(func) => func.breadcrumb(),
+            _ => None,
+        }
+    }
+
+    fn location(&self) -> Option<Location> {
+        Some(Location::of(self))
+    }
+
     hasfn_item!(name, &str);
+    hasfn_item!(exact_name, &str);
     hasfn_item!(vis, &Visibility);
     hasfn_item!(attrs, &[String]);
+    hasfn_item!(attr_values, &[Attribute]);
     hasfn_item!(block, &TokenStream);
+    hasfn_item!(signature, &str);
+    hasfn_item!(max_complexity, usize);
+
+    fn has_async(&self, asyncness: bool) -> CheckResult {
+        match self {
+            Item::Fn(func) => func.has_async(asyncness),
+            _ => CheckResult::missing("async"),
+        }
+    }
+
+    fn has_name_with(&self, name: &str, options: &NameOptions) -> CheckResult {
+        match self {
+            Item::Fn(func) => func.has_name_with(name, options),
+            _ => CheckResult::missing("name"),
+        }
+    }
+
+    fn has_return_type_in(&self, return_type: &str, imports: &ImportScope) -> CheckResult {
+        match self {
+            Item::Fn(func) => func.has_return_type_in(return_type, imports),
+            _ => CheckResult::missing("return_type"),
+        }
+    }
+    hasfn_item!(receiver, &str);
+    hasfn_item!(params, &[String]);
+    hasfn_item!(generics, &[String]);
+    hasfn_item!(only_attrs, &[String]);
+    hasfn_item!(only_params, &[String]);
+    hasfn_item!(only_generics, &[String]);
 }
 
 macro_rules! hasfn_vec {
     ($v: ident, $t: ty) => {
         paste::paste! {
             fn [<has_ $v>](&self, $v: $t) -> CheckResult {
-                CheckResult::any(self.iter().map(|f| f.[<has_ $v>](&$v)))
+                CheckResult::any(self.iter().map(|f| f.[<has_ $v>](&$v).in_item(f.breadcrumb(), f.location())))
             }
         }
     };
@@ -83,27 +263,107 @@ where
     T: HasFn,
 {
     hasfn_vec!(name, &str);
+    hasfn_vec!(exact_name, &str);
     hasfn_vec!(vis, &Visibility);
     hasfn_vec!(attrs, &[String]);
+    hasfn_vec!(attr_values, &[Attribute]);
     hasfn_vec!(block, &TokenStream);
+    hasfn_vec!(signature, &str);
+    hasfn_vec!(receiver, &str);
+    hasfn_vec!(params, &[String]);
+    hasfn_vec!(generics, &[String]);
+    hasfn_vec!(only_attrs, &[String]);
+    hasfn_vec!(only_params, &[String]);
+    hasfn_vec!(only_generics, &[String]);
+
+    fn has_max_complexity(&self, max: usize) -> CheckResult {
+        CheckResult::any(self.iter().map(|f| {
+            f.has_max_complexity(max)
+                .in_item(f.breadcrumb(), f.location())
+        }))
+    }
+
+    fn has_async(&self, asyncness: bool) -> CheckResult {
+        CheckResult::any(
+            self.iter()
+                .map(|f| f.has_async(asyncness).in_item(f.breadcrumb(), f.location())),
+        )
+    }
+
+    fn has_name_with(&self, name: &str, options: &NameOptions) -> CheckResult {
+        CheckResult::any(self.iter().map(|f| {
+            f.has_name_with(name, options)
+                .in_item(f.breadcrumb(), f.location())
+        }))
+    }
+
+    fn has_return_type_in(&self, return_type: &str, imports: &ImportScope) -> CheckResult {
+        CheckResult::any(self.iter().map(|f| {
+            f.has_return_type_in(return_type, imports)
+                .in_item(f.breadcrumb(), f.location())
+        }))
+    }
 }
 
+#[must_use]
 pub struct AssertFn<'s, T> {
     t: &'s T,
+    label: Option<&'s str>,
+    strict: bool,
     name: Option<&'s str>,
+    exact_name: Option<&'s str>,
+    name_options: NameOptions,
+    imports: ImportScope,
     vis: Option<Visibility>,
     attrs: Vec<String>,
+    attr_values: Vec<Attribute>,
     block: Option<TokenStream>,
+    block_str: Option<&'s str>,
+    return_type: Option<&'s str>,
+    signature: Option<&'s str>,
+    max_complexity: Option<usize>,
+    asyncness: Option<bool>,
+    receiver: Option<&'s str>,
+    params: Vec<String>,
+    generics: Vec<String>,
 }
 
 impl<'s, T> AssertFn<'s, T> {
     pub fn new(t: &'s T) -> Self {
         Self {
             t,
+            label: Default::default(),
+            strict: Default::default(),
             name: Default::default(),
+            exact_name: Default::default(),
+            name_options: Default::default(),
+            imports: Default::default(),
             vis: Default::default(),
             attrs: Default::default(),
+            attr_values: Default::default(),
             block: Default::default(),
+            block_str: Default::default(),
+            return_type: Default::default(),
+            signature: Default::default(),
+            max_complexity: Default::default(),
+            asyncness: Default::default(),
+            receiver: Default::default(),
+            params: Default::default(),
+            generics: Default::default(),
+        }
+    }
+
+    pub fn labeled(self, label: &'s str) -> Self {
+        Self {
+            label: Some(label),
+            ..self
+        }
+    }
+
+    pub fn strict(self) -> Self {
+        Self {
+            strict: true,
+            ..self
         }
     }
 
@@ -114,6 +374,24 @@ impl<'s, T> AssertFn<'s, T> {
         }
     }
 
+    pub fn with_exact_name(self, exact_name: &'s str) -> Self {
+        Self {
+            exact_name: Some(exact_name),
+            ..self
+        }
+    }
+
+    pub fn with_name_options(self, name_options: NameOptions) -> Self {
+        Self {
+            name_options,
+            ..self
+        }
+    }
+
+    pub fn with_imports(self, imports: ImportScope) -> Self {
+        Self { imports, ..self }
+    }
+
     pub fn with_vis(self, vis: Visibility) -> Self {
         Self {
             vis: Some(vis),
@@ -121,8 +399,20 @@ impl<'s, T> AssertFn<'s, T> {
         }
     }
 
-    pub fn with_attrs(self, attrs: Vec<String>) -> Self {
-        Self { attrs, ..self }
+    pub fn with_attrs<I, S>(self, attrs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            attrs: attrs.into_iter().map(|a| a.as_ref().to_string()).collect(),
+            ..self
+        }
+    }
+
+    pub fn with_attr(mut self, attr: Attribute) -> Self {
+        self.attr_values.push(attr);
+        self
     }
 
     pub fn with_block(self, block: TokenStream) -> Self {
@@ -131,6 +421,110 @@ impl<'s, T> AssertFn<'s, T> {
             ..self
         }
     }
+
+    pub fn with_block_str(self, block: &'s str) -> Self {
+        Self {
+            block_str: Some(block),
+            ..self
+        }
+    }
+
+    pub fn with_return_type(self, return_type: &'s str) -> Self {
+        Self {
+            return_type: Some(return_type),
+            ..self
+        }
+    }
+
+    pub fn with_signature(self, signature: &'s str) -> Self {
+        Self {
+            signature: Some(signature),
+            ..self
+        }
+    }
+
+    pub fn with_max_complexity(self, max: usize) -> Self {
+        Self {
+            max_complexity: Some(max),
+            ..self
+        }
+    }
+
+    pub fn with_async(self, asyncness: bool) -> Self {
+        Self {
+            asyncness: Some(asyncness),
+            ..self
+        }
+    }
+
+    pub fn with_receiver(self, receiver: &'s str) -> Self {
+        Self {
+            receiver: Some(receiver),
+            ..self
+        }
+    }
+
+    pub fn with_params<I, S>(self, params: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            params: params.into_iter().map(|p| p.as_ref().to_string()).collect(),
+            ..self
+        }
+    }
+
+    pub fn with_generics<I, S>(self, generics: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            generics: generics
+                .into_iter()
+                .map(|g| g.as_ref().to_string())
+                .collect(),
+            ..self
+        }
+    }
+}
+
+impl<'s, T> AssertFn<'s, T>
+where
+    T: HasFn,
+{
+    fn is_empty(&self) -> bool {
+        !self.strict
+            && self.name.is_none()
+            && self.exact_name.is_none()
+            && self.vis.is_none()
+            && self.block.is_none()
+            && self.block_str.is_none()
+            && self.return_type.is_none()
+            && self.signature.is_none()
+            && self.max_complexity.is_none()
+            && self.asyncness.is_none()
+            && self.receiver.is_none()
+            && self.attrs.is_empty()
+            && self.attr_values.is_empty()
+            && self.params.is_empty()
+            && self.generics.is_empty()
+    }
+
+    fn check_strict(&self) -> CheckResult {
+        let mut attrs = self.attrs.clone();
+        attrs.extend(attr_paths(&self.attr_values));
+        let results = self.t.has_only_attrs(&attrs);
+        if self.signature.is_some() {
+            return results;
+        }
+        let mut params = self.params.clone();
+        if self.receiver.is_some() && !params.iter().any(|p| p == "self") {
+            params.insert(0, "self".to_string());
+        }
+        results + self.t.has_only_params(&params) + self.t.has_only_generics(&self.generics)
+    }
 }
 
 impl<'s, T> Check for AssertFn<'s, T>
@@ -138,10 +532,264 @@ where
     T: HasFn,
 {
     fn check(self) -> CheckResult {
-        check_option!(self, name)
+        let (breadcrumb, location) = (self.t.breadcrumb(), self.t.location());
+        let label = self.label;
+        let empty = match self.is_empty() {
+            true => CheckResult::missing("constraints in fn assertion"),
+            false => CheckResult::Success,
+        };
+        let strict = match self.strict {
+            true => self.check_strict(),
+            false => CheckResult::Success,
+        };
+        let results = (empty
+            + strict
+            + self.name.map_or(CheckResult::Success, |name| {
+                self.t.has_name_with(name, &self.name_options)
+            })
+            + check_option!(self, exact_name)
             + check_option!(self, vis)
             + check_option!(self, block)
+            + check_option!(self, block_str)
+            + self
+                .return_type
+                .map_or(CheckResult::Success, |return_type| {
+                    self.t.has_return_type_in(return_type, &self.imports)
+                })
+            + check_option!(self, signature)
+            + self
+                .max_complexity
+                .map_or(CheckResult::Success, |max| self.t.has_max_complexity(max))
+            + self.asyncness.map_or(CheckResult::Success, |asyncness| {
+                self.t.has_async(asyncness)
+            })
+            + check_option!(self, receiver)
+            + self.t.has_params(&self.params)
+            + self.t.has_generics(&self.generics)
             + self.t.has_attrs(&self.attrs)
+            + self.t.has_attr_values(&self.attr_values))
+        .in_item(breadcrumb, location);
+        match label {
+            Some(label) => results.within(label),
+            None => results,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FnSpec {
+    label: Option<String>,
+    pub(crate) strict: bool,
+    pub(crate) name: Option<String>,
+    pub(crate) exact_name: Option<String>,
+    pub(crate) name_options: NameOptions,
+    pub(crate) imports: ImportScope,
+    pub(crate) vis: Option<Visibility>,
+    pub(crate) attrs: Vec<String>,
+    pub(crate) attr_values: Vec<Attribute>,
+    pub(crate) block: Option<TokenStream>,
+    pub(crate) block_str: Option<String>,
+    pub(crate) return_type: Option<String>,
+    pub(crate) signature: Option<String>,
+    pub(crate) asyncness: Option<bool>,
+    pub(crate) receiver: Option<String>,
+    pub(crate) params: Vec<String>,
+    pub(crate) generics: Vec<String>,
+    pub(crate) max_complexity: Option<usize>,
+}
+
+impl FnSpec {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn labeled(self, label: &str) -> Self {
+        Self {
+            label: Some(label.to_string()),
+            ..self
+        }
+    }
+
+    pub fn strict(self) -> Self {
+        Self {
+            strict: true,
+            ..self
+        }
+    }
+
+    pub fn with_name(self, name: &str) -> Self {
+        Self {
+            name: Some(name.to_string()),
+            ..self
+        }
+    }
+
+    pub fn with_exact_name(self, exact_name: &str) -> Self {
+        Self {
+            exact_name: Some(exact_name.to_string()),
+            ..self
+        }
+    }
+
+    pub fn with_name_options(self, name_options: NameOptions) -> Self {
+        Self {
+            name_options,
+            ..self
+        }
+    }
+
+    pub fn with_imports(self, imports: ImportScope) -> Self {
+        Self { imports, ..self }
+    }
+
+    pub fn with_vis(self, vis: Visibility) -> Self {
+        Self {
+            vis: Some(vis),
+            ..self
+        }
+    }
+
+    pub fn with_attrs<I, S>(self, attrs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            attrs: attrs.into_iter().map(|a| a.as_ref().to_string()).collect(),
+            ..self
+        }
+    }
+
+    pub fn with_attr(mut self, attr: Attribute) -> Self {
+        self.attr_values.push(attr);
+        self
+    }
+
+    pub fn with_block(self, block: TokenStream) -> Self {
+        Self {
+            block: Some(block),
+            ..self
+        }
+    }
+
+    pub fn with_block_str(self, block: &str) -> Self {
+        Self {
+            block_str: Some(block.to_string()),
+            ..self
+        }
+    }
+
+    pub fn with_return_type(self, return_type: &str) -> Self {
+        Self {
+            return_type: Some(return_type.to_string()),
+            ..self
+        }
+    }
+
+    pub fn with_signature(self, signature: &str) -> Self {
+        Self {
+            signature: Some(signature.to_string()),
+            ..self
+        }
+    }
+
+    pub fn with_max_complexity(self, max: usize) -> Self {
+        Self {
+            max_complexity: Some(max),
+            ..self
+        }
+    }
+
+    pub fn with_async(self, asyncness: bool) -> Self {
+        Self {
+            asyncness: Some(asyncness),
+            ..self
+        }
+    }
+
+    pub fn with_receiver(self, receiver: &str) -> Self {
+        Self {
+            receiver: Some(receiver.to_string()),
+            ..self
+        }
+    }
+
+    pub fn with_params<I, S>(self, params: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            params: params.into_iter().map(|p| p.as_ref().to_string()).collect(),
+            ..self
+        }
+    }
+
+    pub fn with_generics<I, S>(self, generics: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            generics: generics
+                .into_iter()
+                .map(|g| g.as_ref().to_string())
+                .collect(),
+            ..self
+        }
+    }
+
+    pub fn check<T>(&self, t: &T) -> CheckResult
+    where
+        T: HasFn,
+    {
+        let mut assert = t
+            .has_fn()
+            .with_attrs(&self.attrs)
+            .with_params(&self.params)
+            .with_generics(&self.generics)
+            .with_name_options(self.name_options)
+            .with_imports(self.imports.clone());
+        for attr in &self.attr_values {
+            assert = assert.with_attr(attr.clone());
+        }
+        if let Some(label) = &self.label {
+            assert = assert.labeled(label);
+        }
+        if self.strict {
+            assert = assert.strict();
+        }
+        if let Some(name) = &self.name {
+            assert = assert.with_name(name);
+        }
+        if let Some(name) = &self.exact_name {
+            assert = assert.with_exact_name(name);
+        }
+        if let Some(vis) = &self.vis {
+            assert = assert.with_vis(vis.clone());
+        }
+        if let Some(block) = &self.block {
+            assert = assert.with_block(block.clone());
+        }
+        if let Some(block) = &self.block_str {
+            assert = assert.with_block_str(block);
+        }
+        if let Some(return_type) = &self.return_type {
+            assert = assert.with_return_type(return_type);
+        }
+        if let Some(signature) = &self.signature {
+            assert = assert.with_signature(signature);
+        }
+        if let Some(asyncness) = self.asyncness {
+            assert = assert.with_async(asyncness);
+        }
+        if let Some(receiver) = &self.receiver {
+            assert = assert.with_receiver(receiver);
+        }
+        if let Some(max) = self.max_complexity {
+            assert = assert.with_max_complexity(max);
+        }
+        assert.check()
     }
 }
 
@@ -241,6 +889,133 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_raw_name() -> Result<(), TestError> {
+        let func: syn::ItemFn = syn::parse_str("fn r#type() {}")?;
+
+        assert!(func.has_fn().with_name("type").check().as_bool());
+        assert!(func.has_fn().with_name("r#type").check().as_bool());
+        assert!(func.has_fn().with_exact_name("r#type").check().as_bool());
+
+        let failures = func
+            .has_fn()
+            .with_exact_name("type")
+            .check()
+            .into_failures();
+        assert_eq!(
+            failures[0].message(),
+            "Expected name to be 'type', got 'r#type'"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_name_options() -> Result<(), TestError> {
+        let func: syn::ItemFn = syn::parse_str("fn GetUser() {}")?;
+
+        assert!(!func.has_fn().with_name("getuser").check().as_bool());
+        assert!(func
+            .has_fn()
+            .with_name("getuser")
+            .with_name_options(NameOptions::new().case_insensitive())
+            .check()
+            .as_bool());
+        assert!(FnSpec::new()
+            .with_name("GETUSER")
+            .with_name_options(NameOptions::new().case_insensitive())
+            .check(&func)
+            .as_bool());
+
+        let func: syn::ItemFn = syn::parse_str("fn caf\u{e9}() {}")?;
+        let options = NameOptions::new().nfc();
+        assert!(!func.has_fn().with_name("cafe\u{301}").check().as_bool());
+        assert!(func
+            .has_fn()
+            .with_name("cafe\u{301}")
+            .with_name_options(options)
+            .check()
+            .as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_strict() -> Result<(), TestError> {
+        let func: syn::ItemFn = syn::parse_str(
+            "#[inline] #[must_use] fn get<T>(key: &str, default: T) -> T { default }",
+        )?;
+
+        assert!(func.has_fn().with_name("get").check().as_bool());
+
+        let messages = func
+            .has_fn()
+            .with_name("get")
+            .with_attrs(["inline"])
+            .strict()
+            .check()
+            .into_failures()
+            .iter()
+            .map(Failure::message)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "Unexpected attribute 'must_use'",
+                "Unexpected generic 'T'",
+                "Unexpected parameter 'default'",
+                "Unexpected parameter 'key'",
+            ]
+        );
+
+        assert!(func
+            .has_fn()
+            .with_name("get")
+            .with_attrs(["inline", "must_use"])
+            .with_params(["key", "default"])
+            .with_generics(["T"])
+            .strict()
+            .check()
+            .as_bool());
+
+        let messages = func
+            .has_fn()
+            .with_params(["key", "value"])
+            .with_generics(["U"])
+            .check()
+            .into_failures()
+            .iter()
+            .map(Failure::message)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec!["Missing generic 'U'", "Missing parameter 'value'"]
+        );
+
+        assert!(func
+            .has_fn()
+            .with_attrs(["inline", "must_use"])
+            .with_signature("fn get<T>(key: &str, default: T) -> T")
+            .strict()
+            .check()
+            .as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty() -> Result<(), TestError> {
+        let func: syn::ItemFn = syn::parse_str("fn main() {}")?;
+
+        let failures = func.has_fn().labeled("entry").check().into_failures();
+        assert_eq!(failures[0].message(), "Missing constraints in fn assertion");
+        assert_eq!(failures[0].path, vec!["entry", "fn main"]);
+        assert!(!FnSpec::new().check(&func).as_bool());
+        assert!(func.has_fn().strict().check().as_bool());
+
+        Ok(())
+    }
+
     #[test]
     fn test_attrs_1() -> Result<(), TestError> {
         let func: syn::ItemFn = syn::parse_str(
@@ -304,4 +1079,266 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_return_type() -> Result<(), TestError> {
+        let func: syn::ItemFn = syn::parse_str(
+            r#"
+            fn answer() -> Option<u32> { Some(42) }
+        "#,
+        )?;
+
+        let results = func.has_fn().with_return_type("Option<u32>").check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        let results = func.has_fn().with_return_type("u32").check();
+        dbg!(&results);
+        assert!(!results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_return_type_with_imports() -> Result<(), TestError> {
+        let file: syn::File = syn::parse_str(
+            r#"
+            use std::collections::HashMap;
+
+            fn counts() -> HashMap<String, u32> { HashMap::new() }
+        "#,
+        )?;
+        let func = match &file.items[1] {
+            syn::Item::Fn(func) => func,
+            _ => return Err("expected fn".into()),
+        };
+
+        let expected = "std::collections::HashMap<String, u32>";
+        let results = func.has_fn().with_return_type(expected).check();
+        dbg!(&results);
+        assert!(!results.as_bool());
+
+        let results = func
+            .has_fn()
+            .with_return_type(expected)
+            .with_imports(ImportScope::of(&file.items))
+            .check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_location() -> Result<(), TestError> {
+        let file: syn::File = syn::parse_str(
+            r#"
+fn main() {}
+
+pub fn other() {}
+        "#,
+        )?;
+
+        let results = file.items.has_fn().with_vis(syn::parse_quote!(pub)).check();
+        dbg!(&results);
+        let failures = results.into_failures();
+        assert_eq!(failures.len(), 1);
+        let location = failures[0].location.clone().unwrap();
+        assert_eq!((location.line, location.column), (2, 1));
+        assert_eq!(location.snippet.as_deref(), Some("fn main() {}"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_diff() -> Result<(), TestError> {
+        let func: syn::ItemFn = syn::parse_str(
+            r#"
+            fn main() { let a = 1; let b = 2; println!("{}", a + b); }
+        "#,
+        )?;
+
+        let results = func
+            .has_fn()
+            .with_block(quote::quote! { { let a = 1; let b = 3; println!("{}", a + b); } })
+            .check();
+        dbg!(&results);
+        let failures = results.into_failures();
+        assert_eq!(
+            failures[0].message(),
+            "Mismatched block: ... let b = [-3-] {+2+} ; println ! ..."
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_breadcrumb() -> Result<(), TestError> {
+        let func: syn::ItemFn = syn::parse_str(
+            r#"
+            fn main() { println!("Hello, world!"); }
+        "#,
+        )?;
+
+        let results = func.has_fn().with_name("not_main").check();
+        dbg!(&results);
+        let failures = results.into_failures();
+        assert_eq!(failures[0].path, vec!["fn main".to_string()]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_labeled() -> Result<(), TestError> {
+        let func: syn::ItemFn = syn::parse_str("fn main() {}")?;
+
+        let results = func
+            .has_fn()
+            .labeled("entrypoint exists")
+            .with_name("not_main")
+            .check();
+        dbg!(&results);
+        let failures = results.into_failures();
+        assert_eq!(
+            failures[0].to_string(),
+            "entrypoint exists > fn main: Expected name to be 'not_main', got 'main' (at 1:1)"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_signature() -> Result<(), TestError> {
+        let func: syn::ImplItemMethod = syn::parse_str(
+            r#"
+            async fn fetch(&self, id: Id) -> Result<Item, Error> { self.store.get(id).await }
+        "#,
+        )?;
+
+        let results = func
+            .has_fn()
+            .with_signature("async fn fetch(&self, id: Id) -> Result<Item, Error>")
+            .check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        let results = func
+            .has_fn()
+            .with_signature("fn fetch(&self, id: Id) -> Result<Item, Error>")
+            .check();
+        dbg!(&results);
+        assert_eq!(
+            results.into_failures()[0].message(),
+            "Mismatched signature: {+async+} fn fetch ( ..."
+        );
+
+        let results = func.has_fn().with_signature("fn fetch(").check();
+        assert!(!results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_spec() -> Result<(), TestError> {
+        let file: syn::File = syn::parse_str(
+            r#"
+            pub fn get() -> Response { Response }
+            pub fn put() -> Response { Response }
+            fn helper() {}
+        "#,
+        )?;
+
+        let spec = FnSpec::new()
+            .with_vis(syn::parse_quote!(pub))
+            .with_return_type("Response");
+        let results = file
+            .items
+            .iter()
+            .map(|item| spec.check(item))
+            .collect::<Vec<_>>();
+
+        assert!(results[0].as_bool());
+        assert!(results[1].as_bool());
+        assert_eq!(results[2].failure_count(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_str() -> Result<(), TestError> {
+        let func: syn::ImplItemMethod = syn::parse_str(
+            r#"
+            async fn call(&self, req: Request) -> Response { self.inner.call(req).await }
+        "#,
+        )?;
+
+        let results = func
+            .has_fn()
+            .with_block_str("{ self.inner.call(req).await }")
+            .check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        let results = func
+            .has_fn()
+            .with_block_str("{ self.inner.call(req) ")
+            .check();
+        dbg!(&results);
+        let failures = results.into_failures();
+        assert_eq!(failures[0].kind, crate::FailureKind::Invalid);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_attrs_array() -> Result<(), TestError> {
+        let func: syn::ItemFn = syn::parse_str(
+            r#"
+            #[inline]
+            #[must_use]
+            fn answer() -> u32 { 42 }
+            "#,
+        )?;
+
+        let results = func.has_fn().with_attrs(["inline", "must_use"]).check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_attr_values() -> Result<(), TestError> {
+        let func: syn::ItemFn = syn::parse_str(
+            r#"
+            #[cfg(feature = "std")]
+            #[must_use]
+            fn answer() -> u32 { 42 }
+            "#,
+        )?;
+
+        let results = func
+            .has_fn()
+            .with_attr(syn::parse_quote!(#[cfg(feature = "std")]))
+            .with_attr(syn::parse_quote!(#[must_use]))
+            .check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        let results = func
+            .has_fn()
+            .with_attr(syn::parse_quote!(#[cfg(feature = "alloc")]))
+            .with_attr(syn::parse_quote!(#[inline]))
+            .check();
+        dbg!(&results);
+        let failures = results.into_failures();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(
+            failures[0].message(),
+            "Mismatched attribute: ... ( feature = [-\"alloc\"-] {+\"std\"+} ) ]"
+        );
+        assert_eq!(failures[1].message(), "Missing attribute '# [inline]'");
+
+        Ok(())
+    }
 }
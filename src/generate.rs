@@ -0,0 +1,348 @@
+use crate::failure::Failure;
+use crate::function::FnSpec;
+use crate::utils::{parse_str, CheckResult};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    Block, GenericParam, Ident, ImplItemMethod, ItemFn, ItemImpl, Path, Receiver, Signature, Type,
+    Visibility,
+};
+
+const TYPES: &[&str] = &[
+    "u8",
+    "u32",
+    "i64",
+    "bool",
+    "String",
+    "Vec<u8>",
+    "Option<u32>",
+];
+const VISIBILITIES: &[&str] = &["", "pub", "pub(crate)", "pub(super)"];
+const ATTRS: &[&str] = &["inline", "must_use", "doc(hidden)", "allow(dead_code)"];
+const BLOCKS: &[&str] = &[
+    "{ unimplemented!() }",
+    "{ todo!() }",
+    "{ loop {} }",
+    "{ panic!(\"generated\") }",
+];
+
+pub struct Generator {
+    state: u64,
+}
+
+impl Generator {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1,
+        }
+    }
+
+    pub fn item_fns(&mut self, spec: &FnSpec, count: usize) -> Result<Vec<ItemFn>, CheckResult> {
+        (0..count).map(|_| self.item_fn(spec)).collect()
+    }
+
+    pub fn item_fn(&mut self, spec: &FnSpec) -> Result<ItemFn, CheckResult> {
+        let item = self.function(spec)?;
+        match item.sig.receiver() {
+            Some(_) => Err(CheckResult::Failure(vec![Failure::invalid(
+                "receiver",
+                "free fns cannot take self, generate an impl item method instead",
+            )])),
+            None => Ok(item),
+        }
+    }
+
+    fn function(&mut self, spec: &FnSpec) -> Result<ItemFn, CheckResult> {
+        let mut attrs = Vec::new();
+        for attr in &spec.attrs {
+            let path = parse_str::<Path>("attribute", attr)?;
+            attrs.push(syn::parse_quote!(#[#path]));
+        }
+        attrs.extend(spec.attr_values.iter().cloned());
+        let extra_attrs = match spec.strict {
+            true => 0,
+            false => self.below(3),
+        };
+        for _ in 0..extra_attrs {
+            let attr: TokenStream = self.pick(ATTRS).parse().unwrap_or_default();
+            attrs.push(syn::parse_quote!(#[#attr]));
+        }
+
+        let vis = match &spec.vis {
+            Some(vis) => vis.clone(),
+            None => parse_str::<Visibility>("visibility", self.pick(VISIBILITIES))?,
+        };
+
+        let sig = match &spec.signature {
+            Some(signature) => parse_str::<Signature>("signature", signature)?,
+            None => self.signature(spec)?,
+        };
+
+        let block = match (&spec.block, &spec.block_str) {
+            (Some(block), _) => syn::parse2::<Block>(block.clone()).map_err(|e| {
+                CheckResult::Failure(vec![Failure::invalid("block", &e.to_string())])
+            })?,
+            (None, Some(block)) => parse_str::<Block>("block", block)?,
+            (None, None) => parse_str::<Block>("block", self.pick(BLOCKS))?,
+        };
+
+        Ok(ItemFn {
+            attrs,
+            vis,
+            sig,
+            block: Box::new(block),
+        })
+    }
+
+    pub fn impl_item_method(&mut self, spec: &FnSpec) -> Result<ImplItemMethod, CheckResult> {
+        let ItemFn {
+            attrs,
+            vis,
+            sig,
+            block,
+        } = self.function(spec)?;
+        Ok(ImplItemMethod {
+            attrs,
+            vis,
+            defaultness: None,
+            sig,
+            block: *block,
+        })
+    }
+
+    pub fn item_impl(
+        &mut self,
+        self_ty: &str,
+        spec: &FnSpec,
+        count: usize,
+    ) -> Result<ItemImpl, CheckResult> {
+        let self_ty = parse_str::<Type>("type", self_ty)?;
+        let fixed_name =
+            spec.signature.is_some() || spec.exact_name.is_some() || spec.name.is_some();
+        let mut methods = Vec::<ImplItemMethod>::with_capacity(count);
+        for _ in 0..count {
+            let mut method = self.impl_item_method(spec)?;
+            while methods.iter().any(|m| m.sig.ident == method.sig.ident) {
+                if fixed_name {
+                    return Err(CheckResult::Failure(vec![Failure::invalid(
+                        &format!("method name '{}'", method.sig.ident),
+                        "an impl cannot hold more than one method with a fixed name",
+                    )]));
+                }
+                method.sig.ident = Ident::new(
+                    &format!("{}_{}", method.sig.ident, methods.len()),
+                    Span::call_site(),
+                );
+            }
+            methods.push(method);
+        }
+        Ok(syn::parse_quote!(impl #self_ty { #(#methods)* }))
+    }
+
+    fn signature(&mut self, spec: &FnSpec) -> Result<Signature, CheckResult> {
+        let name = match spec.exact_name.as_ref().or(spec.name.as_ref()) {
+            Some(name) => parse_str::<Ident>("name", name)?,
+            None => Ident::new(
+                &format!("generated_{}", self.below(10_000)),
+                Span::call_site(),
+            ),
+        };
+
+        let mut generics = Vec::new();
+        for generic in &spec.generics {
+            generics.push(parse_str::<GenericParam>("generic", generic)?);
+        }
+        let generics = match generics.is_empty() {
+            true => TokenStream::new(),
+            false => quote! { <#(#generics),*> },
+        };
+
+        let asyncness = match spec.asyncness {
+            Some(true) => quote! { async },
+            Some(false) => TokenStream::new(),
+            None if self.below(2) == 0 => quote! { async },
+            None => TokenStream::new(),
+        };
+
+        let mut inputs = Vec::new();
+        let receiver = match &spec.receiver {
+            Some(receiver) => Some(parse_str::<Receiver>("receiver", receiver)?),
+            None if spec.params.iter().any(|p| p == "self") => Some(syn::parse_quote!(&self)),
+            None => None,
+        };
+        if let Some(receiver) = receiver {
+            inputs.push(quote! { #receiver });
+        }
+        for param in spec.params.iter().filter(|p| *p != "self") {
+            let arg = parse_str::<Ident>("parameter", param)?;
+            let ty = parse_str::<Type>("type", self.pick(TYPES))?;
+            inputs.push(quote! { #arg: #ty });
+        }
+        let extra_params = match spec.strict {
+            true => 0,
+            false => self.below(4),
+        };
+        for i in 0..extra_params {
+            let arg = Ident::new(&format!("arg{i}"), Span::call_site());
+            if spec.params.iter().any(|p| arg == p) {
+                continue;
+            }
+            let ty = parse_str::<Type>("type", self.pick(TYPES))?;
+            inputs.push(quote! { #arg: #ty });
+        }
+
+        let output = match &spec.return_type {
+            Some(ty) if ty.trim() == "()" => TokenStream::new(),
+            Some(ty) => {
+                let ty = parse_str::<Type>("type", ty)?;
+                quote! { -> #ty }
+            }
+            None if self.below(2) == 0 => TokenStream::new(),
+            None => {
+                let ty = parse_str::<Type>("type", self.pick(TYPES))?;
+                quote! { -> #ty }
+            }
+        };
+
+        Ok(syn::parse_quote!(#asyncness fn #name #generics(#(#inputs),*) #output))
+    }
+
+    fn pick<'a>(&mut self, choices: &[&'a str]) -> &'a str {
+        choices[self.below(choices.len() as u64) as usize]
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state % bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item_impl::HasImpl;
+    use crate::names::NameOptions;
+    use crate::utils::Check;
+
+    #[test]
+    fn test_item_fns() {
+        let spec = FnSpec::new()
+            .with_vis(syn::parse_quote!(pub))
+            .with_attrs(["must_use"])
+            .with_return_type("Response");
+
+        let items = Generator::new(42).item_fns(&spec, 50).unwrap();
+        for item in &items {
+            spec.check(item).assert();
+        }
+        assert!(items
+            .iter()
+            .any(|item| item.sig.ident != items[0].sig.ident));
+        assert!(items.iter().any(|item| item.sig.asyncness.is_some()));
+        assert!(items.iter().any(|item| item.sig.asyncness.is_none()));
+
+        let again = Generator::new(42).item_fns(&spec, 50).unwrap();
+        assert_eq!(items, again);
+
+        let spec = FnSpec::new()
+            .with_exact_name("fetch_all")
+            .with_async(true)
+            .with_receiver("&mut self");
+
+        let mut generator = Generator::new(42);
+        for _ in 0..50 {
+            spec.check(&generator.impl_item_method(&spec).unwrap())
+                .assert();
+        }
+        assert!(generator.item_fn(&spec).is_err());
+
+        let spec = FnSpec::new()
+            .with_name("FETCH")
+            .with_name_options(NameOptions::new().case_insensitive())
+            .with_exact_name("fetch")
+            .with_async(false)
+            .with_receiver("self")
+            .strict();
+
+        for _ in 0..50 {
+            spec.check(&generator.impl_item_method(&spec).unwrap())
+                .assert();
+        }
+
+        let spec = FnSpec::new().with_params(["self", "id"]);
+        assert!(Generator::new(42).item_fns(&spec, 5).is_err());
+    }
+
+    #[test]
+    fn test_item_fns_strict() {
+        let spec = FnSpec::new()
+            .with_name("lookup")
+            .with_attrs(["must_use"])
+            .with_params(["key", "fallback"])
+            .with_generics(["T"])
+            .strict();
+
+        for item in Generator::new(3).item_fns(&spec, 50).unwrap() {
+            spec.check(&item).assert();
+        }
+    }
+
+    #[test]
+    fn test_item_impl() {
+        let spec = FnSpec::new()
+            .with_receiver("&self")
+            .with_return_type("u32")
+            .strict();
+
+        let impl_ = Generator::new(11).item_impl("Store", &spec, 5).unwrap();
+        impl_
+            .has_impl()
+            .for_type("Store")
+            .inherent()
+            .check()
+            .assert();
+        assert_eq!(impl_.items.len(), 5);
+        for item in &impl_.items {
+            match item {
+                syn::ImplItem::Method(method) => spec.check(method).assert(),
+                _ => panic!("expected method"),
+            }
+        }
+
+        let names = Generator::new(11)
+            .item_impl("Store", &FnSpec::new().with_receiver("&self"), 200)
+            .unwrap()
+            .items
+            .iter()
+            .map(|item| match item {
+                syn::ImplItem::Method(method) => method.sig.ident.to_string(),
+                _ => panic!("expected method"),
+            })
+            .collect::<std::collections::BTreeSet<_>>();
+        assert_eq!(names.len(), 200);
+
+        assert!(Generator::new(11).item_impl("Store<", &spec, 1).is_err());
+        assert!(Generator::new(11)
+            .item_impl("Store", &spec.with_exact_name("get"), 2)
+            .is_err());
+    }
+
+    #[test]
+    fn test_item_fn_signature() {
+        let spec = FnSpec::new().with_signature("async fn fetch(id: Id) -> Item");
+
+        let item = Generator::new(7).item_fn(&spec).unwrap();
+        spec.check(&item).assert();
+
+        let spec = FnSpec::new().with_signature("async fn fetch(&self, id: Id) -> Item");
+
+        let method = Generator::new(7).impl_item_method(&spec).unwrap();
+        spec.check(&method).assert();
+        assert!(Generator::new(7).item_fn(&spec).is_err());
+
+        let spec = FnSpec::new().with_return_type("->");
+        assert!(Generator::new(7).item_fn(&spec).is_err());
+    }
+}
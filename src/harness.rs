@@ -0,0 +1,306 @@
+use crate::failure::Failure;
+use crate::file::HasItems;
+use crate::normalize::{normalize, sort_attrs};
+use crate::utils::{compare_tokens, CheckResult};
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use std::any::Any;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use syn::parse::Parse;
+use syn::{DeriveInput, File, Ident, Item};
+
+pub fn derive<F>(macro_fn: F, input: TokenStream) -> Result<File, CheckResult>
+where
+    F: FnOnce(TokenStream) -> TokenStream,
+{
+    if let Err(e) = syn::parse2::<DeriveInput>(input.clone()) {
+        return Err(invalid("derive input", &e.to_string()));
+    }
+
+    let output = expand("derive macro", || macro_fn(input))?;
+    syn::parse2(output).map_err(|e| invalid("derive output", &e.to_string()))
+}
+
+pub fn attribute<F>(macro_fn: F, attr: TokenStream, item: TokenStream) -> Result<File, CheckResult>
+where
+    F: FnOnce(TokenStream, TokenStream) -> TokenStream,
+{
+    let original = syn::parse2::<Item>(item.clone())
+        .map_err(|e| invalid("attribute input", &e.to_string()))?;
+
+    let output = expand("attribute macro", || macro_fn(attr, item))?;
+    let file: File =
+        syn::parse2(output).map_err(|e| invalid("attribute output", &e.to_string()))?;
+
+    match preserved(&original, &file) {
+        CheckResult::Success => Ok(file),
+        failure => Err(failure),
+    }
+}
+
+pub fn generates<F>(macro_fn: F, input: &str, expected: &str) -> CheckResult
+where
+    F: FnOnce(TokenStream) -> TokenStream,
+{
+    let input = match lex("input", input) {
+        Ok(input) => input,
+        Err(failure) => return failure,
+    };
+    let output = match expand("macro", || macro_fn(input)) {
+        Ok(output) => output,
+        Err(failure) => return failure,
+    };
+    match syn::parse2::<File>(output) {
+        Ok(actual) => compare_expansion(expected, &actual),
+        Err(e) => invalid("macro output", &e.to_string()),
+    }
+}
+
+pub fn generates_attribute<F>(macro_fn: F, attr: &str, item: &str, expected: &str) -> CheckResult
+where
+    F: FnOnce(TokenStream, TokenStream) -> TokenStream,
+{
+    let (attr, item) = match (lex("attribute", attr), lex("input", item)) {
+        (Ok(attr), Ok(item)) => (attr, item),
+        (Err(failure), _) | (_, Err(failure)) => return failure,
+    };
+    match attribute(macro_fn, attr, item) {
+        Ok(actual) => compare_expansion(expected, &actual),
+        Err(failure) => failure,
+    }
+}
+
+pub(crate) fn compare_expansion(expected: &str, actual: &File) -> CheckResult {
+    match syn::parse_file(expected) {
+        Ok(expected) => compare_tokens(
+            "expansion",
+            &normalize(sort_attrs(expected.into_token_stream())),
+            &normalize(sort_attrs(actual.to_token_stream())),
+        ),
+        Err(e) => invalid("expected expansion", &e.to_string()),
+    }
+}
+
+fn lex(subject: &str, source: &str) -> Result<TokenStream, CheckResult> {
+    source
+        .parse()
+        .map_err(|e: proc_macro2::LexError| invalid(subject, &e.to_string()))
+}
+
+fn preserved(original: &Item, file: &File) -> CheckResult {
+    let (kind, name) = match item_ident(original) {
+        Some(ident) => ident,
+        None => return CheckResult::Success,
+    };
+    let subject = format!("{kind} {name}");
+
+    let found = file.walk_items().into_iter().find(|item| {
+        mem::discriminant(*item) == mem::discriminant(original)
+            && item_ident(item).is_some_and(|(_, ident)| ident == name)
+    });
+
+    match (original, found) {
+        (Item::Fn(original), Some(Item::Fn(found))) => {
+            compare_tokens("signature", &original.sig.to_token_stream(), &found.sig)
+                .within(&subject)
+        }
+        (_, Some(_)) => CheckResult::Success,
+        (_, None) => CheckResult::missing(&format!("original item '{subject}'")),
+    }
+}
+
+pub(crate) fn item_ident(item: &Item) -> Option<(&'static str, &Ident)> {
+    match item {
+        Item::Const(i) => Some(("const", &i.ident)),
+        Item::Enum(i) => Some(("enum", &i.ident)),
+        Item::Fn(i) => Some(("fn", &i.sig.ident)),
+        Item::Mod(i) => Some(("mod", &i.ident)),
+        Item::Static(i) => Some(("static", &i.ident)),
+        Item::Struct(i) => Some(("struct", &i.ident)),
+        Item::Trait(i) => Some(("trait", &i.ident)),
+        Item::Type(i) => Some(("type", &i.ident)),
+        Item::Union(i) => Some(("union", &i.ident)),
+        _ => None,
+    }
+}
+
+pub fn function_like<T, F>(macro_fn: F, input: TokenStream) -> Result<T, CheckResult>
+where
+    T: Parse,
+    F: FnOnce(TokenStream) -> TokenStream,
+{
+    let output = expand("function-like macro", || macro_fn(input))?;
+    syn::parse2(output).map_err(|e| invalid("function-like macro output", &e.to_string()))
+}
+
+pub(crate) fn expand<F>(subject: &str, f: F) -> Result<TokenStream, CheckResult>
+where
+    F: FnOnce() -> TokenStream,
+{
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        CheckResult::Failure(vec![Failure::custom(&format!(
+            "{subject} panicked: {}",
+            panic_message(payload.as_ref())
+        ))])
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<non-string panic payload>".to_string()
+    }
+}
+
+fn invalid(subject: &str, error: &str) -> CheckResult {
+    CheckResult::Failure(vec![Failure::invalid(subject, error)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use quote::quote;
+
+    fn derive_new(input: TokenStream) -> TokenStream {
+        let input: DeriveInput = syn::parse2(input).unwrap();
+        let name = input.ident;
+        quote! {
+            impl #name {
+                pub fn new() -> Self { Self {} }
+            }
+        }
+    }
+
+    #[test]
+    fn test_derive() {
+        let file = derive(derive_new, quote! { struct Foo {} }).unwrap();
+
+        assert!(!file.implements("Default", "Foo").as_bool());
+        file.items
+            .has_impl()
+            .for_type("Foo")
+            .inherent()
+            .containing_fn(|f| f.with_name("new"))
+            .check()
+            .assert();
+    }
+
+    fn instrument(_attr: TokenStream, item: TokenStream) -> TokenStream {
+        let mut func: syn::ItemFn = syn::parse2(item).unwrap();
+        let block = func.block;
+        func.block = syn::parse_quote!({ let _span = trace(); #block });
+        func.into_token_stream()
+    }
+
+    #[test]
+    fn test_attribute() {
+        let file = attribute(
+            instrument,
+            quote! { level = "debug" },
+            quote! { pub fn handle(req: Request) -> Response { respond(req) } },
+        )
+        .unwrap();
+
+        file.items
+            .has_fn()
+            .with_name("handle")
+            .with_block_str("{ let _span = trace(); { respond(req) } }")
+            .check()
+            .assert();
+    }
+
+    #[test]
+    fn test_attribute_fail() {
+        let input = quote! { pub fn handle(req: Request) -> Response { respond(req) } };
+
+        let results = attribute(|_, _| quote! { fn other() {} }, quote! {}, input.clone());
+        assert_eq!(
+            results.unwrap_err().into_failures()[0].message(),
+            "Missing original item 'fn handle'"
+        );
+
+        let results = attribute(
+            |_, _| quote! { pub fn handle(req: Request) { respond(req); } },
+            quote! {},
+            input,
+        );
+        let failures = results.unwrap_err().into_failures();
+        assert_eq!(failures[0].path, vec!["fn handle".to_string()]);
+        assert_eq!(
+            failures[0].message(),
+            "Mismatched signature: ... : Request ) [--> Response-]"
+        );
+    }
+
+    fn make_answer(input: TokenStream) -> TokenStream {
+        quote! { { let answer = #input; answer * 2 } }
+    }
+
+    #[test]
+    fn test_function_like() {
+        let expr: syn::Expr = function_like(make_answer, quote! { 21 }).unwrap();
+        assert!(matches!(expr, syn::Expr::Block(_)));
+
+        let block: syn::Block = function_like(make_answer, quote! { 21 }).unwrap();
+        assert_eq!(block.stmts.len(), 2);
+
+        let results = function_like::<syn::Item, _>(make_answer, quote! { 21 }).unwrap_err();
+        assert!(results.failures()[0]
+            .message()
+            .starts_with("Invalid function-like macro output"));
+    }
+
+    #[test]
+    fn test_generates() {
+        generates(
+            derive_new,
+            "struct Foo {}",
+            "impl Foo { pub fn new() -> Self { Self {} } }",
+        )
+        .assert();
+
+        let results = generates(derive_new, "struct Foo {}", "impl Foo {}");
+        assert!(results.failures()[0]
+            .message()
+            .starts_with("Mismatched expansion"));
+
+        generates_attribute(
+            instrument,
+            "",
+            "fn handle() { respond() }",
+            "fn handle() { let _span = trace(); { respond() } }",
+        )
+        .assert();
+    }
+
+    #[cfg(feature = "macros")]
+    #[crate::assert_generates(
+        derive_new,
+        input = { struct Foo {} },
+        expected = { impl Foo { pub fn new() -> Self { Self {} } } },
+    )]
+    #[test]
+    fn test_assert_generates() {}
+
+    #[test]
+    fn test_derive_fail() {
+        let results = derive(|_| panic!("unsupported shape"), quote! { struct Foo; }).unwrap_err();
+        assert_eq!(
+            results.into_failures()[0].message(),
+            "derive macro panicked: unsupported shape"
+        );
+
+        let results = derive(|_| quote! { impl Foo }, quote! { struct Foo; }).unwrap_err();
+        assert_eq!(results.failures()[0].kind, crate::FailureKind::Invalid);
+
+        let results = derive(derive_new, quote! { fn foo() {} }).unwrap_err();
+        assert!(results.failures()[0]
+            .message()
+            .starts_with("Invalid derive input"));
+    }
+}
@@ -0,0 +1,260 @@
+use proc_macro2::Span;
+use std::fmt::{self, Display};
+use syn::{Ident, Item, ItemUse, Path, PathSegment, UseTree};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Import {
+    pub path: String,
+    pub alias: Option<String>,
+    pub glob: bool,
+}
+
+impl Import {
+    pub fn of(item: &ItemUse) -> Vec<Import> {
+        let mut imports = Vec::new();
+        collect(&item.tree, &mut Vec::new(), &mut imports);
+        imports
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        match &self.alias {
+            _ if self.glob => None,
+            Some(alias) => Some(alias),
+            None => self.path.rsplit("::").next(),
+        }
+    }
+
+    pub fn covers(&self, path: &str) -> bool {
+        let path = normalize_path(path);
+        if self.glob {
+            path.rsplit_once("::")
+                .is_some_and(|(parent, _)| parent == self.path)
+        } else {
+            self.path == path
+        }
+    }
+
+    pub fn is_within(&self, prefix: &str) -> bool {
+        let prefix = normalize_path(prefix);
+        self.path == prefix
+            || self
+                .path
+                .strip_prefix(&prefix)
+                .is_some_and(|rest| rest.starts_with("::"))
+    }
+}
+
+impl Display for Import {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path)?;
+        if self.glob {
+            write!(f, "::*")?;
+        }
+        if let Some(alias) = &self.alias {
+            write!(f, " as {alias}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ImportScope {
+    imports: Vec<Import>,
+}
+
+impl ImportScope {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn of<'a>(items: impl IntoIterator<Item = &'a Item>) -> Self {
+        Self {
+            imports: items
+                .into_iter()
+                .flat_map(|item| match item {
+                    Item::Use(item_use) => Import::of(item_use),
+                    _ => Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn imports(&self) -> &[Import] {
+        &self.imports
+    }
+
+    pub(crate) fn resolve(&self, path: &mut Path) {
+        if path.leading_colon.is_some() {
+            return;
+        }
+        let first = match path.segments.first() {
+            Some(segment) => segment.ident.to_string(),
+            None => return,
+        };
+        if let Some(import) = self
+            .imports
+            .iter()
+            .find(|i| i.name() == Some(&first) && i.path.contains("::"))
+        {
+            prepend(path, &import.path);
+        }
+    }
+}
+
+fn prepend(path: &mut Path, target: &str) {
+    let mut segments = target.split("::").collect::<Vec<_>>();
+    let last = segments.pop().unwrap_or_default();
+    let span = path.segments[0].ident.span();
+    path.segments[0].ident = Ident::new(last, span);
+    let rest = std::mem::take(&mut path.segments);
+    path.segments.extend(
+        segments
+            .into_iter()
+            .map(|s| PathSegment::from(Ident::new(s, Span::call_site()))),
+    );
+    path.segments.extend(rest);
+}
+
+pub(crate) fn resolve(path: &str, module: &str) -> String {
+    let mut base = module.split("::").collect::<Vec<_>>();
+    let mut segments = path.split("::").peekable();
+    match segments.peek() {
+        Some(&"self") => {
+            segments.next();
+        }
+        Some(&"super") => {
+            while segments.next_if_eq(&"super").is_some() {
+                base.pop();
+            }
+        }
+        _ => return path.to_string(),
+    }
+    base.extend(segments);
+    base.join("::")
+}
+
+pub(crate) fn normalize_path(path: &str) -> String {
+    let path = path.split_whitespace().collect::<String>();
+    path.trim_start_matches("::").to_string()
+}
+
+fn collect(tree: &UseTree, prefix: &mut Vec<String>, out: &mut Vec<Import>) {
+    match tree {
+        UseTree::Path(path) => {
+            prefix.push(path.ident.to_string());
+            collect(&path.tree, prefix, out);
+            prefix.pop();
+        }
+        UseTree::Name(name) if name.ident == "self" => out.push(Import {
+            path: prefix.join("::"),
+            alias: None,
+            glob: false,
+        }),
+        UseTree::Name(name) => out.push(Import {
+            path: join(prefix, &name.ident.to_string()),
+            alias: None,
+            glob: false,
+        }),
+        UseTree::Rename(rename) => out.push(Import {
+            path: match rename.ident.to_string().as_str() {
+                "self" => prefix.join("::"),
+                ident => join(prefix, ident),
+            },
+            alias: Some(rename.rename.to_string()),
+            glob: false,
+        }),
+        UseTree::Glob(_) => out.push(Import {
+            path: prefix.join("::"),
+            alias: None,
+            glob: true,
+        }),
+        UseTree::Group(group) => {
+            for tree in &group.items {
+                collect(tree, prefix, out);
+            }
+        }
+    }
+}
+
+fn join(prefix: &[String], ident: &str) -> String {
+    prefix
+        .iter()
+        .map(String::as_str)
+        .chain([ident])
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::ToTokens;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_of() {
+        let item: ItemUse = parse_quote!(
+            use ::std::{
+                io::{self, Write as _},
+                collections::*,
+                fmt::Display as Show,
+            };
+        );
+
+        let imports = Import::of(&item);
+        assert_eq!(
+            imports.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec![
+                "std::io",
+                "std::io::Write as _",
+                "std::collections::*",
+                "std::fmt::Display as Show",
+            ]
+        );
+        assert_eq!(imports[3].name(), Some("Show"));
+        assert!(imports[2].covers("std::collections::HashMap"));
+        assert!(!imports[2].covers("std::collections::hash_map::Entry"));
+        assert!(imports[1].is_within("std::io"));
+        assert!(!imports[1].is_within("std::i"));
+    }
+
+    #[test]
+    fn test_scope_resolve() {
+        let scope = ImportScope::of(&[parse_quote!(
+            use std::{collections::HashMap, fmt, io::Result as IoResult};
+        )]);
+        let cases = [
+            ("HashMap<K, V>", "std :: collections :: HashMap < K , V >"),
+            ("fmt::Display", "std :: fmt :: Display"),
+            ("IoResult<()>", "std :: io :: Result < () >"),
+            ("::fmt::Display", ":: fmt :: Display"),
+            ("Vec<u8>", "Vec < u8 >"),
+        ];
+        for (input, expected) in cases {
+            let mut path: Path = syn::parse_str(input).unwrap();
+            scope.resolve(&mut path);
+            assert_eq!(path.to_token_stream().to_string(), expected);
+        }
+
+        let mut path: Path = parse_quote!(HashMap<K, V>);
+        ImportScope::new().resolve(&mut path);
+        assert_eq!(path.to_token_stream().to_string(), "HashMap < K , V >");
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(
+            resolve("super::http::Request", "crate::domain"),
+            "crate::http::Request"
+        );
+        assert_eq!(
+            resolve("super::super::Error", "crate::a::b"),
+            "crate::Error"
+        );
+        assert_eq!(
+            resolve("self::model", "crate::domain"),
+            "crate::domain::model"
+        );
+        assert_eq!(resolve("std::fmt", "crate::domain"), "std::fmt");
+    }
+}
@@ -0,0 +1,109 @@
+use crate::failure::{Failure, Location};
+use crate::normalize::{normalize, sort_attrs};
+use crate::utils::{compare_tokens, CheckResult};
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::Item;
+
+pub fn item_eq<T>(actual: T, expected: TokenStream) -> CheckResult
+where
+    T: ToTokens,
+{
+    compare_items(actual, expected, sort_attrs)
+}
+
+pub fn item_eq_strict<T>(actual: T, expected: TokenStream) -> CheckResult
+where
+    T: ToTokens,
+{
+    compare_items(actual, expected, |tokens| tokens)
+}
+
+#[track_caller]
+pub fn assert_item_eq<T>(actual: T, expected: TokenStream)
+where
+    T: ToTokens,
+{
+    item_eq(actual, expected).assert()
+}
+
+#[track_caller]
+pub fn assert_item_eq_strict<T>(actual: T, expected: TokenStream)
+where
+    T: ToTokens,
+{
+    item_eq_strict(actual, expected).assert()
+}
+
+fn compare_items<T, F>(actual: T, expected: TokenStream, order: F) -> CheckResult
+where
+    T: ToTokens,
+    F: Fn(TokenStream) -> TokenStream,
+{
+    let expected = match syn::parse2::<Item>(expected) {
+        Ok(item) => normalize(order(item.to_token_stream())),
+        Err(e) => {
+            return CheckResult::Failure(vec![Failure::invalid("expected item", &e.to_string())])
+        }
+    };
+
+    compare_tokens(
+        "item",
+        &expected,
+        &normalize(order(actual.to_token_stream())),
+    )
+    .at(Some(Location::of(&actual)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_item_eq() -> Result<(), TestError> {
+        let item: syn::ItemStruct = syn::parse_str(
+            r#"
+            pub struct Foo {
+                id: u64,
+            }
+        "#,
+        )?;
+
+        assert_item_eq(&item, quote::quote! { pub struct Foo { id: u64 } });
+
+        let results = item_eq(&item, quote::quote! { pub struct Foo { id: u32 } });
+        assert_eq!(
+            results.into_failures()[0].to_string(),
+            "Mismatched item: ... { id : [-u32-] {+u64+} } (at 2:13)"
+        );
+
+        let results = item_eq(&item, quote::quote! { pub struct });
+        assert_eq!(results.failure_count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_item_eq_attr_order() -> Result<(), TestError> {
+        let item: syn::ItemFn = syn::parse_str("#[inline] #[must_use] pub fn id() -> u8 { 0 }")?;
+
+        assert_item_eq(
+            &item,
+            quote::quote! { #[must_use] #[inline] pub fn id() -> u8 { 0 } },
+        );
+        assert_item_eq_strict(
+            &item,
+            quote::quote! { #[inline] #[must_use] pub fn id() -> u8 { 0 } },
+        );
+        assert!(!item_eq_strict(
+            &item,
+            quote::quote! { #[must_use] #[inline] pub fn id() -> u8 { 0 } }
+        )
+        .as_bool());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,404 @@
+use crate::assoc::compare_exprs;
+use crate::failure::Location;
+use crate::names::NameOptions;
+use crate::types::compare_types;
+use crate::utils::{
+    attr_paths, check_option, compare_attrs, parse_str, unexpected_extras, Check, CheckResult,
+};
+use crate::visibility::compare_vis;
+use syn::{Attribute, Expr, Item, ItemConst, Visibility};
+
+pub trait HasConst {
+    fn has_const(&self) -> AssertConst<'_, Self>
+    where
+        Self: Sized,
+    {
+        AssertConst::new(self)
+    }
+
+    fn breadcrumb(&self) -> Option<String> {
+        None
+    }
+
+    fn location(&self) -> Option<Location> {
+        None
+    }
+
+    fn has_name(&self, name: &str) -> CheckResult;
+    fn has_exact_name(&self, name: &str) -> CheckResult;
+    fn has_name_with(&self, name: &str, options: &NameOptions) -> CheckResult;
+    fn has_vis(&self, vis: &Visibility) -> CheckResult;
+    fn has_attrs(&self, attrs: &[String]) -> CheckResult;
+    fn has_attr_values(&self, attrs: &[Attribute]) -> CheckResult;
+    fn has_ty(&self, ty: &str) -> CheckResult;
+    fn has_value(&self, value: &str) -> CheckResult;
+    fn has_expr(&self, expr: &Expr) -> CheckResult;
+    fn has_only_attrs(&self, attrs: &[String]) -> CheckResult;
+}
+
+impl HasConst for ItemConst {
+    fn breadcrumb(&self) -> Option<String> {
+        Some(format!("const {}", self.ident))
+    }
+
+    fn location(&self) -> Option<Location> {
+        Some(Location::of(self))
+    }
+
+    fn has_name(&self, name: &str) -> CheckResult {
+        CheckResult::compare_ident("name", name, &self.ident)
+    }
+
+    fn has_exact_name(&self, name: &str) -> CheckResult {
+        CheckResult::compare_named("name", name, &self.ident)
+    }
+
+    fn has_name_with(&self, name: &str, options: &NameOptions) -> CheckResult {
+        CheckResult::compare_ident_with("name", name, &self.ident, options)
+    }
+
+    fn has_vis(&self, vis: &Visibility) -> CheckResult {
+        compare_vis(vis, &self.vis)
+    }
+
+    fn has_attrs(&self, attrs: &[String]) -> CheckResult {
+        CheckResult::contains_display(attr_paths(&self.attrs), attrs)
+    }
+
+    fn has_attr_values(&self, attrs: &[Attribute]) -> CheckResult {
+        compare_attrs(&self.attrs, attrs)
+    }
+
+    fn has_ty(&self, ty: &str) -> CheckResult {
+        compare_types(ty, &self.ty)
+    }
+
+    fn has_value(&self, value: &str) -> CheckResult {
+        match parse_str::<Expr>("expression", value) {
+            Ok(expected) => self.has_expr(&expected),
+            Err(failure) => failure,
+        }
+    }
+
+    fn has_expr(&self, expr: &Expr) -> CheckResult {
+        compare_exprs("const value", expr, &self.expr)
+    }
+
+    fn has_only_attrs(&self, attrs: &[String]) -> CheckResult {
+        unexpected_extras("attribute", attr_paths(&self.attrs), attrs)
+    }
+}
+
+macro_rules! hasconst_item {
+    ($v:ident, $t: ty) => {
+        paste::paste! {
+            fn [<has_ $v>](&self, $v: $t) -> CheckResult {
+                match self {
+                    Item::Const(c) => c.[<has_ $v>]($v),
+                    _ => CheckResult::missing(stringify!($v)),
+                }
+            }
+        }
+    };
+}
+
+impl HasConst for Item {
+    fn breadcrumb(&self) -> Option<String> {
+        match self {
+            Item::Const(c) => c.breadcrumb(),
+            _ => None,
+        }
+    }
+
+    fn location(&self) -> Option<Location> {
+        Some(Location::of(self))
+    }
+
+    hasconst_item!(name, &str);
+    hasconst_item!(exact_name, &str);
+
+    fn has_name_with(&self, name: &str, options: &NameOptions) -> CheckResult {
+        match self {
+            Item::Const(c) => c.has_name_with(name, options),
+            _ => CheckResult::missing("name"),
+        }
+    }
+
+    hasconst_item!(vis, &Visibility);
+    hasconst_item!(attrs, &[String]);
+    hasconst_item!(attr_values, &[Attribute]);
+    hasconst_item!(ty, &str);
+    hasconst_item!(value, &str);
+    hasconst_item!(expr, &Expr);
+    hasconst_item!(only_attrs, &[String]);
+}
+
+macro_rules! hasconst_vec {
+    ($v: ident, $t: ty) => {
+        paste::paste! {
+            fn [<has_ $v>](&self, $v: $t) -> CheckResult {
+                CheckResult::any(self.iter().map(|f| f.[<has_ $v>](&$v).in_item(f.breadcrumb(), f.location())))
+            }
+        }
+    };
+}
+
+impl<T> HasConst for Vec<T>
+where
+    T: HasConst,
+{
+    hasconst_vec!(name, &str);
+    hasconst_vec!(exact_name, &str);
+
+    fn has_name_with(&self, name: &str, options: &NameOptions) -> CheckResult {
+        CheckResult::any(self.iter().map(|c| {
+            c.has_name_with(name, options)
+                .in_item(c.breadcrumb(), c.location())
+        }))
+    }
+
+    hasconst_vec!(vis, &Visibility);
+    hasconst_vec!(attrs, &[String]);
+    hasconst_vec!(attr_values, &[Attribute]);
+    hasconst_vec!(ty, &str);
+    hasconst_vec!(value, &str);
+    hasconst_vec!(expr, &Expr);
+    hasconst_vec!(only_attrs, &[String]);
+}
+
+#[must_use]
+pub struct AssertConst<'s, T> {
+    t: &'s T,
+    label: Option<&'s str>,
+    strict: bool,
+    name: Option<&'s str>,
+    exact_name: Option<&'s str>,
+    name_options: NameOptions,
+    vis: Option<Visibility>,
+    attrs: Vec<String>,
+    attr_values: Vec<Attribute>,
+    ty: Option<&'s str>,
+    value: Option<&'s str>,
+    expr: Option<Expr>,
+}
+
+impl<'s, T> AssertConst<'s, T> {
+    pub fn new(t: &'s T) -> Self {
+        Self {
+            t,
+            label: Default::default(),
+            strict: Default::default(),
+            name: Default::default(),
+            exact_name: Default::default(),
+            name_options: Default::default(),
+            vis: Default::default(),
+            attrs: Default::default(),
+            attr_values: Default::default(),
+            ty: Default::default(),
+            value: Default::default(),
+            expr: Default::default(),
+        }
+    }
+
+    pub fn labeled(self, label: &'s str) -> Self {
+        Self {
+            label: Some(label),
+            ..self
+        }
+    }
+
+    pub fn strict(self) -> Self {
+        Self {
+            strict: true,
+            ..self
+        }
+    }
+
+    pub fn with_name(self, name: &'s str) -> Self {
+        Self {
+            name: Some(name),
+            ..self
+        }
+    }
+
+    pub fn with_exact_name(self, exact_name: &'s str) -> Self {
+        Self {
+            exact_name: Some(exact_name),
+            ..self
+        }
+    }
+
+    pub fn with_name_options(self, name_options: NameOptions) -> Self {
+        Self {
+            name_options,
+            ..self
+        }
+    }
+
+    pub fn with_vis(self, vis: Visibility) -> Self {
+        Self {
+            vis: Some(vis),
+            ..self
+        }
+    }
+
+    pub fn with_attrs<I, S>(self, attrs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            attrs: attrs.into_iter().map(|a| a.as_ref().to_string()).collect(),
+            ..self
+        }
+    }
+
+    pub fn with_attr(mut self, attr: Attribute) -> Self {
+        self.attr_values.push(attr);
+        self
+    }
+
+    pub fn with_ty(self, ty: &'s str) -> Self {
+        Self {
+            ty: Some(ty),
+            ..self
+        }
+    }
+
+    pub fn with_value(self, value: &'s str) -> Self {
+        Self {
+            value: Some(value),
+            ..self
+        }
+    }
+
+    pub fn with_expr(self, expr: Expr) -> Self {
+        Self {
+            expr: Some(expr),
+            ..self
+        }
+    }
+}
+
+impl<'s, T> AssertConst<'s, T>
+where
+    T: HasConst,
+{
+    fn is_empty(&self) -> bool {
+        !self.strict
+            && self.name.is_none()
+            && self.exact_name.is_none()
+            && self.vis.is_none()
+            && self.ty.is_none()
+            && self.value.is_none()
+            && self.expr.is_none()
+            && self.attrs.is_empty()
+            && self.attr_values.is_empty()
+    }
+
+    fn check_strict(&self) -> CheckResult {
+        let mut attrs = self.attrs.clone();
+        attrs.extend(attr_paths(&self.attr_values));
+        self.t.has_only_attrs(&attrs)
+    }
+}
+
+impl<'s, T> Check for AssertConst<'s, T>
+where
+    T: HasConst,
+{
+    fn check(self) -> CheckResult {
+        let (breadcrumb, location) = (self.t.breadcrumb(), self.t.location());
+        let label = self.label;
+        let empty = match self.is_empty() {
+            true => CheckResult::missing("constraints in const assertion"),
+            false => CheckResult::Success,
+        };
+        let strict = match self.strict {
+            true => self.check_strict(),
+            false => CheckResult::Success,
+        };
+        let results = (empty
+            + strict
+            + self.name.map_or(CheckResult::Success, |name| {
+                self.t.has_name_with(name, &self.name_options)
+            })
+            + check_option!(self, exact_name)
+            + check_option!(self, vis)
+            + check_option!(self, ty)
+            + check_option!(self, value)
+            + check_option!(self, expr)
+            + self.t.has_attrs(&self.attrs)
+            + self.t.has_attr_values(&self.attr_values))
+        .in_item(breadcrumb, location);
+        match label {
+            Some(label) => results.within(label),
+            None => results,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_const_value() -> Result<(), TestError> {
+        let file: syn::File = syn::parse_str(
+            r#"
+            pub const MAX: usize = 1_024;
+            const RATIO: f64 = 0.50;
+            #[allow(dead_code)]
+            const LIMIT: u32 = MAX as u32 * 2;
+        "#,
+        )?;
+
+        file.items[0]
+            .has_const()
+            .with_name("MAX")
+            .with_vis(syn::parse_quote!(pub))
+            .with_ty("usize")
+            .with_value("1024")
+            .check()
+            .assert();
+        file.items[1]
+            .has_const()
+            .with_name("RATIO")
+            .with_value("0.5")
+            .strict()
+            .check()
+            .assert();
+        file.items[2]
+            .has_const()
+            .with_name("LIMIT")
+            .with_expr(syn::parse_quote!(MAX as u32 * 2))
+            .with_attrs(["allow"])
+            .check()
+            .assert();
+
+        let results = file.items[0]
+            .has_const()
+            .with_ty("u64")
+            .with_value("2048")
+            .check();
+        assert_eq!(
+            results
+                .into_failures()
+                .iter()
+                .map(|f| f.message())
+                .collect::<Vec<_>>(),
+            vec![
+                "Expected const value to be '2048', got '1_024'",
+                "Expected type to be 'u64', got 'usize'",
+            ]
+        );
+
+        let results = file.items[2].has_const().strict().check();
+        assert!(!results.as_bool());
+
+        Ok(())
+    }
+}
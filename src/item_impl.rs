@@ -0,0 +1,1350 @@
+use crate::assoc::{AssocConst, AssocType};
+use crate::failure::{Failure, Location};
+use crate::function::{AssertFn, HasFn};
+use crate::normalize::normalize;
+use crate::types::{compare_types, types_eq};
+use crate::utils::{
+    attr_paths, check_option, compare_attrs, compare_parsed, compare_tokens, generic_names,
+    parse_str, unexpected_extras, Check, CheckResult,
+};
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use std::collections::BTreeSet;
+use syn::punctuated::Punctuated;
+use syn::{
+    Attribute, Expr, GenericParam, Generics, ImplItem, ImplItemMethod, Item, ItemImpl, Path, Token,
+    WhereClause, WherePredicate,
+};
+
+#[derive(Debug, Clone)]
+pub struct Override {
+    name: String,
+    overridden: bool,
+    block: Option<TokenStream>,
+}
+
+pub type FnAssertion<'s> =
+    Box<dyn for<'a> Fn(AssertFn<'a, ImplItemMethod>) -> AssertFn<'a, ImplItemMethod> + 's>;
+
+pub trait HasImpl {
+    fn has_impl(&self) -> AssertImpl<'_, Self>
+    where
+        Self: Sized,
+    {
+        AssertImpl::new(self)
+    }
+
+    fn breadcrumb(&self) -> Option<String> {
+        None
+    }
+
+    fn location(&self) -> Option<Location> {
+        None
+    }
+
+    fn has_trait_path(&self, trait_path: &str) -> CheckResult;
+    fn has_self_ty(&self, self_ty: &str) -> CheckResult;
+    fn has_inherent(&self, inherent: &bool) -> CheckResult;
+    fn has_negative(&self, negative: &bool) -> CheckResult;
+    fn has_unsafety(&self, unsafety: &bool) -> CheckResult;
+    fn has_generics(&self, generics: &str) -> CheckResult;
+    fn has_where_clause(&self, where_clause: &str) -> CheckResult;
+    fn has_impl_generics(&self, impl_generics: &[String]) -> CheckResult;
+    fn has_where_predicates(&self, where_predicates: &[String]) -> CheckResult;
+    fn has_attrs(&self, attrs: &[String]) -> CheckResult;
+    fn has_attr_values(&self, attrs: &[Attribute]) -> CheckResult;
+    fn has_assoc_consts(&self, consts: &[AssocConst]) -> CheckResult;
+    fn has_assoc_types(&self, types: &[AssocType]) -> CheckResult;
+    fn has_fns(&self, fns: &[FnAssertion<'_>]) -> CheckResult;
+    fn has_overrides(&self, overrides: &[Override]) -> CheckResult;
+    fn has_only_attrs(&self, attrs: &[String]) -> CheckResult;
+    fn has_only_generics(&self, generics: &[String]) -> CheckResult;
+    fn has_only_where_predicates(&self, where_predicates: &[String]) -> CheckResult;
+    fn has_only_consts(&self, consts: &[String]) -> CheckResult;
+    fn has_only_assoc_types(&self, types: &[String]) -> CheckResult;
+    fn has_only_fns(&self, fns: &[FnAssertion<'_>], names: &[String]) -> CheckResult;
+}
+
+fn generic_params_eq(expected: &GenericParam, actual: &GenericParam) -> bool {
+    match (expected, actual) {
+        (GenericParam::Type(e), GenericParam::Type(a)) => {
+            e.ident == a.ident
+                && bounds_eq(&e.bounds, &a.bounds)
+                && match (&e.default, &a.default) {
+                    (Some(e), Some(a)) => types_eq(e, a),
+                    (e, a) => e.is_none() && a.is_none(),
+                }
+        }
+        (GenericParam::Lifetime(e), GenericParam::Lifetime(a)) => {
+            e.lifetime == a.lifetime && bounds_eq(&e.bounds, &a.bounds)
+        }
+        (GenericParam::Const(e), GenericParam::Const(a)) => {
+            e.ident == a.ident
+                && types_eq(&e.ty, &a.ty)
+                && match (&e.default, &a.default) {
+                    (Some(e), Some(a)) => tokens_eq(e, a),
+                    (e, a) => e.is_none() && a.is_none(),
+                }
+        }
+        _ => false,
+    }
+}
+
+fn predicates_eq(expected: &WherePredicate, actual: &WherePredicate) -> bool {
+    match (expected, actual) {
+        (WherePredicate::Type(e), WherePredicate::Type(a)) => {
+            tokens_eq(&e.lifetimes, &a.lifetimes)
+                && types_eq(&e.bounded_ty, &a.bounded_ty)
+                && bounds_eq(&e.bounds, &a.bounds)
+        }
+        (WherePredicate::Lifetime(e), WherePredicate::Lifetime(a)) => {
+            e.lifetime == a.lifetime && bounds_eq(&e.bounds, &a.bounds)
+        }
+        (WherePredicate::Eq(e), WherePredicate::Eq(a)) => {
+            types_eq(&e.lhs_ty, &a.lhs_ty) && types_eq(&e.rhs_ty, &a.rhs_ty)
+        }
+        _ => false,
+    }
+}
+
+fn parse_predicates(predicates: &[String]) -> (Vec<WherePredicate>, Vec<Failure>) {
+    let mut failures = Vec::new();
+    let parsed = predicates
+        .iter()
+        .filter_map(
+            |p| match parse_str::<WherePredicate>("where predicate", p) {
+                Ok(predicate) => Some(predicate),
+                Err(failure) => {
+                    failures.extend(failure.into_failures());
+                    None
+                }
+            },
+        )
+        .collect();
+    (parsed, failures)
+}
+
+fn unmatched_predicates<'p>(
+    predicates: &'p [WherePredicate],
+    others: &'p [WherePredicate],
+) -> impl Iterator<Item = String> + 'p {
+    predicates
+        .iter()
+        .filter(|p| !others.iter().any(|o| predicates_eq(p, o)))
+        .map(|p| format!("where predicate '{}'", p.to_token_stream()))
+}
+
+fn bounds_eq<T>(expected: &Punctuated<T, Token![+]>, actual: &Punctuated<T, Token![+]>) -> bool
+where
+    T: ToTokens,
+{
+    let bounds = |bounds: &Punctuated<T, Token![+]>| {
+        bounds
+            .iter()
+            .map(|b| normalize(b.to_token_stream()).to_string())
+            .collect::<BTreeSet<_>>()
+    };
+    bounds(expected) == bounds(actual)
+}
+
+fn tokens_eq<T>(expected: &T, actual: &T) -> bool
+where
+    T: ToTokens,
+{
+    normalize(expected.to_token_stream()).to_string()
+        == normalize(actual.to_token_stream()).to_string()
+}
+
+fn where_predicates_of(generics: &Generics) -> Vec<WherePredicate> {
+    generics
+        .where_clause
+        .iter()
+        .flat_map(|w| w.predicates.iter().cloned())
+        .collect()
+}
+
+fn impl_breadcrumb(impl_: &ItemImpl) -> String {
+    match &impl_.trait_ {
+        Some((negative, path, _)) => format!(
+            "impl {}{} for {}",
+            if negative.is_some() { "!" } else { "" },
+            path.to_token_stream(),
+            impl_.self_ty.to_token_stream()
+        ),
+        None => format!("impl {}", impl_.self_ty.to_token_stream()),
+    }
+}
+
+impl HasImpl for ItemImpl {
+    fn breadcrumb(&self) -> Option<String> {
+        Some(impl_breadcrumb(self))
+    }
+
+    fn location(&self) -> Option<Location> {
+        Some(Location::of(self))
+    }
+
+    fn has_trait_path(&self, trait_path: &str) -> CheckResult {
+        match &self.trait_ {
+            Some((_, path, _)) => compare_parsed::<Path>("path", trait_path, path),
+            None => CheckResult::missing("trait"),
+        }
+    }
+
+    fn has_self_ty(&self, self_ty: &str) -> CheckResult {
+        compare_types(self_ty, &self.self_ty)
+    }
+
+    fn has_inherent(&self, inherent: &bool) -> CheckResult {
+        CheckResult::compare_named("inherent", inherent, self.trait_.is_none())
+    }
+
+    fn has_negative(&self, negative: &bool) -> CheckResult {
+        let actual = matches!(&self.trait_, Some((Some(_), _, _)));
+        CheckResult::compare_named("negative", negative, actual)
+    }
+
+    fn has_impl_generics(&self, impl_generics: &[String]) -> CheckResult {
+        let params = generic_names(&self.generics)
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+        CheckResult::contains_display(params, impl_generics)
+    }
+
+    fn has_where_predicates(&self, where_predicates: &[String]) -> CheckResult {
+        let (expected, mut failures) = parse_predicates(where_predicates);
+        let actual = where_predicates_of(&self.generics);
+        failures.extend(unmatched_predicates(&expected, &actual).map(|p| Failure::missing(&p)));
+        failures.into()
+    }
+
+    fn has_unsafety(&self, unsafety: &bool) -> CheckResult {
+        CheckResult::compare_named("unsafe", unsafety, self.unsafety.is_some())
+    }
+
+    fn has_generics(&self, generics: &str) -> CheckResult {
+        match parse_str::<Generics>("generics", generics) {
+            Ok(expected)
+                if expected.params.len() == self.generics.params.len()
+                    && expected
+                        .params
+                        .iter()
+                        .zip(&self.generics.params)
+                        .all(|(e, a)| generic_params_eq(e, a)) =>
+            {
+                CheckResult::Success
+            }
+            Ok(expected) => CheckResult::Failure(vec![Failure::mismatch(
+                "generics",
+                &expected.params.to_token_stream().to_string(),
+                &self.generics.params.to_token_stream().to_string(),
+            )]),
+            Err(failure) => failure,
+        }
+    }
+
+    fn has_where_clause(&self, where_clause: &str) -> CheckResult {
+        let expected = match parse_str::<WhereClause>("where clause", where_clause) {
+            Ok(expected) => expected.predicates.into_iter().collect::<Vec<_>>(),
+            Err(failure) => return failure,
+        };
+        let actual = where_predicates_of(&self.generics);
+
+        let missing = unmatched_predicates(&expected, &actual).map(|p| Failure::missing(&p));
+        let unexpected = unmatched_predicates(&actual, &expected).map(|p| Failure::unexpected(&p));
+        missing.chain(unexpected).collect::<Vec<_>>().into()
+    }
+
+    fn has_attrs(&self, attrs: &[String]) -> CheckResult {
+        CheckResult::contains_display(attr_paths(&self.attrs), attrs)
+    }
+
+    fn has_attr_values(&self, attrs: &[Attribute]) -> CheckResult {
+        compare_attrs(&self.attrs, attrs)
+    }
+
+    fn has_assoc_consts(&self, consts: &[AssocConst]) -> CheckResult {
+        CheckResult::any(consts.iter().map(|c| {
+            c.check(self.items.iter().filter_map(|i| match i {
+                ImplItem::Const(c) => Some((&c.ident, &c.ty, Some(&c.expr))),
+                _ => None,
+            }))
+        }))
+    }
+
+    fn has_assoc_types(&self, types: &[AssocType]) -> CheckResult {
+        CheckResult::any(types.iter().map(|t| {
+            t.check(self.items.iter().filter_map(|i| match i {
+                ImplItem::Type(t) => Some((&t.ident, &t.ty)),
+                _ => None,
+            }))
+        }))
+    }
+
+    fn has_fns(&self, fns: &[FnAssertion<'_>]) -> CheckResult {
+        let methods = self
+            .items
+            .iter()
+            .filter_map(|i| match i {
+                ImplItem::Method(m) => Some(m),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        CheckResult::any(fns.iter().map(|f| {
+            let mut failures = Vec::new();
+            for method in &methods {
+                match f(method.has_fn()).check() {
+                    CheckResult::Success => return CheckResult::Success,
+                    CheckResult::Failure(method_failures) => failures.extend(method_failures),
+                }
+            }
+            if methods.is_empty() {
+                failures.push(Failure::missing("fn"));
+            }
+            failures.into()
+        }))
+    }
+
+    fn has_overrides(&self, overrides: &[Override]) -> CheckResult {
+        CheckResult::any(overrides.iter().map(|o| {
+            let method = self.items.iter().find_map(|i| match i {
+                ImplItem::Method(m) if m.sig.ident == o.name => Some(m),
+                _ => None,
+            });
+
+            match (method, o.overridden, &o.block) {
+                (None, false, _) => CheckResult::Success,
+                (None, true, _) => {
+                    CheckResult::missing(&format!("override of method '{}'", o.name))
+                }
+                (Some(_), false, _) => CheckResult::Failure(vec![Failure::unexpected(&format!(
+                    "override of method '{}'",
+                    o.name
+                ))]),
+                (Some(_), true, None) => CheckResult::Success,
+                (Some(m), true, Some(block)) => {
+                    compare_tokens(&format!("override of method '{}'", o.name), block, &m.block)
+                }
+            }
+        }))
+    }
+
+    fn has_only_attrs(&self, attrs: &[String]) -> CheckResult {
+        unexpected_extras("attribute", attr_paths(&self.attrs), attrs)
+    }
+
+    fn has_only_generics(&self, generics: &[String]) -> CheckResult {
+        unexpected_extras("generic", generic_names(&self.generics), generics)
+    }
+
+    fn has_only_where_predicates(&self, where_predicates: &[String]) -> CheckResult {
+        let (expected, mut failures) = parse_predicates(where_predicates);
+        let actual = where_predicates_of(&self.generics);
+        failures.extend(unmatched_predicates(&actual, &expected).map(|p| Failure::unexpected(&p)));
+        failures.into()
+    }
+
+    fn has_only_consts(&self, consts: &[String]) -> CheckResult {
+        let actual = self.items.iter().filter_map(|i| match i {
+            ImplItem::Const(c) => Some(c.ident.to_string()),
+            _ => None,
+        });
+        unexpected_extras("assoc const", actual, consts)
+    }
+
+    fn has_only_assoc_types(&self, types: &[String]) -> CheckResult {
+        let actual = self.items.iter().filter_map(|i| match i {
+            ImplItem::Type(t) => Some(t.ident.to_string()),
+            _ => None,
+        });
+        unexpected_extras("assoc type", actual, types)
+    }
+
+    fn has_only_fns(&self, fns: &[FnAssertion<'_>], names: &[String]) -> CheckResult {
+        self.items
+            .iter()
+            .filter_map(|i| match i {
+                ImplItem::Method(m) => Some(m),
+                _ => None,
+            })
+            .filter(|m| {
+                !names.iter().any(|n| m.sig.ident == n)
+                    && !fns.iter().any(|f| f(m.has_fn()).check().as_bool())
+            })
+            .map(|m| {
+                Failure::unexpected(&format!("fn '{}'", m.sig.ident))
+                    .at(Some(Location::of(&m.sig.ident)))
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+macro_rules! hasimpl_item {
+    ($v:ident, $t: ty) => {
+        paste::paste! {
+            fn [<has_ $v>](&self, $v: $t) -> CheckResult {
+                match self {
+                    Item::Impl(impl_) => impl_.[<has_ $v>]($v),
+                    _ => CheckResult::missing(stringify!($v)),
+                }
+            }
+        }
+    };
+}
+
+impl HasImpl for Item {
+    fn breadcrumb(&self) -> Option<String> {
+        match self {
+            Item::Impl(impl_) => impl_.breadcrumb(),
+            _ => None,
+        }
+    }
+
+    fn location(&self) -> Option<Location> {
+        Some(Location::of(self))
+    }
+
+    hasimpl_item!(trait_path, &str);
+    hasimpl_item!(self_ty, &str);
+    hasimpl_item!(inherent, &bool);
+    hasimpl_item!(negative, &bool);
+    hasimpl_item!(unsafety, &bool);
+    hasimpl_item!(generics, &str);
+    hasimpl_item!(where_clause, &str);
+    hasimpl_item!(impl_generics, &[String]);
+    hasimpl_item!(where_predicates, &[String]);
+    hasimpl_item!(attrs, &[String]);
+    hasimpl_item!(attr_values, &[Attribute]);
+    hasimpl_item!(assoc_consts, &[AssocConst]);
+    hasimpl_item!(assoc_types, &[AssocType]);
+    hasimpl_item!(fns, &[FnAssertion<'_>]);
+    hasimpl_item!(overrides, &[Override]);
+    hasimpl_item!(only_attrs, &[String]);
+    hasimpl_item!(only_generics, &[String]);
+    hasimpl_item!(only_where_predicates, &[String]);
+    hasimpl_item!(only_consts, &[String]);
+    hasimpl_item!(only_assoc_types, &[String]);
+
+    fn has_only_fns(&self, fns: &[FnAssertion<'_>], names: &[String]) -> CheckResult {
+        match self {
+            Item::Impl(impl_) => impl_.has_only_fns(fns, names),
+            _ => CheckResult::missing("fns"),
+        }
+    }
+}
+
+macro_rules! hasimpl_vec {
+    ($v: ident, $t: ty) => {
+        paste::paste! {
+            fn [<has_ $v>](&self, $v: $t) -> CheckResult {
+                CheckResult::any(self.iter().map(|f| f.[<has_ $v>](&$v).in_item(f.breadcrumb(), f.location())))
+            }
+        }
+    };
+}
+
+impl<T> HasImpl for Vec<T>
+where
+    T: HasImpl,
+{
+    hasimpl_vec!(trait_path, &str);
+    hasimpl_vec!(self_ty, &str);
+    hasimpl_vec!(inherent, &bool);
+    hasimpl_vec!(negative, &bool);
+    hasimpl_vec!(unsafety, &bool);
+    hasimpl_vec!(generics, &str);
+    hasimpl_vec!(where_clause, &str);
+    hasimpl_vec!(impl_generics, &[String]);
+    hasimpl_vec!(where_predicates, &[String]);
+    hasimpl_vec!(attrs, &[String]);
+    hasimpl_vec!(attr_values, &[Attribute]);
+    hasimpl_vec!(assoc_consts, &[AssocConst]);
+    hasimpl_vec!(assoc_types, &[AssocType]);
+    hasimpl_vec!(fns, &[FnAssertion<'_>]);
+    hasimpl_vec!(overrides, &[Override]);
+    hasimpl_vec!(only_attrs, &[String]);
+    hasimpl_vec!(only_generics, &[String]);
+    hasimpl_vec!(only_where_predicates, &[String]);
+    hasimpl_vec!(only_consts, &[String]);
+    hasimpl_vec!(only_assoc_types, &[String]);
+
+    fn has_only_fns(&self, fns: &[FnAssertion<'_>], names: &[String]) -> CheckResult {
+        CheckResult::any(self.iter().map(|f| {
+            f.has_only_fns(fns, names)
+                .in_item(f.breadcrumb(), f.location())
+        }))
+    }
+}
+
+#[must_use]
+pub struct AssertImpl<'s, T> {
+    t: &'s T,
+    label: Option<&'s str>,
+    strict: bool,
+    trait_path: Option<&'s str>,
+    self_ty: Option<&'s str>,
+    inherent: Option<bool>,
+    negative: Option<bool>,
+    unsafety: Option<bool>,
+    generics: Option<&'s str>,
+    where_clause: Option<&'s str>,
+    impl_generics: Vec<String>,
+    where_predicates: Vec<String>,
+    attrs: Vec<String>,
+    attr_values: Vec<Attribute>,
+    assoc_consts: Vec<AssocConst>,
+    assoc_types: Vec<AssocType>,
+    fns: Vec<FnAssertion<'s>>,
+    overrides: Vec<Override>,
+}
+
+impl<'s, T> AssertImpl<'s, T> {
+    pub fn new(t: &'s T) -> Self {
+        Self {
+            t,
+            label: Default::default(),
+            strict: Default::default(),
+            trait_path: Default::default(),
+            self_ty: Default::default(),
+            inherent: Default::default(),
+            negative: Default::default(),
+            unsafety: Default::default(),
+            generics: Default::default(),
+            where_clause: Default::default(),
+            impl_generics: Default::default(),
+            where_predicates: Default::default(),
+            attrs: Default::default(),
+            attr_values: Default::default(),
+            assoc_consts: Default::default(),
+            assoc_types: Default::default(),
+            fns: Default::default(),
+            overrides: Default::default(),
+        }
+    }
+
+    pub fn labeled(self, label: &'s str) -> Self {
+        Self {
+            label: Some(label),
+            ..self
+        }
+    }
+
+    pub fn strict(self) -> Self {
+        Self {
+            strict: true,
+            ..self
+        }
+    }
+
+    pub fn implements(self, trait_path: &'s str) -> Self {
+        Self {
+            trait_path: Some(trait_path),
+            ..self
+        }
+    }
+
+    pub fn for_type(self, self_ty: &'s str) -> Self {
+        Self {
+            self_ty: Some(self_ty),
+            ..self
+        }
+    }
+
+    pub fn inherent(self) -> Self {
+        Self {
+            inherent: Some(true),
+            ..self
+        }
+    }
+
+    pub fn negative(self) -> Self {
+        Self {
+            negative: Some(true),
+            ..self
+        }
+    }
+
+    pub fn unsafe_(self) -> Self {
+        Self {
+            unsafety: Some(true),
+            ..self
+        }
+    }
+
+    pub fn with_generics(self, generics: &'s str) -> Self {
+        Self {
+            generics: Some(generics),
+            ..self
+        }
+    }
+
+    pub fn with_where_clause(self, where_clause: &'s str) -> Self {
+        Self {
+            where_clause: Some(where_clause),
+            ..self
+        }
+    }
+
+    pub fn with_impl_generics<I, S>(self, impl_generics: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            impl_generics: impl_generics
+                .into_iter()
+                .map(|g| g.as_ref().to_string())
+                .collect(),
+            ..self
+        }
+    }
+
+    pub fn with_where_predicate(mut self, where_predicate: &str) -> Self {
+        self.where_predicates.push(where_predicate.to_string());
+        self
+    }
+
+    pub fn with_attrs<I, S>(self, attrs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            attrs: attrs.into_iter().map(|a| a.as_ref().to_string()).collect(),
+            ..self
+        }
+    }
+
+    pub fn with_attr(mut self, attr: Attribute) -> Self {
+        self.attr_values.push(attr);
+        self
+    }
+
+    pub fn with_assoc_const(mut self, name: &str, ty: &str) -> Self {
+        self.assoc_consts.push(AssocConst::new(name, ty));
+        self
+    }
+
+    pub fn with_assoc_const_value(mut self, name: &str, ty: &str, value: &str) -> Self {
+        self.assoc_consts
+            .push(AssocConst::new(name, ty).with_value(value));
+        self
+    }
+
+    pub fn with_assoc_const_expr(mut self, name: &str, ty: &str, expr: Expr) -> Self {
+        self.assoc_consts
+            .push(AssocConst::new(name, ty).with_expr(expr));
+        self
+    }
+
+    pub fn with_assoc_type(mut self, name: &str, ty: &str) -> Self {
+        self.assoc_types.push(AssocType::new(name, ty));
+        self
+    }
+
+    pub fn containing_fn<F>(mut self, f: F) -> Self
+    where
+        F: for<'a> Fn(AssertFn<'a, ImplItemMethod>) -> AssertFn<'a, ImplItemMethod> + 's,
+    {
+        self.fns.push(Box::new(f));
+        self
+    }
+
+    pub fn overrides(mut self, name: &str) -> Self {
+        self.overrides.push(Override {
+            name: name.to_string(),
+            overridden: true,
+            block: None,
+        });
+        self
+    }
+
+    pub fn overrides_with_block(mut self, name: &str, block: TokenStream) -> Self {
+        self.overrides.push(Override {
+            name: name.to_string(),
+            overridden: true,
+            block: Some(block),
+        });
+        self
+    }
+
+    pub fn does_not_override(mut self, name: &str) -> Self {
+        self.overrides.push(Override {
+            name: name.to_string(),
+            overridden: false,
+            block: None,
+        });
+        self
+    }
+}
+
+impl<'s, T> AssertImpl<'s, T>
+where
+    T: HasImpl,
+{
+    fn is_empty(&self) -> bool {
+        !self.strict
+            && self.trait_path.is_none()
+            && self.self_ty.is_none()
+            && self.inherent.is_none()
+            && self.negative.is_none()
+            && self.unsafety.is_none()
+            && self.generics.is_none()
+            && self.where_clause.is_none()
+            && self.impl_generics.is_empty()
+            && self.where_predicates.is_empty()
+            && self.attrs.is_empty()
+            && self.attr_values.is_empty()
+            && self.assoc_consts.is_empty()
+            && self.assoc_types.is_empty()
+            && self.fns.is_empty()
+            && self.overrides.is_empty()
+    }
+
+    fn check_strict(&self) -> CheckResult {
+        let mut attrs = self.attrs.clone();
+        attrs.extend(attr_paths(&self.attr_values));
+        let generics = match self.generics {
+            Some(_) => CheckResult::Success,
+            None => self.t.has_only_generics(&self.impl_generics),
+        };
+        let where_predicates = match self.where_clause {
+            Some(_) => CheckResult::Success,
+            None => self.t.has_only_where_predicates(&self.where_predicates),
+        };
+        let consts = self
+            .assoc_consts
+            .iter()
+            .map(|c| c.name().to_string())
+            .collect::<Vec<_>>();
+        let types = self
+            .assoc_types
+            .iter()
+            .map(|t| t.name().to_string())
+            .collect::<Vec<_>>();
+        let overrides = self
+            .overrides
+            .iter()
+            .filter(|o| o.overridden)
+            .map(|o| o.name.clone())
+            .collect::<Vec<_>>();
+        self.t.has_only_attrs(&attrs)
+            + generics
+            + where_predicates
+            + self.t.has_only_consts(&consts)
+            + self.t.has_only_assoc_types(&types)
+            + self.t.has_only_fns(&self.fns, &overrides)
+    }
+}
+
+impl<'s, T> Check for AssertImpl<'s, T>
+where
+    T: HasImpl,
+{
+    fn check(self) -> CheckResult {
+        let (breadcrumb, location) = (self.t.breadcrumb(), self.t.location());
+        let label = self.label;
+        let empty = match self.is_empty() {
+            true => CheckResult::missing("constraints in impl assertion"),
+            false => CheckResult::Success,
+        };
+        let strict = match self.strict {
+            true => self.check_strict(),
+            false => CheckResult::Success,
+        };
+        let results = (empty
+            + strict
+            + check_option!(self, trait_path)
+            + check_option!(self, self_ty)
+            + check_option!(self, inherent)
+            + check_option!(self, negative)
+            + check_option!(self, unsafety)
+            + check_option!(self, generics)
+            + check_option!(self, where_clause)
+            + self.t.has_impl_generics(&self.impl_generics)
+            + self.t.has_where_predicates(&self.where_predicates)
+            + self.t.has_attrs(&self.attrs)
+            + self.t.has_attr_values(&self.attr_values)
+            + self.t.has_assoc_consts(&self.assoc_consts)
+            + self.t.has_assoc_types(&self.assoc_types)
+            + self.t.has_fns(&self.fns)
+            + self.t.has_overrides(&self.overrides))
+        .in_item(breadcrumb, location);
+        match label {
+            Some(label) => results.within(label),
+            None => results,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_itemimpl() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl Table for Foo { const SIZE: u32 = 16; }
+        "#,
+        )?;
+
+        let results = impl_.has_impl().with_assoc_const("SIZE", "u32").check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_strict() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl<T> Table for Foo<T> where T: Clone {
+                const SIZE: u32 = 16;
+                type Key = String;
+                fn get(&self) -> u32 { 0 }
+                fn clear(&mut self) {}
+            }
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .with_impl_generics(["T"])
+            .with_where_predicate("T: Clone")
+            .with_assoc_const("SIZE", "u32")
+            .containing_fn(|f| f.with_name("get"))
+            .strict()
+            .check();
+        let messages = results
+            .failures()
+            .iter()
+            .map(Failure::message)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec!["Unexpected assoc type 'Key'", "Unexpected fn 'clear'"]
+        );
+
+        assert!(impl_
+            .has_impl()
+            .with_generics("<T>")
+            .with_where_clause("where T: Clone")
+            .with_assoc_const("SIZE", "u32")
+            .with_assoc_type("Key", "String")
+            .containing_fn(|f| f.with_name("get"))
+            .overrides("clear")
+            .strict()
+            .check()
+            .as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_assoc_const_value() -> Result<(), TestError> {
+        let impl_: syn::Item = syn::parse_str(
+            r#"
+            impl Table for Foo { const SIZE: u32 = 4 * 4; }
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .with_assoc_const_value("SIZE", "u32", "4*4")
+            .check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_assoc_const_value_fail() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl Table for Foo { const SIZE: u32 = 16; }
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .with_assoc_const_value("SIZE", "u32", "32")
+            .check();
+        dbg!(&results);
+        assert!(!results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_assoc_const_literal_value() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl Table for Foo {
+                const MAX: usize = 1_024;
+                const MASK: u32 = 0xFF;
+                const RATIO: f64 = 0.50;
+                const NAME: &str = r"table";
+                const MIN: i32 = -(1);
+            }
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .with_assoc_const_value("MAX", "usize", "1024usize")
+            .with_assoc_const_value("MASK", "u32", "255")
+            .with_assoc_const_value("RATIO", "f64", "0.5")
+            .with_assoc_const_value("NAME", "&str", "\"table\"")
+            .with_assoc_const_value("MIN", "i32", "-1")
+            .check();
+        assert!(results.as_bool());
+
+        let results = impl_
+            .has_impl()
+            .with_assoc_const_value("MAX", "usize", "2048")
+            .with_assoc_const_value("MASK", "u32", "0xFF + 0")
+            .check();
+        assert_eq!(
+            results
+                .into_failures()
+                .iter()
+                .map(|f| f.message())
+                .collect::<Vec<_>>(),
+            vec![
+                "Expected assoc const 'MASK' to be '0xFF + 0', got '0xFF'",
+                "Expected assoc const 'MAX' to be '2048', got '1_024'",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_implements_for_type() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl<T> serde::Serialize for MyStruct<T> {}
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .implements("serde :: Serialize")
+            .for_type("MyStruct < T >")
+            .check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_implements_fail() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl MyStruct {}
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .implements("serde::Serialize")
+            .for_type("MyStruct<T>")
+            .check();
+        dbg!(&results);
+        assert!(!results.as_bool());
+        assert_eq!(results.failure_count(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_inherent() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl MyStruct { fn new() -> Self { Self } }
+        "#,
+        )?;
+
+        let results = impl_.has_impl().inherent().for_type("MyStruct").check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_inherent_fail() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl Default for MyStruct { fn default() -> Self { Self } }
+        "#,
+        )?;
+
+        let results = impl_.has_impl().inherent().for_type("MyStruct").check();
+        dbg!(&results);
+        assert_eq!(
+            results.into_failures()[0].message(),
+            "Expected inherent to be 'true', got 'false'"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_containing_fn() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl MyStruct {
+                fn default_value() -> u32 { 0 }
+                pub fn new() -> Self { Self }
+            }
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .containing_fn(|f| f.with_name("new").with_return_type("Self"))
+            .check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_containing_fn_fail() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl MyStruct {
+                pub fn new() -> Option<Self> { None }
+            }
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .containing_fn(|f| f.with_name("new").with_return_type("Self"))
+            .check();
+        dbg!(&results);
+        assert_eq!(results.failure_count(), 1);
+        assert_eq!(
+            results.failures()[0].path,
+            vec!["impl MyStruct".to_string(), "fn new".to_string()]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_assoc_const_expr() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl Foo { const VERSION: &str = "1.2.3"; }
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .with_assoc_const_expr("VERSION", "&str", syn::parse_quote!("1.2.3"))
+            .check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        let results = impl_
+            .has_impl()
+            .with_assoc_const_expr("VERSION", "&str", syn::parse_quote!("1.2.4"))
+            .check();
+        dbg!(&results);
+        let failures = results.into_failures();
+        assert_eq!(
+            failures.iter().map(Failure::message).collect::<Vec<_>>(),
+            vec![r#"Expected assoc const 'VERSION' to be '"1.2.4"', got '"1.2.3"'"#.to_string()]
+        );
+        assert_eq!(failures[0].location.as_ref().map(|l| l.line), Some(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_negative() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl !Send for Foo {}
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .negative()
+            .implements("Send")
+            .for_type("Foo")
+            .check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_negative_fail() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl Send for Foo {}
+        "#,
+        )?;
+
+        let results = impl_.has_impl().negative().implements("Send").check();
+        dbg!(&results);
+        assert_eq!(
+            results.into_failures()[0].message(),
+            "Expected negative to be 'true', got 'false'"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_generics() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl<T: Read, U> MyTrait for Wrapper<T, U> where U: Clone + Send, T: 'static {}
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .with_generics("<T: Read, U>")
+            .with_where_clause("where T: 'static, U: Clone + Send")
+            .implements("MyTrait")
+            .for_type("Wrapper<T, U>")
+            .check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_generics_structural() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl<'a, T: Debug + Clone + 'a, const N: usize = { 4 }> Buffer<'a, T, N> {}
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .with_generics("<'a, T: 'a + Clone + Debug, const N: usize = { 4 }>")
+            .check();
+        assert!(results.as_bool());
+
+        let results = impl_
+            .has_impl()
+            .with_generics("<'a, T: Clone, const N: usize = { 4 }>")
+            .check();
+        assert_eq!(
+            results.into_failures()[0].message(),
+            "Expected generics to be ''a , T : Clone , const N : usize = { 4 }', got ''a , T : Debug + Clone + 'a , const N : usize = { 4 }'"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_where_predicates_structural() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl<T, U> Table for Pair<T, U> where T: Clone + Send + 'static, U: Iterator<Item = T> {}
+        "#,
+        )?;
+
+        impl_
+            .has_impl()
+            .with_impl_generics(["T", "U"])
+            .with_where_clause("where U: Iterator<Item = T>, T: 'static + Send + Clone")
+            .with_where_predicate("T: Send + Clone + 'static")
+            .strict()
+            .with_where_predicate("U: Iterator<Item = T>")
+            .check()
+            .assert();
+
+        let results = impl_
+            .has_impl()
+            .with_impl_generics(["T", "U"])
+            .with_where_predicate("T: Send + Clone")
+            .with_where_predicate("U: Iterator<")
+            .strict()
+            .check();
+        let messages = results
+            .failures()
+            .iter()
+            .map(Failure::message)
+            .collect::<Vec<_>>();
+        assert_eq!(messages.len(), 4);
+        assert!(messages
+            .iter()
+            .any(|m| m.starts_with("Invalid where predicate 'U: Iterator<'")));
+        assert!(messages.contains(&"Missing where predicate 'T : Send + Clone'".to_string()));
+        assert!(messages
+            .contains(&"Unexpected where predicate 'U : Iterator < Item = T >'".to_string()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_generics_fail() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl<T> MyTrait for Wrapper<T> where T: Clone {}
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .with_generics("<T: Read>")
+            .with_where_clause("where T: Send")
+            .check();
+        dbg!(&results);
+        assert_eq!(results.failure_count(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsafe() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            unsafe impl Send for RawHandle {}
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .unsafe_()
+            .implements("Send")
+            .for_type("RawHandle")
+            .check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsafe_fail() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl Send for RawHandle {}
+        "#,
+        )?;
+
+        let results = impl_.has_impl().unsafe_().implements("Send").check();
+        dbg!(&results);
+        assert_eq!(
+            results.into_failures()[0].message(),
+            "Expected unsafe to be 'true', got 'false'"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_overrides() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl Iterator for Counter {
+                type Item = u32;
+                fn next(&mut self) -> Option<u32> { None }
+                fn size_hint(&self) -> (usize, Option<usize>) { (0, Some(0)) }
+            }
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .implements("Iterator")
+            .overrides("next")
+            .overrides_with_block("size_hint", quote::quote! { { (0, Some(0)) } })
+            .does_not_override("count")
+            .check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_overrides_fail() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl Iterator for Counter {
+                type Item = u32;
+                fn next(&mut self) -> Option<u32> { None }
+                fn count(self) -> usize { 0 }
+            }
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .overrides_with_block("next", quote::quote! { { Some(1) } })
+            .overrides("size_hint")
+            .does_not_override("count")
+            .check();
+        dbg!(&results);
+        assert_eq!(results.failure_count(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_generics() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl<'a, T, const N: usize> From<&'a [T; N]> for Wrapper<Vec<U>>
+            where
+                T: Clone,
+                [T; N]: Sized,
+            {}
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .with_impl_generics(["T", "'a", "N"])
+            .with_where_predicate("T: Clone")
+            .check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_generics_fail() -> Result<(), TestError> {
+        let impl_: syn::ItemImpl = syn::parse_str(
+            r#"
+            impl<T> Trait for Wrapper<U> where T: Clone {}
+        "#,
+        )?;
+
+        let results = impl_
+            .has_impl()
+            .with_impl_generics(["U"])
+            .with_where_predicate("T: Send")
+            .check();
+        dbg!(&results);
+        assert_eq!(results.failure_count(), 2);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,813 @@
+use crate::assoc::{AssocConst, AssocFn};
+use crate::failure::{Failure, Location};
+use crate::names::NameOptions;
+use crate::utils::{
+    attr_paths, check_option, compare_attrs, generic_names, missing_names, unexpected_extras,
+    Check, CheckResult,
+};
+use crate::visibility::compare_vis;
+use syn::visit::{self, Visit};
+use syn::{
+    Attribute, FnArg, GenericParam, Item, ItemTrait, Pat, ReturnType, TraitItem, TraitItemMethod,
+    Type, TypeParamBound, TypePath, Visibility, WherePredicate,
+};
+
+pub trait HasTrait {
+    fn has_trait(&self) -> AssertTrait<'_, Self>
+    where
+        Self: Sized,
+    {
+        AssertTrait::new(self)
+    }
+
+    fn breadcrumb(&self) -> Option<String> {
+        None
+    }
+
+    fn location(&self) -> Option<Location> {
+        None
+    }
+
+    fn has_name(&self, name: &str) -> CheckResult;
+    fn has_exact_name(&self, name: &str) -> CheckResult;
+    fn has_name_with(&self, name: &str, options: &NameOptions) -> CheckResult;
+    fn has_vis(&self, vis: &Visibility) -> CheckResult;
+    fn has_attrs(&self, attrs: &[String]) -> CheckResult;
+    fn has_attr_values(&self, attrs: &[Attribute]) -> CheckResult;
+    fn has_assoc_consts(&self, consts: &[AssocConst]) -> CheckResult;
+    fn has_methods(&self, methods: &[AssocFn]) -> CheckResult;
+    fn has_unsafety(&self, unsafety: &bool) -> CheckResult;
+    fn has_auto(&self, auto: &bool) -> CheckResult;
+    fn has_object_safety(&self, object_safety: &bool) -> CheckResult;
+    fn has_generics(&self, generics: &[String]) -> CheckResult;
+    fn has_assoc_types(&self, types: &[String]) -> CheckResult;
+    fn has_only_attrs(&self, attrs: &[String]) -> CheckResult;
+    fn has_only_generics(&self, generics: &[String]) -> CheckResult;
+    fn has_only_items(&self, items: &[String]) -> CheckResult;
+}
+
+impl HasTrait for ItemTrait {
+    fn breadcrumb(&self) -> Option<String> {
+        Some(format!("trait {}", self.ident))
+    }
+
+    fn location(&self) -> Option<Location> {
+        Some(Location::of(self))
+    }
+
+    fn has_name(&self, name: &str) -> CheckResult {
+        CheckResult::compare_ident("name", name, &self.ident)
+    }
+
+    fn has_exact_name(&self, name: &str) -> CheckResult {
+        CheckResult::compare_named("name", name, &self.ident)
+    }
+
+    fn has_name_with(&self, name: &str, options: &NameOptions) -> CheckResult {
+        CheckResult::compare_ident_with("name", name, &self.ident, options)
+    }
+
+    fn has_vis(&self, vis: &Visibility) -> CheckResult {
+        compare_vis(vis, &self.vis)
+    }
+
+    fn has_attrs(&self, attrs: &[String]) -> CheckResult {
+        CheckResult::contains_display(attr_paths(&self.attrs), attrs)
+    }
+
+    fn has_attr_values(&self, attrs: &[Attribute]) -> CheckResult {
+        compare_attrs(&self.attrs, attrs)
+    }
+
+    fn has_assoc_consts(&self, consts: &[AssocConst]) -> CheckResult {
+        CheckResult::any(consts.iter().map(|c| {
+            c.check(self.items.iter().filter_map(|i| match i {
+                TraitItem::Const(c) => Some((&c.ident, &c.ty, c.default.as_ref().map(|d| &d.1))),
+                _ => None,
+            }))
+        }))
+    }
+
+    fn has_methods(&self, methods: &[AssocFn]) -> CheckResult {
+        CheckResult::any(methods.iter().map(|m| {
+            m.check(self.items.iter().filter_map(|i| match i {
+                TraitItem::Method(m) => Some((&m.sig.ident, m.default.as_ref())),
+                _ => None,
+            }))
+        }))
+    }
+
+    fn has_unsafety(&self, unsafety: &bool) -> CheckResult {
+        CheckResult::compare_named("unsafe", unsafety, self.unsafety.is_some())
+    }
+
+    fn has_auto(&self, auto: &bool) -> CheckResult {
+        CheckResult::compare_named("auto", auto, self.auto_token.is_some())
+    }
+
+    fn has_object_safety(&self, object_safety: &bool) -> CheckResult {
+        let violations = object_safety_violations(self);
+        match (*object_safety, violations.is_empty()) {
+            (true, _) => violations.into(),
+            (false, true) => {
+                CheckResult::Failure(vec![Failure::mismatch("object safety", "false", "true")])
+            }
+            (false, false) => CheckResult::Success,
+        }
+    }
+
+    fn has_generics(&self, generics: &[String]) -> CheckResult {
+        missing_names("generic", generic_names(&self.generics), generics)
+    }
+
+    fn has_assoc_types(&self, types: &[String]) -> CheckResult {
+        let actual = self.items.iter().filter_map(|i| match i {
+            TraitItem::Type(t) => Some(t.ident.to_string()),
+            _ => None,
+        });
+        missing_names("assoc type", actual, types)
+    }
+
+    fn has_only_attrs(&self, attrs: &[String]) -> CheckResult {
+        unexpected_extras("attribute", attr_paths(&self.attrs), attrs)
+    }
+
+    fn has_only_generics(&self, generics: &[String]) -> CheckResult {
+        unexpected_extras("generic", generic_names(&self.generics), generics)
+    }
+
+    fn has_only_items(&self, items: &[String]) -> CheckResult {
+        self.items
+            .iter()
+            .filter_map(|i| match i {
+                TraitItem::Const(c) => Some(("assoc const", &c.ident)),
+                TraitItem::Method(m) => Some(("method", &m.sig.ident)),
+                TraitItem::Type(t) => Some(("assoc type", &t.ident)),
+                _ => None,
+            })
+            .filter(|(_, ident)| !items.iter().any(|i| *ident == i))
+            .map(|(kind, ident)| {
+                Failure::unexpected(&format!("{kind} '{ident}'")).at(Some(Location::of(ident)))
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+fn object_safety_violations(trait_: &ItemTrait) -> Vec<Failure> {
+    let mut failures = trait_
+        .supertraits
+        .iter()
+        .filter(|bound| is_sized(bound))
+        .map(|bound| Failure::unexpected("supertrait 'Sized'").at(Some(Location::of(bound))))
+        .collect::<Vec<_>>();
+    for item in &trait_.items {
+        match item {
+            TraitItem::Const(c) => failures.push(
+                Failure::unexpected(&format!("associated const '{}'", c.ident))
+                    .at(Some(Location::of(c))),
+            ),
+            TraitItem::Method(m) if !requires_sized(m) => {
+                let name = &m.sig.ident;
+                if m.sig
+                    .generics
+                    .params
+                    .iter()
+                    .any(|p| matches!(p, GenericParam::Type(_)))
+                {
+                    failures.push(
+                        Failure::unexpected(&format!("generic method '{name}'"))
+                            .at(Some(Location::of(&m.sig.generics))),
+                    );
+                }
+                if !m.sig.inputs.first().is_some_and(is_receiver) {
+                    failures.push(
+                        Failure::unexpected(&format!("method '{name}' without receiver"))
+                            .at(Some(Location::of(name))),
+                    );
+                }
+                let mut visitor = SelfVisitor(Vec::new());
+                for input in m.sig.inputs.iter().filter(|i| !is_receiver(i)) {
+                    if let FnArg::Typed(arg) = input {
+                        visitor.visit_type(&arg.ty);
+                    }
+                }
+                if let ReturnType::Type(_, ty) = &m.sig.output {
+                    visitor.visit_type(ty);
+                }
+                failures.extend(visitor.0.into_iter().map(|location| {
+                    Failure::unexpected(&format!("'Self' in signature of method '{name}'"))
+                        .at(Some(location))
+                }));
+            }
+            _ => (),
+        }
+    }
+    failures
+}
+
+fn is_receiver(arg: &FnArg) -> bool {
+    match arg {
+        FnArg::Receiver(_) => true,
+        FnArg::Typed(arg) => matches!(&*arg.pat, Pat::Ident(p) if p.ident == "self"),
+    }
+}
+
+fn is_sized(bound: &TypeParamBound) -> bool {
+    matches!(bound, TypeParamBound::Trait(t) if t.path.is_ident("Sized"))
+}
+
+fn requires_sized(method: &TraitItemMethod) -> bool {
+    method.sig.generics.where_clause.as_ref().is_some_and(|w| {
+        w.predicates.iter().any(|p| match p {
+            WherePredicate::Type(p) => {
+                matches!(&p.bounded_ty, Type::Path(t) if t.path.is_ident("Self"))
+                    && p.bounds.iter().any(is_sized)
+            }
+            _ => false,
+        })
+    })
+}
+
+struct SelfVisitor(Vec<Location>);
+
+impl<'ast> Visit<'ast> for SelfVisitor {
+    fn visit_type(&mut self, ty: &'ast Type) {
+        match ty {
+            Type::Path(TypePath { qself: None, path }) if path.is_ident("Self") => {
+                self.0.push(Location::of(ty))
+            }
+            _ => visit::visit_type(self, ty),
+        }
+    }
+}
+
+macro_rules! hastrait_item {
+    ($v:ident, $t: ty) => {
+        paste::paste! {
+            fn [<has_ $v>](&self, $v: $t) -> CheckResult {
+                match self {
+                    Item::Trait(trait_) => trait_.[<has_ $v>]($v),
+                    _ => CheckResult::missing(stringify!($v)),
+                }
+            }
+        }
+    };
+}
+
+impl HasTrait for Item {
+    fn breadcrumb(&self) -> Option<String> {
+        match self {
+            Item::Trait(trait_) => trait_.breadcrumb(),
+            _ => None,
+        }
+    }
+
+    fn location(&self) -> Option<Location> {
+        Some(Location::of(self))
+    }
+
+    hastrait_item!(name, &str);
+    hastrait_item!(exact_name, &str);
+
+    fn has_name_with(&self, name: &str, options: &NameOptions) -> CheckResult {
+        match self {
+            Item::Trait(trait_) => trait_.has_name_with(name, options),
+            _ => CheckResult::missing("name"),
+        }
+    }
+
+    hastrait_item!(vis, &Visibility);
+    hastrait_item!(attrs, &[String]);
+    hastrait_item!(attr_values, &[Attribute]);
+    hastrait_item!(assoc_consts, &[AssocConst]);
+    hastrait_item!(methods, &[AssocFn]);
+    hastrait_item!(unsafety, &bool);
+    hastrait_item!(auto, &bool);
+    hastrait_item!(object_safety, &bool);
+    hastrait_item!(generics, &[String]);
+    hastrait_item!(assoc_types, &[String]);
+    hastrait_item!(only_attrs, &[String]);
+    hastrait_item!(only_generics, &[String]);
+    hastrait_item!(only_items, &[String]);
+}
+
+macro_rules! hastrait_vec {
+    ($v: ident, $t: ty) => {
+        paste::paste! {
+            fn [<has_ $v>](&self, $v: $t) -> CheckResult {
+                CheckResult::any(self.iter().map(|f| f.[<has_ $v>](&$v).in_item(f.breadcrumb(), f.location())))
+            }
+        }
+    };
+}
+
+impl<T> HasTrait for Vec<T>
+where
+    T: HasTrait,
+{
+    hastrait_vec!(name, &str);
+    hastrait_vec!(exact_name, &str);
+
+    fn has_name_with(&self, name: &str, options: &NameOptions) -> CheckResult {
+        CheckResult::any(self.iter().map(|t| {
+            t.has_name_with(name, options)
+                .in_item(t.breadcrumb(), t.location())
+        }))
+    }
+
+    hastrait_vec!(vis, &Visibility);
+    hastrait_vec!(attrs, &[String]);
+    hastrait_vec!(attr_values, &[Attribute]);
+    hastrait_vec!(assoc_consts, &[AssocConst]);
+    hastrait_vec!(methods, &[AssocFn]);
+    hastrait_vec!(unsafety, &bool);
+    hastrait_vec!(auto, &bool);
+    hastrait_vec!(object_safety, &bool);
+    hastrait_vec!(generics, &[String]);
+    hastrait_vec!(assoc_types, &[String]);
+    hastrait_vec!(only_attrs, &[String]);
+    hastrait_vec!(only_generics, &[String]);
+    hastrait_vec!(only_items, &[String]);
+}
+
+#[must_use]
+pub struct AssertTrait<'s, T> {
+    t: &'s T,
+    label: Option<&'s str>,
+    strict: bool,
+    name: Option<&'s str>,
+    exact_name: Option<&'s str>,
+    name_options: NameOptions,
+    vis: Option<Visibility>,
+    attrs: Vec<String>,
+    attr_values: Vec<Attribute>,
+    assoc_consts: Vec<AssocConst>,
+    methods: Vec<AssocFn>,
+    unsafety: Option<bool>,
+    auto: Option<bool>,
+    object_safety: Option<bool>,
+    generics: Vec<String>,
+    assoc_types: Vec<String>,
+}
+
+impl<'s, T> AssertTrait<'s, T> {
+    pub fn new(t: &'s T) -> Self {
+        Self {
+            t,
+            label: Default::default(),
+            strict: Default::default(),
+            name: Default::default(),
+            exact_name: Default::default(),
+            name_options: Default::default(),
+            vis: Default::default(),
+            attrs: Default::default(),
+            attr_values: Default::default(),
+            assoc_consts: Default::default(),
+            methods: Default::default(),
+            unsafety: Default::default(),
+            auto: Default::default(),
+            object_safety: Default::default(),
+            generics: Default::default(),
+            assoc_types: Default::default(),
+        }
+    }
+
+    pub fn labeled(self, label: &'s str) -> Self {
+        Self {
+            label: Some(label),
+            ..self
+        }
+    }
+
+    pub fn strict(self) -> Self {
+        Self {
+            strict: true,
+            ..self
+        }
+    }
+
+    pub fn with_name(self, name: &'s str) -> Self {
+        Self {
+            name: Some(name),
+            ..self
+        }
+    }
+
+    pub fn with_exact_name(self, exact_name: &'s str) -> Self {
+        Self {
+            exact_name: Some(exact_name),
+            ..self
+        }
+    }
+
+    pub fn with_name_options(self, name_options: NameOptions) -> Self {
+        Self {
+            name_options,
+            ..self
+        }
+    }
+
+    pub fn with_vis(self, vis: Visibility) -> Self {
+        Self {
+            vis: Some(vis),
+            ..self
+        }
+    }
+
+    pub fn with_attrs<I, S>(self, attrs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            attrs: attrs.into_iter().map(|a| a.as_ref().to_string()).collect(),
+            ..self
+        }
+    }
+
+    pub fn with_attr(mut self, attr: Attribute) -> Self {
+        self.attr_values.push(attr);
+        self
+    }
+
+    pub fn with_assoc_const(mut self, name: &str, ty: &str) -> Self {
+        self.assoc_consts.push(AssocConst::new(name, ty));
+        self
+    }
+
+    pub fn with_method(mut self, method: AssocFn) -> Self {
+        self.methods.push(method);
+        self
+    }
+
+    pub fn with_assoc_type(mut self, name: &str) -> Self {
+        self.assoc_types.push(name.to_string());
+        self
+    }
+
+    pub fn with_generics<I, S>(self, generics: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            generics: generics
+                .into_iter()
+                .map(|g| g.as_ref().to_string())
+                .collect(),
+            ..self
+        }
+    }
+
+    pub fn unsafe_(self) -> Self {
+        Self {
+            unsafety: Some(true),
+            ..self
+        }
+    }
+
+    pub fn auto(self) -> Self {
+        Self {
+            auto: Some(true),
+            ..self
+        }
+    }
+
+    pub fn object_safe(self) -> Self {
+        Self {
+            object_safety: Some(true),
+            ..self
+        }
+    }
+}
+
+impl<'s, T> AssertTrait<'s, T>
+where
+    T: HasTrait,
+{
+    fn is_empty(&self) -> bool {
+        !self.strict
+            && self.name.is_none()
+            && self.exact_name.is_none()
+            && self.vis.is_none()
+            && self.unsafety.is_none()
+            && self.auto.is_none()
+            && self.object_safety.is_none()
+            && self.attrs.is_empty()
+            && self.attr_values.is_empty()
+            && self.assoc_consts.is_empty()
+            && self.methods.is_empty()
+            && self.generics.is_empty()
+            && self.assoc_types.is_empty()
+    }
+
+    fn check_strict(&self) -> CheckResult {
+        let mut attrs = self.attrs.clone();
+        attrs.extend(attr_paths(&self.attr_values));
+        let items = self
+            .assoc_consts
+            .iter()
+            .map(|c| c.name().to_string())
+            .chain(self.methods.iter().map(|m| m.name().to_string()))
+            .chain(self.assoc_types.iter().cloned())
+            .collect::<Vec<_>>();
+        self.t.has_only_attrs(&attrs)
+            + self.t.has_only_generics(&self.generics)
+            + self.t.has_only_items(&items)
+    }
+}
+
+impl<'s, T> Check for AssertTrait<'s, T>
+where
+    T: HasTrait,
+{
+    fn check(self) -> CheckResult {
+        let (breadcrumb, location) = (self.t.breadcrumb(), self.t.location());
+        let label = self.label;
+        let empty = match self.is_empty() {
+            true => CheckResult::missing("constraints in trait assertion"),
+            false => CheckResult::Success,
+        };
+        let strict = match self.strict {
+            true => self.check_strict(),
+            false => CheckResult::Success,
+        };
+        let results = (empty
+            + strict
+            + self.name.map_or(CheckResult::Success, |name| {
+                self.t.has_name_with(name, &self.name_options)
+            })
+            + check_option!(self, exact_name)
+            + check_option!(self, vis)
+            + check_option!(self, unsafety)
+            + check_option!(self, auto)
+            + check_option!(self, object_safety)
+            + self.t.has_attrs(&self.attrs)
+            + self.t.has_attr_values(&self.attr_values)
+            + self.t.has_assoc_consts(&self.assoc_consts)
+            + self.t.has_methods(&self.methods)
+            + self.t.has_generics(&self.generics)
+            + self.t.has_assoc_types(&self.assoc_types))
+        .in_item(breadcrumb, location);
+        match label {
+            Some(label) => results.within(label),
+            None => results,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_itemtrait() -> Result<(), TestError> {
+        let trait_: syn::ItemTrait = syn::parse_str(
+            r#"
+            pub trait Table { const SIZE: u32; }
+        "#,
+        )?;
+
+        let results = trait_
+            .has_trait()
+            .with_name("Table")
+            .with_assoc_const("SIZE", "u32")
+            .check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_item() -> Result<(), TestError> {
+        let trait_: syn::Item = syn::parse_str(
+            r#"
+            pub trait Table { const SIZE: u32 = 4; }
+        "#,
+        )?;
+
+        let results = trait_
+            .has_trait()
+            .with_name("Table")
+            .with_assoc_const("SIZE", "u32")
+            .check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_assoc_const_fail() -> Result<(), TestError> {
+        let trait_: syn::ItemTrait = syn::parse_str(
+            r#"
+            pub trait Table { const SIZE: u64; }
+        "#,
+        )?;
+
+        let results = trait_
+            .has_trait()
+            .with_assoc_const("SIZE", "u32")
+            .with_assoc_const("NAME", "&'static str")
+            .check();
+        dbg!(&results);
+        assert!(!results.as_bool());
+        assert_eq!(results.failure_count(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_method() -> Result<(), TestError> {
+        let trait_: syn::ItemTrait = syn::parse_str(
+            r#"
+            pub trait Greet {
+                fn name(&self) -> String;
+                fn greet(&self) { println!("Hello, {}!", self.name()); }
+            }
+        "#,
+        )?;
+
+        let results = trait_
+            .has_trait()
+            .with_method(AssocFn::new("name").without_default())
+            .with_method(
+                AssocFn::new("greet").with_default_containing(quote::quote! { self.name() }),
+            )
+            .check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_method_fail() -> Result<(), TestError> {
+        let trait_: syn::ItemTrait = syn::parse_str(
+            r#"
+            pub trait Greet {
+                fn name(&self) -> String;
+                fn greet(&self) { println!("Hello!"); }
+            }
+        "#,
+        )?;
+
+        let results = trait_
+            .has_trait()
+            .with_method(AssocFn::new("name").with_default())
+            .with_method(AssocFn::new("greet").with_default_block(quote::quote! { {} }))
+            .check();
+        dbg!(&results);
+        assert!(!results.as_bool());
+        assert_eq!(results.failure_count(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsafe_auto() -> Result<(), TestError> {
+        let trait_: syn::ItemTrait = syn::parse_str(
+            r#"
+            pub unsafe auto trait Marker {}
+        "#,
+        )?;
+
+        let results = trait_.has_trait().unsafe_().auto().check();
+        dbg!(&results);
+        assert!(results.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsafe_fail() -> Result<(), TestError> {
+        let trait_: syn::ItemTrait = syn::parse_str(
+            r#"
+            pub trait Marker {}
+        "#,
+        )?;
+
+        let results = trait_.has_trait().unsafe_().auto().check();
+        dbg!(&results);
+        assert_eq!(
+            results
+                .into_failures()
+                .iter()
+                .map(|f| f.message())
+                .collect::<Vec<_>>(),
+            vec![
+                "Expected auto to be 'true', got 'false'",
+                "Expected unsafe to be 'true', got 'false'",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_object_safe() -> Result<(), TestError> {
+        let trait_: syn::ItemTrait = syn::parse_str(
+            r#"
+            pub trait Handler {
+                fn handle(&self, req: &Request) -> Response;
+                fn boxed(self: Box<Self>) -> Box<dyn Handler>;
+                fn map<F: Fn()>(&self, f: F) where Self: Sized;
+                fn new() -> Self where Self: Sized;
+            }
+        "#,
+        )?;
+
+        assert!(trait_.has_trait().object_safe().check().as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_object_safe_fail() -> Result<(), TestError> {
+        let trait_: syn::ItemTrait = syn::parse_str(
+            r#"pub trait Shape: Clone + Sized {
+    const SIDES: u8;
+    fn scale<T: Into<f64>>(&self, by: T);
+    fn create() -> Box<Self>;
+    fn merge(&self, other: &Self) -> Option<Self>;
+    fn area(&self) -> f64;
+}"#,
+        )?;
+
+        let failures = trait_.has_trait().object_safe().check().into_failures();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Unexpected supertrait 'Sized'".to_string(), Some(1)),
+                ("Unexpected associated const 'SIDES'".to_string(), Some(2)),
+                ("Unexpected generic method 'scale'".to_string(), Some(3)),
+                (
+                    "Unexpected method 'create' without receiver".to_string(),
+                    Some(4)
+                ),
+                (
+                    "Unexpected 'Self' in signature of method 'create'".to_string(),
+                    Some(4)
+                ),
+                (
+                    "Unexpected 'Self' in signature of method 'merge'".to_string(),
+                    Some(5)
+                ),
+                (
+                    "Unexpected 'Self' in signature of method 'merge'".to_string(),
+                    Some(5)
+                ),
+            ]
+        );
+        assert_eq!(failures[0].path, vec!["trait Shape"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_strict() -> Result<(), TestError> {
+        let trait_: syn::ItemTrait = syn::parse_str(
+            r#"
+            pub trait Store<K> {
+                type Value;
+                fn get(&self, key: K) -> Option<Self::Value>;
+            }
+        "#,
+        )?;
+
+        let messages = trait_
+            .has_trait()
+            .with_method(AssocFn::new("get"))
+            .strict()
+            .check()
+            .into_failures()
+            .iter()
+            .map(Failure::message)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec!["Unexpected generic 'K'", "Unexpected assoc type 'Value'"]
+        );
+
+        assert!(trait_
+            .has_trait()
+            .with_method(AssocFn::new("get"))
+            .with_generics(["K"])
+            .with_assoc_type("Value")
+            .strict()
+            .check()
+            .as_bool());
+
+        assert!(!trait_.has_trait().with_assoc_type("Key").check().as_bool());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use crate::utils::CheckResult;
+use std::{fs, io, path::Path};
+
+pub struct JUnitReport {
+    name: String,
+    cases: Vec<(String, CheckResult)>,
+}
+
+impl JUnitReport {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            cases: Default::default(),
+        }
+    }
+
+    pub fn add(&mut self, name: &str, result: CheckResult) -> &mut Self {
+        self.cases.push((name.to_string(), result));
+        self
+    }
+
+    pub fn failures(&self) -> usize {
+        self.cases.iter().filter(|(_, r)| !r.as_bool()).count()
+    }
+
+    pub fn to_xml(&self) -> String {
+        let tests = self.cases.len();
+        let failures = self.failures();
+        let name = escape(&self.name);
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites tests=\"{tests}\" failures=\"{failures}\">\n"
+        ));
+        xml.push_str(&format!(
+            "  <testsuite name=\"{name}\" tests=\"{tests}\" failures=\"{failures}\">\n"
+        ));
+        for (case, result) in &self.cases {
+            let case = escape(case);
+            if result.as_bool() {
+                xml.push_str(&format!(
+                    "    <testcase name=\"{case}\" classname=\"{name}\"/>\n"
+                ));
+            } else {
+                xml.push_str(&format!(
+                    "    <testcase name=\"{case}\" classname=\"{name}\">\n"
+                ));
+                xml.push_str(&format!(
+                    "      <failure message=\"{} check(s) failed\" type=\"CheckFailure\">{}</failure>\n",
+                    result.errors().count(),
+                    escape(&result.render(false))
+                ));
+                xml.push_str("    </testcase>\n");
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+
+    pub fn write<P>(&self, path: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        fs::write(path, self.to_xml())
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_xml() {
+        let mut report = JUnitReport::new("codegen");
+        report
+            .add("has main fn", CheckResult::Success)
+            .add("impl Debug for <Foo>", CheckResult::missing("name"));
+
+        assert_eq!(report.failures(), 1);
+        assert_eq!(
+            report.to_xml(),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="2" failures="1">
+  <testsuite name="codegen" tests="2" failures="1">
+    <testcase name="has main fn" classname="codegen"/>
+    <testcase name="impl Debug for &lt;Foo&gt;" classname="codegen">
+      <failure message="1 check(s) failed" type="CheckFailure">1 check(s) failed:
+  - Missing name</failure>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+}
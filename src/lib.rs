@@ -1,7 +1,106 @@
+mod accessors;
+mod assoc;
+pub use assoc::*;
+mod asyncness;
+mod baseline;
+pub use baseline::*;
+mod build;
+pub use build::*;
+mod cfg;
+pub use cfg::*;
+mod color;
+mod complexity;
+pub use complexity::complexity;
+mod conversions;
+mod coverage;
+mod crate_ast;
+pub use crate_ast::*;
+mod diff;
+pub use diff::*;
+mod docs;
+pub use docs::*;
+mod entry;
+#[cfg(feature = "expand")]
+pub mod expanded;
+mod explain;
+pub use explain::*;
+mod failure;
+mod fallible;
+pub use failure::*;
+mod file;
+pub use file::*;
+#[cfg(feature = "cli")]
+pub mod cli;
+pub mod fixtures;
 mod function;
 pub use function::*;
+mod generate;
+pub use generate::*;
+mod imports;
+pub use imports::*;
+mod item;
+pub use item::*;
+mod item_const;
+pub use item_const::*;
+mod item_impl;
+pub use item_impl::*;
+mod item_trait;
+pub use item_trait::*;
+#[cfg(feature = "workspace")]
+mod json;
+mod junit;
+pub use junit::*;
+mod limits;
+mod lints;
+mod macros;
+pub use macros::*;
+mod module_tree;
+pub use module_tree::*;
+mod names;
+pub use names::*;
+mod naming;
+pub use naming::*;
+mod nfc;
+mod nfc_tables;
+mod normalize;
+pub use normalize::{normalize, sort_attrs, with_crate_name};
+mod panics;
+mod pattern;
+pub use pattern::*;
+mod report;
+pub use report::*;
+mod rule_file;
+mod rules;
+pub use rules::*;
+mod sarif;
+pub use sarif::*;
+mod set;
+pub use set::*;
+mod source;
+pub use source::*;
+#[cfg(test)]
+mod test_dir;
+mod toml;
+mod trait_objects;
+mod type_alias;
+mod types;
+pub use type_alias::*;
+mod unsafety;
 mod utils;
 pub use utils::*;
+mod validate;
+pub use validate::*;
+mod visibility;
+#[cfg(feature = "workspace")]
+mod workspace;
+#[cfg(feature = "workspace")]
+pub use workspace::*;
+pub mod harness;
 pub mod prelude;
 
-pub use syn;
\ No newline at end of file
+pub use syn;
+#[cfg(feature = "macros")]
+pub use syn_assert_macros::assert_generates;
+
+#[cfg(all(test, feature = "macros"))]
+extern crate self as syn_assert;
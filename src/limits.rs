@@ -0,0 +1,126 @@
+use crate::crate_ast::CrateItem;
+use crate::failure::{Failure, Location};
+use crate::rules::Rule;
+use crate::utils::CheckResult;
+use quote::ToTokens;
+use std::collections::BTreeMap;
+use syn::{ImplItem, Item};
+
+impl<'s> Rule<'s> {
+    pub fn max_items_per_module(max: usize) -> Self {
+        Self::new(&format!("at most {max} items per module"))
+            .must_crate(move |_, items| max_items(items, max))
+    }
+
+    pub fn max_fns_per_impl(max: usize) -> Self {
+        Self::new(&format!("at most {max} fns per impl block"))
+            .select(|item| matches!(item, Item::Impl(_)))
+            .must(move |item| max_impl_fns(item, max))
+    }
+}
+
+pub(crate) fn max_items(items: &[CrateItem<'_>], max: usize) -> CheckResult {
+    let mut counts = BTreeMap::<&str, Vec<&CrateItem<'_>>>::new();
+    for item in items {
+        counts.entry(&item.module).or_default().push(item);
+    }
+    CheckResult::any(
+        counts
+            .into_iter()
+            .filter(|(_, items)| items.len() > max)
+            .map(|(module, items)| {
+                let first = items[0];
+                CheckResult::Failure(vec![Failure::mismatch(
+                    &format!("item count of module '{module}'"),
+                    &format!("at most {max}"),
+                    &items.len().to_string(),
+                )])
+                .at(Some(Location::of(first.item)))
+                .in_file(&first.file.name())
+                .within(module)
+            }),
+    )
+}
+
+pub(crate) fn max_impl_fns(item: &Item, max: usize) -> CheckResult {
+    let impl_ = match item {
+        Item::Impl(impl_) => impl_,
+        _ => return CheckResult::Success,
+    };
+    let fns = impl_
+        .items
+        .iter()
+        .filter(|i| matches!(i, ImplItem::Method(_)))
+        .count();
+    if fns <= max {
+        return CheckResult::Success;
+    }
+    let subject = match &impl_.trait_ {
+        Some((_, path, _)) => format!(
+            "impl {} for {}",
+            path.to_token_stream(),
+            impl_.self_ty.to_token_stream()
+        ),
+        None => format!("impl {}", impl_.self_ty.to_token_stream()),
+    };
+    CheckResult::Failure(vec![Failure::mismatch(
+        &format!("fn count of {subject}"),
+        &format!("at most {max}"),
+        &fns.to_string(),
+    )
+    .at(Some(Location::of(&impl_.self_ty)))])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crate_ast::CrateAst;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_item_limits() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"struct Server;
+impl Server {
+    fn start(&self) {}
+    fn stop(&self) {}
+    const PORT: u16 = 80;
+}
+impl Clone for Server { fn clone(&self) -> Self { Self } }
+mod api {
+    fn list() {}
+}
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        assert!(Rule::max_items_per_module(4).check(&ast).as_bool());
+        let failures = Rule::max_items_per_module(1).check(&ast).into_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(
+            failures[0].message(),
+            "Expected item count of module 'crate' to be 'at most 1', got '4'"
+        );
+        assert_eq!(failures[0].path, vec!["crate"]);
+        assert_eq!(failures[0].location.as_ref().map(|l| l.line), Some(1));
+
+        assert!(Rule::max_fns_per_impl(2).check(&ast).as_bool());
+        let failures = Rule::max_fns_per_impl(1).check(&ast).into_failures();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>(),
+            vec![(
+                "Expected fn count of impl Server to be 'at most 1', got '2'".to_string(),
+                Some(2)
+            )]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+use crate::crate_ast::{CrateAst, CrateItem};
+use crate::failure::{Failure, Location};
+use crate::file::HasInnerAttrs;
+use crate::rules::Rule;
+use crate::utils::CheckResult;
+use quote::ToTokens;
+use syn::visit::{self, Visit};
+use syn::{Attribute, ItemMod, Meta, NestedMeta};
+
+impl<'s> Rule<'s> {
+    pub fn no_allow_attrs<I, S>(allowed: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let allowed = allowed
+            .into_iter()
+            .map(|a| a.as_ref().to_string())
+            .collect::<Vec<_>>();
+        Self::new("no allow attributes").must_crate_item(move |item| no_allow(item, &allowed))
+    }
+
+    pub fn no_feature_gates() -> Self {
+        Self::new("no nightly feature gates").must_crate(|ast, _| no_feature_gates(ast))
+    }
+}
+
+pub(crate) fn no_feature_gates(ast: &CrateAst) -> CheckResult {
+    CheckResult::any(ast.files().map(|(_, file)| {
+        let failures = file
+            .inner_attrs()
+            .iter()
+            .filter(|a| a.path.is_ident("feature"))
+            .flat_map(|attr| {
+                lints(attr).into_iter().map(move |feature| {
+                    Failure::unexpected(&format!("#![feature({feature})]"))
+                        .at(Some(Location::of(attr)))
+                })
+            })
+            .collect::<Vec<_>>();
+        CheckResult::from(failures).in_file(&file.name())
+    }))
+}
+
+fn lints(attr: &Attribute) -> Vec<String> {
+    match attr.parse_meta() {
+        Ok(Meta::List(list)) => list
+            .nested
+            .iter()
+            .map(|n| match n {
+                NestedMeta::Meta(meta) => meta.path().to_token_stream().to_string(),
+                NestedMeta::Lit(lit) => lit.to_token_stream().to_string(),
+            })
+            .map(|lint| lint.replace(' ', ""))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+pub(crate) fn no_allow(item: &CrateItem<'_>, allowed: &[String]) -> CheckResult {
+    let mut visitor = AllowVisitor {
+        allowed,
+        failures: Vec::new(),
+    };
+    if item
+        .file
+        .items
+        .first()
+        .is_some_and(|first| std::ptr::eq(first, item.item))
+    {
+        for attr in &item.file.attrs {
+            visitor.visit_attribute(attr);
+        }
+    }
+    visitor.visit_item(item.item);
+    visitor.failures.into()
+}
+
+struct AllowVisitor<'a> {
+    allowed: &'a [String],
+    failures: Vec<Failure>,
+}
+
+impl<'ast> Visit<'ast> for AllowVisitor<'_> {
+    fn visit_item_mod(&mut self, item: &'ast ItemMod) {
+        for attr in &item.attrs {
+            self.visit_attribute(attr);
+        }
+    }
+
+    fn visit_attribute(&mut self, attr: &'ast Attribute) {
+        if attr.path.is_ident("allow") {
+            self.failures.extend(
+                lints(attr)
+                    .into_iter()
+                    .filter(|lint| !self.allowed.contains(lint))
+                    .map(|lint| {
+                        Failure::unexpected(&format!("#[allow({lint})]"))
+                            .at(Some(Location::of(attr)))
+                    }),
+            );
+        }
+        visit::visit_attribute(self, attr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crate_ast::CrateAst;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_no_allow_attrs() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"#![allow(dead_code)]
+#[allow(clippy::too_many_arguments, unused)]
+fn run() {
+    #[allow(unused_variables)]
+    let x = 1;
+}
+#[allow(dead_code)]
+mod inner {
+    #[allow(non_snake_case)]
+    struct Inner { #[allow(missing_docs)] field: u8 }
+}
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let failures = Rule::no_allow_attrs(["dead_code"])
+            .check(&ast)
+            .into_failures();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "Unexpected #[allow(clippy::too_many_arguments)]".to_string(),
+                    Some(2)
+                ),
+                ("Unexpected #[allow(unused)]".to_string(), Some(2)),
+                ("Unexpected #[allow(unused_variables)]".to_string(), Some(4)),
+                ("Unexpected #[allow(non_snake_case)]".to_string(), Some(9)),
+                ("Unexpected #[allow(missing_docs)]".to_string(), Some(10)),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_feature_gates() -> Result<(), TestError> {
+        let dir = TestDir::new(&[
+            (
+                "src/lib.rs",
+                "#![no_std]\n#![feature(never_type, let_chains)]\nmod inner;\n",
+            ),
+            ("src/inner.rs", "fn run() {}\n"),
+        ])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let failures = Rule::no_feature_gates().check(&ast).into_failures();
+        assert_eq!(
+            failures.iter().map(|f| f.message()).collect::<Vec<_>>(),
+            vec![
+                "Unexpected #![feature(let_chains)]",
+                "Unexpected #![feature(never_type)]",
+            ]
+        );
+        let location = failures[0].location.clone().unwrap();
+        assert_eq!(location.line, 2);
+        assert!(location.file.unwrap().ends_with("lib.rs"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use crate::failure::Failure;
+use crate::file::HasItems;
+use crate::utils::CheckResult;
+use proc_macro2::TokenStream;
+use syn::{File, Item};
+
+#[macro_export]
+macro_rules! assert_syn {
+    ($tokens:expr, contains: $vis:vis fn $name:ident ( $($inputs:tt)* ) $(-> $ret:ty)? $(;)?) => {
+        $crate::contains_item(&$tokens, stringify!(fn $name), |item| match item {
+            $crate::syn::Item::Fn(func) => {
+                use $crate::prelude::*;
+                Some(
+                    func.has_fn()
+                        .with_name(stringify!($name))
+                        .with_vis($crate::syn::parse_quote!($vis))
+                        .with_signature(stringify!(fn $name ( $($inputs)* ) $(-> $ret)?))
+                        .check(),
+                )
+            }
+            _ => None,
+        })
+        .assert()
+    };
+}
+
+pub fn contains_item<F>(tokens: &TokenStream, subject: &str, check: F) -> CheckResult
+where
+    F: Fn(&Item) -> Option<CheckResult>,
+{
+    let file: File = match syn::parse2(tokens.clone()) {
+        Ok(file) => file,
+        Err(e) => return CheckResult::Failure(vec![Failure::invalid("tokens", &e.to_string())]),
+    };
+
+    let mut results = Vec::new();
+    for result in file.walk_items().into_iter().filter_map(check) {
+        if result.as_bool() {
+            return result;
+        }
+        results.push(result);
+    }
+
+    if results.is_empty() {
+        CheckResult::missing(subject)
+    } else {
+        CheckResult::any(results.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assert_syn() {
+        let tokens = quote::quote! {
+            struct Request;
+            fn helper() {}
+            pub fn handle(_: Request) -> Response { Response }
+        };
+
+        assert_syn!(tokens, contains: pub fn handle(_: Request) -> Response);
+        assert_syn!(tokens, contains: fn helper());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "fn handle: Mismatched signature: ... Request ) -> [-Request-] {+Response+}"
+    )]
+    fn test_assert_syn_fail() {
+        let tokens = quote::quote! {
+            pub fn handle(_: Request) -> Response { Response }
+        };
+
+        assert_syn!(tokens, contains: pub fn handle(_: Request) -> Request;);
+    }
+
+    #[test]
+    fn test_contains_item_missing() {
+        let tokens = quote::quote! { struct Request; };
+
+        let results = contains_item(&tokens, "fn handle", |_| None);
+        assert_eq!(results.into_failures()[0].message(), "Missing fn handle");
+    }
+}
@@ -0,0 +1,189 @@
+use crate::crate_ast::CrateAst;
+use crate::failure::Failure;
+use crate::utils::CheckResult;
+use std::fmt::{self, Display};
+use syn::Item;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleTree {
+    pub name: String,
+    pub children: Vec<ModuleTree>,
+}
+
+impl ModuleTree {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            children: Default::default(),
+        }
+    }
+
+    pub fn from_paths<I, S>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut tree = Self::new("crate");
+        for path in paths {
+            let mut node = &mut tree;
+            for segment in path.as_ref().split("::").skip(1) {
+                let index = match node.children.iter().position(|c| c.name == segment) {
+                    Some(index) => index,
+                    None => {
+                        node.children.push(Self::new(segment));
+                        node.children.len() - 1
+                    }
+                };
+                node = &mut node.children[index];
+            }
+        }
+        tree
+    }
+
+    pub fn get(&self, path: &str) -> Option<&ModuleTree> {
+        let mut segments = path.split("::");
+        if segments.next() != Some(self.name.as_str()) {
+            return None;
+        }
+        segments.try_fold(self, |node, segment| {
+            node.children.iter().find(|c| c.name == segment)
+        })
+    }
+
+    pub fn depth(&self) -> usize {
+        self.children
+            .iter()
+            .map(|c| c.depth() + 1)
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub fn has_submodules(&self, path: &str, names: &[&str]) -> CheckResult {
+        let node = match self.get(path) {
+            Some(node) => node,
+            None => {
+                return CheckResult::missing(&format!("module '{path}' in module tree\n{self}"))
+            }
+        };
+
+        CheckResult::any(names.iter().map(|name| {
+            if node.children.iter().any(|c| c.name == *name) {
+                CheckResult::Success
+            } else {
+                CheckResult::missing(&format!(
+                    "submodule '{name}' of '{path}' in module tree\n{node}"
+                ))
+            }
+        }))
+    }
+
+    pub fn max_depth(&self, max: usize) -> CheckResult {
+        let mut failures = Vec::new();
+        self.collect_too_deep(&self.name, 0, max, &self.to_string(), &mut failures);
+        failures.into()
+    }
+
+    fn collect_too_deep(
+        &self,
+        path: &str,
+        depth: usize,
+        max: usize,
+        tree: &str,
+        out: &mut Vec<Failure>,
+    ) {
+        if depth > max {
+            out.push(Failure::unexpected(&format!(
+                "module '{path}' nested {depth} levels deep (max {max}) in module tree\n{tree}"
+            )));
+            return;
+        }
+        for child in &self.children {
+            child.collect_too_deep(
+                &format!("{path}::{}", child.name),
+                depth + 1,
+                max,
+                tree,
+                out,
+            );
+        }
+    }
+
+    fn render(&self, indent: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:indent$}{}", "", self.name)?;
+        for child in &self.children {
+            writeln!(f)?;
+            child.render(indent + 2, f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for ModuleTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(0, f)
+    }
+}
+
+impl CrateAst {
+    pub fn module_tree(&self) -> ModuleTree {
+        ModuleTree::from_paths(
+            self.items()
+                .into_iter()
+                .filter(|item| matches!(item.item, Item::Mod(_)))
+                .filter_map(|item| item.path()),
+        )
+    }
+
+    pub fn has_submodules(&self, path: &str, names: &[&str]) -> CheckResult {
+        self.module_tree().has_submodules(path, names)
+    }
+
+    pub fn max_module_depth(&self, max: usize) -> CheckResult {
+        self.module_tree().max_depth(max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_module_tree() -> Result<(), TestError> {
+        let dir = TestDir::new(&[
+            ("src/lib.rs", "mod api;\nmod util { mod a { mod b {} } }\n"),
+            ("src/api.rs", "mod v1;\nmod v2 {}\n"),
+            ("src/api/v1.rs", ""),
+        ])?;
+
+        let ast = CrateAst::load(&dir)?;
+
+        let tree = ast.module_tree();
+        assert_eq!(
+            tree.to_string(),
+            "crate\n  api\n    v1\n    v2\n  util\n    a\n      b"
+        );
+        assert_eq!(tree.depth(), 3);
+
+        assert!(ast.has_submodules("crate::api", &["v1", "v2"]).as_bool());
+        assert_eq!(
+            ast.has_submodules("crate::api", &["v1", "v3"])
+                .into_failures()[0]
+                .message(),
+            "Missing submodule 'v3' of 'crate::api' in module tree\napi\n  v1\n  v2"
+        );
+
+        assert!(ast.max_module_depth(3).as_bool());
+        let failures = ast.max_module_depth(2).into_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(
+            failures[0].message(),
+            format!("Unexpected module 'crate::util::a::b' nested 3 levels deep (max 2) in module tree\n{tree}")
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use crate::nfc::nfc;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NameOptions {
+    case_insensitive: bool,
+    nfc: bool,
+}
+
+impl NameOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn case_insensitive(self) -> Self {
+        Self {
+            case_insensitive: true,
+            ..self
+        }
+    }
+
+    pub fn nfc(self) -> Self {
+        Self { nfc: true, ..self }
+    }
+
+    pub(crate) fn apply(&self, name: &str) -> String {
+        let name = match self.nfc {
+            true => nfc(name),
+            false => name.to_string(),
+        };
+        match self.case_insensitive {
+            true => name.to_lowercase(),
+            false => name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let options = NameOptions::new();
+        assert_eq!(options.apply("Cafe\u{301}"), "Cafe\u{301}");
+        assert_eq!(options.nfc().apply("Cafe\u{301}"), "Café");
+        assert_eq!(options.nfc().apply("n\u{303}u\u{301}"), "ñú");
+        assert_eq!(options.nfc().apply("x\u{301}"), "x\u{301}");
+        assert_eq!(options.nfc().apply("\u{1112}\u{1161}\u{11ab}"), "\u{d55c}");
+        assert_eq!(options.case_insensitive().apply("GetUser"), "getuser");
+        assert_eq!(
+            options.nfc().case_insensitive().apply("CAFE\u{301}"),
+            "café"
+        );
+    }
+}
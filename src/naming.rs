@@ -0,0 +1,227 @@
+use crate::failure::{Failure, Location};
+use crate::harness::item_ident;
+use crate::rules::Rule;
+use crate::utils::CheckResult;
+use syn::{ext::IdentExt, Ident, ImplItem, Item, TraitItem};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Case {
+    Camel,
+    Snake,
+    ScreamingSnake,
+}
+
+impl Case {
+    pub fn for_kind(kind: &str) -> Option<Self> {
+        match kind {
+            "struct" | "enum" | "union" | "trait" | "type" | "variant" => Some(Case::Camel),
+            "fn" | "mod" => Some(Case::Snake),
+            "const" | "static" => Some(Case::ScreamingSnake),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Case::Camel => "CamelCase",
+            Case::Snake => "snake_case",
+            Case::ScreamingSnake => "SCREAMING_SNAKE_CASE",
+        }
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        let name = name.strip_prefix("r#").unwrap_or(name);
+        let trimmed = name.trim_matches('_');
+        if trimmed.is_empty() {
+            return true;
+        }
+        match self {
+            Case::Camel => {
+                trimmed.starts_with(|c: char| c.is_uppercase()) && !trimmed.contains('_')
+            }
+            Case::Snake => trimmed
+                .chars()
+                .all(|c| c.is_lowercase() || c.is_ascii_digit() || c == '_'),
+            Case::ScreamingSnake => trimmed
+                .chars()
+                .all(|c| c.is_uppercase() || c.is_ascii_digit() || c == '_'),
+        }
+    }
+}
+
+impl<'s> Rule<'s> {
+    pub fn naming_conventions<I, S>(exempt: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let exempt = exempt
+            .into_iter()
+            .map(|e| e.as_ref().to_string())
+            .collect::<Vec<_>>();
+        Self::new("naming conventions").must(move |item| naming(item, &exempt))
+    }
+}
+
+pub(crate) fn naming(item: &Item, exempt: &[String]) -> CheckResult {
+    let mut names = Vec::new();
+    match item {
+        Item::Enum(e) => names.extend(e.variants.iter().map(|v| ("variant", &v.ident))),
+        Item::Impl(i) if i.trait_.is_none() => {
+            names.extend(i.items.iter().filter_map(|item| match item {
+                ImplItem::Const(c) => Some(("const", &c.ident)),
+                ImplItem::Method(m) => Some(("fn", &m.sig.ident)),
+                _ => None,
+            }))
+        }
+        Item::Trait(t) => names.extend(t.items.iter().filter_map(|item| match item {
+            TraitItem::Const(c) => Some(("const", &c.ident)),
+            TraitItem::Method(m) => Some(("fn", &m.sig.ident)),
+            TraitItem::Type(t) => Some(("type", &t.ident)),
+            _ => None,
+        })),
+        _ => (),
+    }
+    if let Some((kind, ident)) = item_ident(item) {
+        names.insert(0, (kind, ident));
+    }
+
+    let failures = names
+        .into_iter()
+        .filter_map(|(kind, ident)| check_name(kind, ident, exempt))
+        .collect::<Vec<_>>();
+    failures.into()
+}
+
+fn check_name(kind: &str, ident: &Ident, exempt: &[String]) -> Option<Failure> {
+    let name = ident.unraw().to_string();
+    let case = Case::for_kind(kind)?;
+    if case.matches(&name) || exempt.contains(&name) {
+        return None;
+    }
+    Some(
+        Failure::mismatch(
+            &format!("{} name of {kind}", case.name()),
+            &convert(&name, case),
+            &name,
+        )
+        .at(Some(Location::of(ident))),
+    )
+}
+
+fn convert(name: &str, case: Case) -> String {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if (c == '_' || (c.is_uppercase() && prev_lower)) && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        if c != '_' {
+            word.push(c);
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    match case {
+        Case::Camel => words
+            .iter()
+            .map(|w| {
+                let mut chars = w.chars();
+                chars.next().map_or(String::new(), |c| {
+                    c.to_uppercase()
+                        .chain(chars.flat_map(char::to_lowercase))
+                        .collect()
+                })
+            })
+            .collect(),
+        Case::Snake => words
+            .iter()
+            .map(|w| w.to_lowercase())
+            .collect::<Vec<_>>()
+            .join("_"),
+        Case::ScreamingSnake => words
+            .iter()
+            .map(|w| w.to_uppercase())
+            .collect::<Vec<_>>()
+            .join("_"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crate_ast::CrateAst;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_case() {
+        assert!(Case::Camel.matches("HttpServer"));
+        assert!(!Case::Camel.matches("Http_Server"));
+        assert!(Case::Snake.matches("_get_user2"));
+        assert!(Case::Snake.matches("r#type"));
+        assert!(!Case::Snake.matches("getUser"));
+        assert!(Case::ScreamingSnake.matches("MAX_SIZE"));
+        assert_eq!(convert("getUserID", Case::Snake), "get_user_id");
+        assert_eq!(convert("http_server", Case::Camel), "HttpServer");
+        assert_eq!(convert("maxSize", Case::ScreamingSnake), "MAX_SIZE");
+    }
+
+    #[test]
+    fn test_naming_conventions() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"
+pub struct http_server;
+enum Method { Get, post }
+impl Server { fn getUser() {} }
+const max_size: usize = 1;
+mod Api {}
+struct HTTPServer;
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let failures = Rule::naming_conventions(["HTTPServer"])
+            .check(&ast)
+            .into_failures();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "Expected CamelCase name of struct to be 'HttpServer', got 'http_server'"
+                        .to_string(),
+                    Some(2)
+                ),
+                (
+                    "Expected CamelCase name of variant to be 'Post', got 'post'".to_string(),
+                    Some(3)
+                ),
+                (
+                    "Expected snake_case name of fn to be 'get_user', got 'getUser'".to_string(),
+                    Some(4)
+                ),
+                (
+                    "Expected SCREAMING_SNAKE_CASE name of const to be 'MAX_SIZE', got 'max_size'"
+                        .to_string(),
+                    Some(5)
+                ),
+                (
+                    "Expected snake_case name of mod to be 'api', got 'Api'".to_string(),
+                    Some(6)
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+}
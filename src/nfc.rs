@@ -0,0 +1,133 @@
+use crate::nfc_tables::{COMBINING_CLASSES, COMPOSITIONS, DECOMPOSITIONS};
+use std::cmp::Ordering;
+
+const S_BASE: u32 = 0xac00;
+const L_BASE: u32 = 0x1100;
+const V_BASE: u32 = 0x1161;
+const T_BASE: u32 = 0x11a7;
+const L_COUNT: u32 = 19;
+const V_COUNT: u32 = 21;
+const T_COUNT: u32 = 28;
+const N_COUNT: u32 = V_COUNT * T_COUNT;
+const S_COUNT: u32 = L_COUNT * N_COUNT;
+
+pub(crate) fn nfc(s: &str) -> String {
+    compose(nfd(s)).into_iter().collect()
+}
+
+fn nfd(s: &str) -> Vec<char> {
+    let mut chars = Vec::with_capacity(s.len());
+    for c in s.chars() {
+        decompose(c, &mut chars);
+    }
+    for i in 1..chars.len() {
+        let mut j = i;
+        while j > 0 && combining_class(chars[j]) != 0 {
+            if combining_class(chars[j - 1]) <= combining_class(chars[j]) {
+                break;
+            }
+            chars.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+    chars
+}
+
+fn decompose(c: char, out: &mut Vec<char>) {
+    let s_index = (c as u32).wrapping_sub(S_BASE);
+    if s_index < S_COUNT {
+        let l = L_BASE + s_index / N_COUNT;
+        let v = V_BASE + (s_index % N_COUNT) / T_COUNT;
+        let t = T_BASE + s_index % T_COUNT;
+        out.extend([l, v].into_iter().filter_map(char::from_u32));
+        if t != T_BASE {
+            out.extend(char::from_u32(t));
+        }
+        return;
+    }
+    match DECOMPOSITIONS.binary_search_by_key(&c, |(c, _)| *c) {
+        Ok(i) => DECOMPOSITIONS[i].1.chars().for_each(|c| decompose(c, out)),
+        Err(_) => out.push(c),
+    }
+}
+
+fn compose(chars: Vec<char>) -> Vec<char> {
+    let mut out: Vec<char> = Vec::with_capacity(chars.len());
+    let mut starter = None;
+    let mut last_class = 0;
+    for c in chars {
+        let class = combining_class(c);
+        if let Some(i) = starter {
+            let unblocked = i + 1 == out.len() || (last_class != 0 && last_class < class);
+            if let Some(composed) = unblocked.then(|| compose_pair(out[i], c)).flatten() {
+                out[i] = composed;
+                continue;
+            }
+        }
+        if class == 0 {
+            starter = Some(out.len());
+        }
+        last_class = class;
+        out.push(c);
+    }
+    out
+}
+
+fn compose_pair(first: char, second: char) -> Option<char> {
+    let (l_index, v_index) = (
+        (first as u32).wrapping_sub(L_BASE),
+        (second as u32).wrapping_sub(V_BASE),
+    );
+    if l_index < L_COUNT && v_index < V_COUNT {
+        return char::from_u32(S_BASE + (l_index * V_COUNT + v_index) * T_COUNT);
+    }
+    let (s_index, t_index) = (
+        (first as u32).wrapping_sub(S_BASE),
+        (second as u32).wrapping_sub(T_BASE),
+    );
+    if s_index < S_COUNT && s_index % T_COUNT == 0 && t_index > 0 && t_index < T_COUNT {
+        return char::from_u32(first as u32 + t_index);
+    }
+    COMPOSITIONS
+        .binary_search_by_key(&(first, second), |(pair, _)| *pair)
+        .ok()
+        .map(|i| COMPOSITIONS[i].1)
+}
+
+fn combining_class(c: char) -> u8 {
+    COMBINING_CLASSES
+        .binary_search_by(|(start, end, _)| match (c < *start, c > *end) {
+            (true, _) => Ordering::Greater,
+            (_, true) => Ordering::Less,
+            _ => Ordering::Equal,
+        })
+        .map_or(0, |i| COMBINING_CLASSES[i].2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nfc() {
+        let cases = [
+            ("Cafe\u{301}", "Caf\u{e9}"),
+            ("Vie\u{323}\u{302}t", "Vi\u{1ec7}t"),
+            ("Vie\u{302}\u{323}t", "Vi\u{1ec7}t"),
+            ("\u{3b1}\u{313}\u{301}\u{345}", "\u{1f84}"),
+            ("\u{418}\u{306}", "\u{419}"),
+            ("\u{435}\u{308}\u{436}", "\u{451}\u{436}"),
+            ("\u{1112}\u{1161}\u{11ab}", "\u{d55c}"),
+            ("\u{d558}\u{11ab}", "\u{d55c}"),
+            ("a\u{301}\u{301}\u{327}", "\u{e1}\u{327}\u{301}"),
+            ("q\u{307}\u{323}", "q\u{323}\u{307}"),
+            ("\u{212b}", "\u{c5}"),
+            ("\u{1e9b}\u{323}", "\u{1e9b}\u{323}"),
+            ("x\u{301}", "x\u{301}"),
+            ("get_user", "get_user"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(nfc(input), expected, "nfc({input:?})");
+        }
+    }
+}
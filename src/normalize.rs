@@ -0,0 +1,327 @@
+use proc_macro2::{Delimiter, Group, Ident, Spacing, TokenStream, TokenTree};
+use std::cell::RefCell;
+
+thread_local! {
+    static CRATE_NAME: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+const KEYWORDS: [&str; 51] = [
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "do", "dyn", "else",
+    "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop", "macro",
+    "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "self", "Self",
+    "static", "struct", "super", "trait", "true", "try", "type", "typeof", "unsafe", "unsized",
+    "use", "virtual", "where", "while", "yield", "abstract", "become",
+];
+
+const PAREN_KEYWORDS: [&str; 9] = [
+    "if", "while", "match", "return", "in", "let", "for", "break", "else",
+];
+
+pub fn with_crate_name<F, R>(name: &str, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let name = name.replace('-', "_");
+    let _restore = RestoreCrateName(CRATE_NAME.with(|c| c.borrow_mut().replace(name)));
+    f()
+}
+
+struct RestoreCrateName(Option<String>);
+
+impl Drop for RestoreCrateName {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CRATE_NAME.with(|c| *c.borrow_mut() = previous);
+    }
+}
+
+pub(crate) fn crate_name() -> Option<String> {
+    CRATE_NAME.with(|c| c.borrow().clone())
+}
+
+pub fn normalize(tokens: TokenStream) -> TokenStream {
+    let crate_name = crate_name();
+    let mut out = Vec::<TokenTree>::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(tt) = tokens.next() {
+        match tt {
+            TokenTree::Group(g) if g.delimiter() == Delimiter::None => {
+                out.extend(normalize(g.stream()));
+            }
+            TokenTree::Group(g) => {
+                let inner = strip_trailing_comma(g.delimiter(), normalize(g.stream()), &out);
+                match redundant_parens(g.delimiter(), &inner, &out) {
+                    Some(tt) => out.push(tt),
+                    None => {
+                        let mut group = Group::new(g.delimiter(), inner);
+                        group.set_span(g.span());
+                        out.push(TokenTree::Group(group));
+                    }
+                }
+            }
+            TokenTree::Ident(ident)
+                if crate_name.as_ref().is_some_and(|name| ident == name)
+                    && is_colon(tokens.peek(), Spacing::Joint) =>
+            {
+                if let Some(start) = path_start(&out) {
+                    out.truncate(start);
+                    out.push(TokenTree::Ident(Ident::new("crate", ident.span())));
+                } else {
+                    out.push(TokenTree::Ident(ident));
+                }
+            }
+            TokenTree::Ident(ident) => out.push(TokenTree::Ident(unraw(ident))),
+            tt => out.push(tt),
+        }
+    }
+    out.into_iter().collect()
+}
+
+fn is_colon(tt: Option<&TokenTree>, spacing: Spacing) -> bool {
+    matches!(tt, Some(TokenTree::Punct(p)) if p.as_char() == ':' && p.spacing() == spacing)
+}
+
+fn path_start(out: &[TokenTree]) -> Option<usize> {
+    let len = out.len();
+    if len < 2 || !is_colon(out.get(len - 1), Spacing::Alone) {
+        return Some(len);
+    }
+    if !is_colon(out.get(len - 2), Spacing::Joint) {
+        return Some(len);
+    }
+    match out.get(len.wrapping_sub(3)) {
+        Some(TokenTree::Ident(_) | TokenTree::Group(_)) => None,
+        Some(TokenTree::Punct(p)) if p.as_char() == '>' => None,
+        _ => Some(len - 2),
+    }
+}
+
+pub fn sort_attrs(tokens: TokenStream) -> TokenStream {
+    let mut tokens = tokens
+        .into_iter()
+        .map(|tt| match tt {
+            TokenTree::Group(g) => {
+                let mut group = Group::new(g.delimiter(), sort_attrs(g.stream()));
+                group.set_span(g.span());
+                TokenTree::Group(group)
+            }
+            tt => tt,
+        })
+        .peekable();
+
+    let mut out = Vec::new();
+    while let Some(tt) = tokens.next() {
+        if !is_pound(&tt) {
+            out.push(tt);
+            continue;
+        }
+        let mut attrs = Vec::new();
+        let mut pound = Some(tt);
+        while let Some(tt) = pound.take() {
+            let bang = match tokens.peek() {
+                Some(TokenTree::Punct(p)) if p.as_char() == '!' => tokens.next(),
+                _ => None,
+            };
+            match tokens.peek() {
+                Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Bracket => {
+                    let body = tokens.next();
+                    attrs.push(
+                        Some(tt)
+                            .into_iter()
+                            .chain(bang)
+                            .chain(body)
+                            .collect::<Vec<_>>(),
+                    );
+                }
+                _ => {
+                    out.extend(Some(tt).into_iter().chain(bang));
+                    break;
+                }
+            }
+            pound = tokens.next_if(is_pound);
+        }
+        let (mut docs, mut others): (Vec<_>, Vec<_>) = attrs.into_iter().partition(|a| is_doc(a));
+        others.sort_by_cached_key(|attr| attr.iter().cloned().collect::<TokenStream>().to_string());
+        docs.append(&mut others);
+        out.extend(docs.into_iter().flatten());
+    }
+    out.into_iter().collect()
+}
+
+fn is_pound(tt: &TokenTree) -> bool {
+    matches!(tt, TokenTree::Punct(p) if p.as_char() == '#')
+}
+
+fn is_doc(attr: &[TokenTree]) -> bool {
+    match attr.last() {
+        Some(TokenTree::Group(g)) => {
+            matches!(g.stream().into_iter().next(), Some(TokenTree::Ident(i)) if i == "doc")
+        }
+        _ => false,
+    }
+}
+
+fn strip_trailing_comma(
+    delimiter: Delimiter,
+    tokens: TokenStream,
+    preceding: &[TokenTree],
+) -> TokenStream {
+    let mut tokens = tokens.into_iter().collect::<Vec<_>>();
+    let commas = tokens
+        .iter()
+        .filter(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == ','))
+        .count();
+    let trailing = matches!(tokens.last(), Some(TokenTree::Punct(p)) if p.as_char() == ',');
+    let tuple = delimiter == Delimiter::Parenthesis && commas == 1 && standalone(preceding);
+    if trailing && !tuple {
+        tokens.pop();
+    }
+    tokens.into_iter().collect()
+}
+
+fn standalone(preceding: &[TokenTree]) -> bool {
+    match preceding {
+        [] => true,
+        [.., TokenTree::Punct(dash), TokenTree::Punct(arrow)]
+            if dash.as_char() == '-'
+                && dash.spacing() == Spacing::Joint
+                && arrow.as_char() == '>' =>
+        {
+            true
+        }
+        [.., TokenTree::Punct(p)] => !matches!(p.as_char(), '>' | '!' | '#'),
+        [.., TokenTree::Ident(ident)] => PAREN_KEYWORDS.contains(&ident.to_string().as_str()),
+        _ => false,
+    }
+}
+
+fn redundant_parens(
+    delimiter: Delimiter,
+    inner: &TokenStream,
+    preceding: &[TokenTree],
+) -> Option<TokenTree> {
+    if delimiter != Delimiter::Parenthesis {
+        return None;
+    }
+    let mut iter = inner.clone().into_iter();
+    let single = match (iter.next(), iter.next()) {
+        (Some(tt @ (TokenTree::Ident(_) | TokenTree::Literal(_) | TokenTree::Group(_))), None) => {
+            tt
+        }
+        _ => return None,
+    };
+    standalone(preceding).then_some(single)
+}
+
+fn unraw(ident: Ident) -> Ident {
+    let rendered = ident.to_string();
+    match rendered.strip_prefix("r#") {
+        Some(name) if !KEYWORDS.contains(&name) => Ident::new(name, ident.span()),
+        _ => ident,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::compare_tokens;
+    use quote::quote;
+
+    #[test]
+    fn test_normalize() {
+        let cases = [
+            (quote!(let x = (1);), quote!(let x = 1;)),
+            (
+                quote!(if (ready) {
+                    run((a))
+                }),
+                quote!(if ready {
+                    run(a)
+                }),
+            ),
+            (quote!(vec![1, 2, 3,]), quote!(vec![1, 2, 3])),
+            (quote!(Point { x, y }), quote!(Point { x, y })),
+            (quote!(f(a, b,)), quote!(f(a, b))),
+            (quote!(f(a,)), quote!(f(a))),
+            (
+                quote!(Some(x).map(|v| g(v,))),
+                quote!(Some(x).map(|v| g(v))),
+            ),
+            (
+                quote!(
+                    fn f() -> (u8) {}
+                ),
+                quote!(
+                    fn f() -> u8 {}
+                ),
+            ),
+            (quote!(let r#value = r#match;), quote!(let value = r#match;)),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(normalize(input).to_string(), expected.to_string());
+        }
+
+        let unchanged = [
+            quote!(let t = (a,);),
+            quote!(f((a,))),
+            quote!(
+                fn f() -> (u8,) {}
+            ),
+            quote!(match (a,) {}),
+            quote!(f(x)),
+            quote!(f::<u8>(x)),
+            quote!(println!(x)),
+            quote!(let n = (a + b) * c;),
+        ];
+        for input in unchanged {
+            assert_eq!(normalize(input.clone()).to_string(), input.to_string());
+        }
+
+        let expected = quote!(use crate::model::User; let u: crate::model::User = crate::new(););
+        for input in [
+            quote!(use my_crate::model::User; let u: ::my_crate::model::User = my_crate::new();),
+            quote!(use crate::model::User; let u: crate::model::User = crate::new();),
+        ] {
+            let normalized = with_crate_name("my-crate", || normalize(input));
+            assert_eq!(normalized.to_string(), expected.to_string());
+        }
+        assert_eq!(
+            with_crate_name("my_crate", || normalize(quote!(other::my_crate::X))).to_string(),
+            quote!(other::my_crate::X).to_string()
+        );
+        assert_eq!(
+            normalize(quote!(my_crate::X)).to_string(),
+            quote!(my_crate::X).to_string()
+        );
+        let panicked =
+            std::panic::catch_unwind(|| with_crate_name("my_crate", || panic!("in scope")));
+        assert!(panicked.is_err());
+        assert_eq!(crate_name(), None);
+
+        let grouped =
+            TokenStream::from(TokenTree::Group(Group::new(Delimiter::None, quote!(a + b))));
+        assert_eq!(normalize(quote!(x * #grouped)).to_string(), "x * a + b");
+
+        assert_eq!(
+            sort_attrs(quote! {
+                /// Docs
+                #[must_use]
+                #[inline]
+                /// More
+                fn f() { #![allow(x)] #![deny(y)] }
+            })
+            .to_string(),
+            quote! {
+                /// Docs
+                /// More
+                #[inline]
+                #[must_use]
+                fn f() { #![allow(x)] #![deny(y)] }
+            }
+            .to_string()
+        );
+
+        let block: syn::Block = syn::parse_quote!({ send(r#msg, (retries),) });
+        assert!(compare_tokens("block", &quote!({ send(msg, retries) }), &block).as_bool());
+    }
+}
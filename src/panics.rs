@@ -0,0 +1,204 @@
+use crate::coverage::{in_test_module, is_test, test_modules};
+use crate::crate_ast::{CrateAst, CrateItem};
+use crate::failure::{Failure, Location};
+use crate::rules::Rule;
+use crate::utils::CheckResult;
+use proc_macro2::{TokenStream, TokenTree};
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::{Attribute, Expr, ExprMethodCall, ImplItemMethod, Item, Macro, Token, TraitItemMethod};
+
+const PANIC_MACROS: [&str; 4] = ["panic", "todo", "unimplemented", "unreachable"];
+const PANIC_METHODS: [&str; 2] = ["unwrap", "expect"];
+
+impl<'s> Rule<'s> {
+    pub fn panic_free(allow_attr: &str) -> Self {
+        let allow_attr = allow_attr.to_string();
+        Self::new("no panics").must_crate(move |ast, items| panic_free(ast, items, &allow_attr))
+    }
+}
+
+pub(crate) fn panic_free(ast: &CrateAst, items: &[CrateItem<'_>], allow_attr: &str) -> CheckResult {
+    let test_modules = test_modules(ast);
+    CheckResult::any(
+        items
+            .iter()
+            .filter(|i| !in_test_module(i, &test_modules))
+            .map(|item| {
+                let mut visitor = PanicVisitor {
+                    allow_attr,
+                    failures: Vec::new(),
+                };
+                visitor.visit_item(item.item);
+                item.scope(visitor.failures.into())
+            }),
+    )
+}
+
+struct PanicVisitor<'a> {
+    allow_attr: &'a str,
+    failures: Vec<Failure>,
+}
+
+impl PanicVisitor<'_> {
+    fn skipped(&self, attrs: &[Attribute]) -> bool {
+        is_test(attrs)
+            || attrs.iter().any(|a| {
+                a.path
+                    .segments
+                    .last()
+                    .is_some_and(|s| s.ident == self.allow_attr)
+            })
+    }
+
+    fn scan_tokens(&mut self, tokens: TokenStream) {
+        let tokens = tokens.into_iter().collect::<Vec<_>>();
+        for (i, token) in tokens.iter().enumerate() {
+            match (token, tokens.get(i + 1), tokens.get(i + 2)) {
+                (
+                    TokenTree::Punct(dot),
+                    Some(TokenTree::Ident(name)),
+                    Some(TokenTree::Group(_)),
+                ) if dot.as_char() == '.' && PANIC_METHODS.contains(&name.to_string().as_str()) => {
+                    self.failures.push(
+                        Failure::unexpected(&format!(".{name}()")).at(Some(Location::of(name))),
+                    );
+                }
+                (TokenTree::Ident(name), Some(TokenTree::Punct(bang)), _)
+                    if bang.as_char() == '!'
+                        && PANIC_MACROS.contains(&name.to_string().as_str()) =>
+                {
+                    self.failures.push(
+                        Failure::unexpected(&format!("{name}!()")).at(Some(Location::of(name))),
+                    );
+                }
+                (TokenTree::Group(group), _, _) => self.scan_tokens(group.stream()),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for PanicVisitor<'_> {
+    fn visit_item(&mut self, item: &'ast Item) {
+        let attrs = match item {
+            Item::Mod(_) => return,
+            Item::Const(i) => &i.attrs,
+            Item::Enum(i) => &i.attrs,
+            Item::Fn(i) => &i.attrs,
+            Item::Impl(i) => &i.attrs,
+            Item::Static(i) => &i.attrs,
+            Item::Struct(i) => &i.attrs,
+            Item::Trait(i) => &i.attrs,
+            _ => return visit::visit_item(self, item),
+        };
+        if !self.skipped(attrs) {
+            visit::visit_item(self, item);
+        }
+    }
+
+    fn visit_impl_item_method(&mut self, method: &'ast ImplItemMethod) {
+        if !self.skipped(&method.attrs) {
+            visit::visit_impl_item_method(self, method);
+        }
+    }
+
+    fn visit_trait_item_method(&mut self, method: &'ast TraitItemMethod) {
+        if !self.skipped(&method.attrs) {
+            visit::visit_trait_item_method(self, method);
+        }
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        if let Some(name) = mac
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident.to_string())
+            .filter(|name| PANIC_MACROS.contains(&name.as_str()))
+        {
+            self.failures
+                .push(Failure::unexpected(&format!("{name}!()")).at(Some(Location::of(mac))));
+        }
+        match mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated) {
+            Ok(args) => args.iter().for_each(|arg| self.visit_expr(arg)),
+            Err(_) => self.scan_tokens(mac.tokens.clone()),
+        }
+        visit::visit_macro(self, mac);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        let name = call.method.to_string();
+        if PANIC_METHODS.contains(&name.as_str()) {
+            self.failures.push(
+                Failure::unexpected(&format!(".{name}()")).at(Some(Location::of(&call.method))),
+            );
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_panic_free() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"fn parse(s: &str) -> u8 {
+    s.parse().unwrap()
+}
+fn load() {
+    let f = open().expect("file");
+    if f.is_empty() { unreachable!() }
+}
+#[allow_panic]
+fn checked() { panic!("boom") }
+struct Server;
+impl Server {
+    fn run(&self) { todo!() }
+    #[allow_panic]
+    fn stop(&self) { unimplemented!() }
+}
+#[cfg(test)]
+mod tests {
+    fn helper() { None::<u8>.unwrap(); }
+}
+#[test]
+fn test_parse() { parse("x").to_string().parse::<u8>().unwrap(); }
+fn check(x: Option<u8>, y: Option<&str>) {
+    assert_eq!(x.unwrap(), 1);
+    let s = format!("{}", y.expect(".."));
+    let v: Vec<u8> = vec![todo!()];
+    let w = vec![0; x.unwrap() as usize];
+}
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let failures = Rule::panic_free("allow_panic").check(&ast).into_failures();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Unexpected .unwrap()".to_string(), Some(2)),
+                ("Unexpected .expect()".to_string(), Some(5)),
+                ("Unexpected unreachable!()".to_string(), Some(6)),
+                ("Unexpected todo!()".to_string(), Some(12)),
+                ("Unexpected .unwrap()".to_string(), Some(23)),
+                ("Unexpected .expect()".to_string(), Some(24)),
+                ("Unexpected todo!()".to_string(), Some(25)),
+                ("Unexpected .unwrap()".to_string(), Some(26)),
+            ]
+        );
+
+        Ok(())
+    }
+}
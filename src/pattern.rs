@@ -0,0 +1,261 @@
+use crate::explain;
+use crate::failure::{Failure, Location};
+use crate::utils::CheckResult;
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::ToTokens;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use syn::parse::Parse;
+use syn::{Expr, Ident, Item, Lifetime, Lit, Pat, Path, Type};
+
+pub type Bindings = BTreeMap<String, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fragment {
+    Ident,
+    Ty,
+    Expr,
+    Path,
+    Pat,
+    Item,
+    Lifetime,
+    Literal,
+    Tt,
+}
+
+impl Fragment {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "ident" => Self::Ident,
+            "ty" => Self::Ty,
+            "expr" => Self::Expr,
+            "path" => Self::Path,
+            "pat" => Self::Pat,
+            "item" => Self::Item,
+            "lifetime" => Self::Lifetime,
+            "literal" => Self::Literal,
+            "tt" => Self::Tt,
+            _ => return None,
+        })
+    }
+
+    fn accepts(&self, tokens: &[TokenTree]) -> bool {
+        fn parses<T: Parse>(tokens: &[TokenTree]) -> bool {
+            syn::parse2::<T>(tokens.iter().cloned().collect()).is_ok()
+        }
+
+        match self {
+            Self::Ident => tokens.len() == 1 && parses::<Ident>(tokens),
+            Self::Ty => parses::<Type>(tokens),
+            Self::Expr => parses::<Expr>(tokens),
+            Self::Path => parses::<Path>(tokens),
+            Self::Pat => parses::<Pat>(tokens),
+            Self::Item => parses::<Item>(tokens),
+            Self::Lifetime => tokens.len() == 2 && parses::<Lifetime>(tokens),
+            Self::Literal => tokens.len() <= 2 && parses::<Lit>(tokens),
+            Self::Tt => tokens.len() == 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum PatternToken {
+    Token(String),
+    Group(Delimiter, Vec<PatternToken>),
+    Var(String, Fragment),
+}
+
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    source: TokenStream,
+    tokens: Vec<PatternToken>,
+}
+
+impl Pattern {
+    pub fn new(source: TokenStream) -> Result<Self, CheckResult> {
+        let tokens = compile(source.clone())
+            .map_err(|e| CheckResult::Failure(vec![Failure::invalid("pattern", &e)]))?;
+        Ok(Self { source, tokens })
+    }
+
+    pub fn parse_str(source: &str) -> Result<Self, CheckResult> {
+        let source = source
+            .parse::<TokenStream>()
+            .map_err(|e| CheckResult::Failure(vec![Failure::invalid("pattern", &e.to_string())]))?;
+        Self::new(source)
+    }
+
+    pub fn bind<T>(&self, actual: &T) -> Result<Bindings, CheckResult>
+    where
+        T: ToTokens,
+    {
+        let tokens = actual.to_token_stream();
+        let mut bindings = Bindings::new();
+        if match_tokens(
+            &self.tokens,
+            &tokens.clone().into_iter().collect::<Vec<_>>(),
+            &mut bindings,
+        ) {
+            Ok(bindings)
+        } else {
+            Err(CheckResult::Failure(vec![Failure::mismatch(
+                "pattern",
+                &self.to_string(),
+                &tokens.to_string(),
+            )
+            .at(Some(Location::of(actual)))]))
+        }
+    }
+
+    pub fn check<T>(&self, actual: &T) -> CheckResult
+    where
+        T: ToTokens,
+    {
+        match self.bind(actual) {
+            Ok(bindings) => {
+                for (name, value) in bindings {
+                    explain::record(|| format!("${name} = '{value}'"));
+                }
+                CheckResult::Success
+            }
+            Err(failure) => failure,
+        }
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+fn compile(tokens: TokenStream) -> Result<Vec<PatternToken>, String> {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut compiled = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            TokenTree::Punct(p) if p.as_char() == '$' => match &tokens[i + 1..] {
+                [TokenTree::Ident(name), TokenTree::Punct(colon), TokenTree::Ident(kind), ..]
+                    if colon.as_char() == ':' =>
+                {
+                    let fragment = Fragment::from_name(&kind.to_string())
+                        .ok_or_else(|| format!("unknown fragment '{kind}' for ${name}"))?;
+                    compiled.push(PatternToken::Var(name.to_string(), fragment));
+                    i += 4;
+                }
+                _ => return Err("expected '$name:fragment'".to_string()),
+            },
+            TokenTree::Group(g) => {
+                compiled.push(PatternToken::Group(g.delimiter(), compile(g.stream())?));
+                i += 1;
+            }
+            tt => {
+                compiled.push(PatternToken::Token(tt.to_string()));
+                i += 1;
+            }
+        }
+    }
+    Ok(compiled)
+}
+
+fn match_tokens(pattern: &[PatternToken], actual: &[TokenTree], bindings: &mut Bindings) -> bool {
+    let (first, rest) = match pattern.split_first() {
+        Some(split) => split,
+        None => return actual.is_empty(),
+    };
+
+    match first {
+        PatternToken::Token(expected) => match actual.split_first() {
+            Some((tt, actual_rest)) if !matches!(tt, TokenTree::Group(_)) => {
+                tt.to_string() == *expected && match_tokens(rest, actual_rest, bindings)
+            }
+            _ => false,
+        },
+        PatternToken::Group(delimiter, inner) => match actual.split_first() {
+            Some((TokenTree::Group(g), actual_rest)) if g.delimiter() == *delimiter => {
+                let group_tokens = g.stream().into_iter().collect::<Vec<_>>();
+                match_tokens(inner, &group_tokens, bindings)
+                    && match_tokens(rest, actual_rest, bindings)
+            }
+            _ => false,
+        },
+        PatternToken::Var(name, fragment) => {
+            for len in 1..=actual.len() {
+                let (candidate, actual_rest) = actual.split_at(len);
+                if !fragment.accepts(candidate) {
+                    continue;
+                }
+                let value = candidate
+                    .iter()
+                    .cloned()
+                    .collect::<TokenStream>()
+                    .to_string();
+                if bindings.get(name).is_some_and(|bound| *bound != value) {
+                    continue;
+                }
+
+                let mut attempt = bindings.clone();
+                attempt.insert(name.clone(), value);
+                if match_tokens(rest, actual_rest, &mut attempt) {
+                    *bindings = attempt;
+                    return true;
+                }
+            }
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_bind() -> Result<(), TestError> {
+        let item: syn::ItemFn = syn::parse_str(
+            r#"
+            pub fn get_user(id: UserId) -> Option<User> { self.users.get(&id).cloned() }
+        "#,
+        )?;
+
+        let pattern = Pattern::new(quote::quote! {
+            pub fn $name:ident(id: $id:ty) -> Option<$ty:ty> { self.$field:ident.get(&id).cloned() }
+        })
+        .unwrap();
+        let bindings = pattern.bind(&item).unwrap();
+
+        assert_eq!(bindings["name"], "get_user");
+        assert_eq!(bindings["id"], "UserId");
+        assert_eq!(bindings["ty"], "User");
+        assert_eq!(bindings["field"], "users");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bind_repeated() -> Result<(), TestError> {
+        let pattern = Pattern::parse_str("impl From<$t:ty> for $t:ty {}").unwrap();
+
+        let item: syn::ItemImpl = syn::parse_str("impl From<Vec<u8>> for Vec<u8> {}")?;
+        assert!(pattern.check(&item).as_bool());
+
+        let item: syn::ItemImpl = syn::parse_str("impl From<Vec<u8>> for String {}")?;
+        let failures = pattern.check(&item).into_failures();
+        assert_eq!(
+            failures[0].message(),
+            "Expected pattern to be 'impl From <$ t : ty > for $ t : ty { }', got 'impl From < Vec < u8 > > for String { }'"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(Pattern::parse_str("fn $name:unknown()").is_err());
+        assert!(Pattern::parse_str("fn $name()").is_err());
+    }
+}
@@ -1,2 +1,7 @@
+pub use crate::file::{HasInnerAttrs, HasItems};
 pub use crate::function::HasFn;
+pub use crate::item_const::HasConst;
+pub use crate::item_impl::HasImpl;
+pub use crate::item_trait::HasTrait;
+pub use crate::type_alias::HasTypeAlias;
 pub use crate::utils::Check;
@@ -0,0 +1,208 @@
+use crate::color;
+use crate::explain::{explain, Verbosity};
+use crate::junit::JUnitReport;
+use crate::utils::{Check, CheckResult};
+use std::fmt::{self, Display};
+use std::io;
+
+#[must_use]
+#[derive(Debug, Default)]
+pub struct Report {
+    pub(crate) checks: Vec<(String, CheckResult)>,
+    explanations: Vec<Vec<String>>,
+    verbosity: Verbosity,
+}
+
+impl Report {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn verbosity(&mut self, verbosity: Verbosity) -> &mut Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    pub fn check<C>(&mut self, name: &str, check: C) -> &mut Self
+    where
+        C: Check,
+    {
+        self.run(name, check, false)
+    }
+
+    fn run<C>(&mut self, name: &str, check: C, warning: bool) -> &mut Self
+    where
+        C: Check,
+    {
+        let (result, explanations) = match self.verbosity {
+            Verbosity::Normal => (check.check(), Vec::new()),
+            Verbosity::Explain => explain(|| check.check()),
+        };
+        let result = if warning { result.as_warning() } else { result };
+        self.add(name, result);
+        if let Some(last) = self.explanations.last_mut() {
+            *last = explanations;
+        }
+        self
+    }
+
+    pub fn warn<C>(&mut self, name: &str, check: C) -> &mut Self
+    where
+        C: Check,
+    {
+        self.run(name, check, true)
+    }
+
+    pub fn add(&mut self, name: &str, result: CheckResult) -> &mut Self {
+        self.checks.push((name.to_string(), result));
+        self.explanations.push(Vec::new());
+        self
+    }
+
+    pub fn results(&self) -> impl Iterator<Item = (&str, &CheckResult)> {
+        self.checks
+            .iter()
+            .map(|(name, result)| (name.as_str(), result))
+    }
+
+    pub fn passed(&self) -> usize {
+        self.checks.iter().filter(|(_, r)| r.as_bool()).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.checks.len() - self.passed()
+    }
+
+    pub fn as_bool(&self) -> bool {
+        self.failed() == 0
+    }
+
+    pub fn to_junit(&self, name: &str) -> JUnitReport {
+        let mut junit = JUnitReport::new(name);
+        for (case, result) in &self.checks {
+            junit.add(case, result.clone());
+        }
+        junit
+    }
+
+    pub fn render(&self, color: bool) -> String {
+        let mut rendered = format!("{} of {} check(s) passed", self.passed(), self.checks.len());
+        for ((name, result), explanations) in self.checks.iter().zip(&self.explanations) {
+            let status = match result {
+                CheckResult::Success => "[ok]  ",
+                _ if result.as_bool() => "[warn]",
+                _ => "[FAIL]",
+            };
+            rendered.push_str(&format!("\n{status} {name}"));
+            for failure in result.failures() {
+                rendered.push_str(&format!(
+                    "\n         - {}",
+                    failure.render_with(color, self.verbosity)
+                ));
+                if self.verbosity == Verbosity::Explain {
+                    rendered.push_str(&failure.render_sides(color, "           "));
+                }
+            }
+            for explanation in explanations {
+                rendered.push_str(&format!("\n         = {explanation}"));
+            }
+        }
+        rendered
+    }
+
+    #[track_caller]
+    pub fn assert(&self) {
+        if !self.as_bool() {
+            panic!("{}", self.render(color::enabled(&io::stderr())));
+        }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_report() -> Result<(), TestError> {
+        let file: syn::File = syn::parse_str(
+            r#"
+            fn main() { println!("Hello, world!"); }
+        "#,
+        )?;
+
+        let mut report = Report::new();
+        report
+            .check("has main fn", file.items.has_fn().with_name("main"))
+            .check("has other fn", file.items.has_fn().with_name("other"));
+
+        assert!(!report.as_bool());
+        assert_eq!((report.passed(), report.failed()), (1, 1));
+        assert_eq!(
+            report.render(false),
+            "1 of 2 check(s) passed
+[ok]   has main fn
+[FAIL] has other fn
+         - fn main: Expected name to be 'other', got 'main' (at 2:13)"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_report_warn() {
+        let mut report = Report::new();
+        report
+            .add("structural", CheckResult::Success)
+            .warn("style", CheckResult::missing("doc comment"));
+
+        assert!(report.as_bool());
+        assert_eq!(
+            report.render(false),
+            "2 of 2 check(s) passed
+[ok]   structural
+[warn] style
+         - warning: Missing doc comment"
+        );
+        report.assert();
+    }
+
+    #[test]
+    fn test_report_explain() -> Result<(), TestError> {
+        let func: syn::ItemFn = syn::parse_str("pub fn main() {}")?;
+
+        let mut report = Report::new();
+        report.verbosity(Verbosity::Explain).check(
+            "main",
+            func.has_fn()
+                .with_name("main")
+                .with_vis(syn::parse_quote!(pub)),
+        );
+
+        assert_eq!(
+            report.render(false),
+            "1 of 1 check(s) passed
+[ok]   main
+         = name: 'main' == 'main'
+         = visibility: 'pub' == 'pub'"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "[FAIL] missing")]
+    fn test_report_assert() {
+        let mut report = Report::new();
+        report.add("missing", CheckResult::missing("name")).assert();
+    }
+}
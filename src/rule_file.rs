@@ -0,0 +1,951 @@
+use crate::accessors::accessors;
+use crate::asyncness::{async_receiver, instrumented, no_async};
+use crate::conversions::{error_impls, from_impls};
+use crate::coverage::test_coverage;
+use crate::docs::DocPolicy;
+use crate::entry::thin_main;
+use crate::failure::Failure;
+use crate::fallible::{fallible, impl_trait_returns};
+use crate::function::FnSpec;
+use crate::harness::item_ident;
+use crate::limits::{max_impl_fns, max_items};
+use crate::lints::{no_allow, no_feature_gates};
+use crate::naming::naming;
+use crate::panics::panic_free;
+use crate::rules::{
+    has_attr, is_reexport, item_kind, no_dependency, no_extern_crate, no_glob_imports, object_safe,
+    reexports, Rule, Rules,
+};
+use crate::toml::{Document, Table, Value};
+use crate::trait_objects::no_pub_trait_objects;
+use crate::unsafety::{no_static_mut, no_unsafe};
+use crate::utils::{parse_str, CheckResult};
+use crate::visibility::{forbid_vis, require_private};
+use std::fs;
+use std::path::Path;
+use syn::{ImplItem, Item, Type, Visibility};
+
+type Predicate = Box<dyn Fn(&Item) -> bool>;
+
+impl Rules<'static> {
+    pub fn load<P>(path: P) -> Result<Self, CheckResult>
+    where
+        P: AsRef<Path>,
+    {
+        let name = path.as_ref().display().to_string();
+        if let Some("yaml" | "yml") = path.as_ref().extension().and_then(|e| e.to_str()) {
+            return Err(invalid(
+                &format!("rule file '{name}'"),
+                "YAML rule files are not supported, use TOML",
+            ));
+        }
+        let source = fs::read_to_string(path.as_ref())
+            .map_err(|e| invalid(&format!("rule file '{name}'"), &e.to_string()))?;
+        Self::from_toml(&source).map_err(|failure| failure.within(&name))
+    }
+
+    pub fn from_toml(source: &str) -> Result<Self, CheckResult> {
+        let document = Document::parse(source).map_err(|e| invalid("rule file", &e))?;
+        if let Some((name, table)) = document.tables.first() {
+            return Err(invalid(
+                &format!("table at line {}", table.line),
+                &format!("unknown table [{name}], rules are declared with [[rule]]"),
+            ));
+        }
+        let mut rules = Rules::new();
+        for table in document.tables("rule") {
+            rules = rules.with_rule(rule_from_table(table)?);
+        }
+        Ok(rules)
+    }
+}
+
+type Section = fn(Rule<'static>, &mut Fields<'_>) -> Result<Rule<'static>, CheckResult>;
+
+const SECTIONS: [Section; 31] = [
+    module,
+    fn_spec,
+    asyncness,
+    async_receiver_rule,
+    error_impls_rule,
+    from_impls_rule,
+    accessors_rule,
+    instrument,
+    object_safety,
+    async_fns,
+    reexports_only_in,
+    dependencies,
+    docs,
+    crate_docs,
+    feature_gates,
+    forbidden_vis,
+    private,
+    static_mut,
+    unsafe_code,
+    thin_main_rule,
+    test_pattern,
+    allow_attrs,
+    naming_conventions,
+    extern_crates,
+    max_items_rule,
+    max_impl_fns_rule,
+    impl_trait,
+    trait_objects,
+    panics,
+    fallible_rule,
+    glob_imports,
+];
+
+struct Fields<'t> {
+    subject: String,
+    entries: Vec<(&'t str, &'t Value)>,
+}
+
+impl<'t> Fields<'t> {
+    fn new(table: &'t Table) -> Self {
+        Self {
+            subject: format!("rule at line {}", table.line),
+            entries: table
+                .entries
+                .iter()
+                .map(|(key, value)| (key.as_str(), value))
+                .collect(),
+        }
+    }
+
+    fn invalid(&self, error: &str) -> CheckResult {
+        invalid(&self.subject, error)
+    }
+
+    fn contains(&self, key: &str) -> bool {
+        self.entries.iter().any(|(k, _)| *k == key)
+    }
+
+    fn take(&mut self, key: &str) -> Option<&'t Value> {
+        let mut value = None;
+        self.entries.retain(|(k, v)| match *k == key {
+            true => {
+                value = Some(*v);
+                false
+            }
+            false => true,
+        });
+        value
+    }
+
+    fn string(&mut self, key: &str) -> Result<Option<String>, CheckResult> {
+        match self.take(key) {
+            Some(Value::String(s)) => Ok(Some(s.clone())),
+            Some(_) => Err(self.invalid(&format!("'{key}' must be a string"))),
+            None => Ok(None),
+        }
+    }
+
+    fn strings(&mut self, key: &str) -> Result<Option<Vec<String>>, CheckResult> {
+        let value = self.take(key);
+        let error = || self.invalid(&format!("'{key}' must be an array of strings"));
+        match value {
+            Some(Value::Array(values)) => values
+                .iter()
+                .map(|v| match v {
+                    Value::String(s) => Ok(s.clone()),
+                    _ => Err(error()),
+                })
+                .collect::<Result<_, _>>()
+                .map(Some),
+            Some(_) => Err(error()),
+            None => Ok(None),
+        }
+    }
+
+    fn boolean(&mut self, key: &str) -> Result<Option<bool>, CheckResult> {
+        match self.take(key) {
+            Some(Value::Bool(b)) => Ok(Some(*b)),
+            Some(_) => Err(self.invalid(&format!("'{key}' must be a boolean"))),
+            None => Ok(None),
+        }
+    }
+
+    fn toggle(&mut self, key: &str, on: bool, modifiers: &[&str]) -> Result<bool, CheckResult> {
+        match self.boolean(key)? {
+            Some(value) if value == on => Ok(true),
+            Some(value) => Err(self.invalid(&format!(
+                "'{key} = {value}' has no effect, only '{key} = {on}' is supported"
+            ))),
+            None => match modifiers.iter().find(|m| self.contains(m)) {
+                Some(modifier) => {
+                    Err(self.invalid(&format!("'{modifier}' requires '{key} = {on}'")))
+                }
+                None => Ok(false),
+            },
+        }
+    }
+
+    fn limit(&mut self, key: &str) -> Result<Option<usize>, CheckResult> {
+        match self.take(key) {
+            Some(Value::Integer(n)) if *n >= 0 => Ok(Some(*n as usize)),
+            Some(_) => Err(self.invalid(&format!("'{key}' must be a non-negative integer"))),
+            None => Ok(None),
+        }
+    }
+
+    fn finish(self) -> Result<(), CheckResult> {
+        match self.entries.first() {
+            Some((key, _)) => Err(self.invalid(&format!("unknown key '{key}'"))),
+            None => Ok(()),
+        }
+    }
+}
+
+fn rule_from_table(table: &Table) -> Result<Rule<'static>, CheckResult> {
+    let mut fields = Fields::new(table);
+    let name = fields
+        .string("name")?
+        .ok_or_else(|| fields.invalid("missing 'name'"))?;
+    let warning = match fields.string("severity")?.as_deref() {
+        None | Some("error") => false,
+        Some("warning") => true,
+        Some(other) => return Err(fields.invalid(&format!("unknown severity '{other}'"))),
+    };
+
+    let predicates = selector(&mut fields)?;
+    let mut rule =
+        Rule::new(&name).select(move |item| predicates.iter().all(|predicate| predicate(item)));
+    for section in SECTIONS {
+        rule = section(rule, &mut fields)?;
+    }
+    fields.finish()?;
+    if !rule.has_assertions() {
+        return Err(invalid(
+            &format!("rule at line {}", table.line),
+            "rule has no constraints",
+        ));
+    }
+
+    Ok(match warning {
+        true => rule.as_warning(),
+        false => rule,
+    })
+}
+
+fn selector(fields: &mut Fields<'_>) -> Result<Vec<Predicate>, CheckResult> {
+    let mut predicates: Vec<Predicate> = Vec::new();
+    if let Some(kind) = fields.string("kind")? {
+        predicates.push(Box::new(move |item| item_kind(item) == kind));
+    }
+    if let Some(attribute) = fields.string("attribute")? {
+        predicates.push(Box::new(move |item| has_attr(item, &attribute)));
+    }
+    if let Some(prefix) = fields.string("name_prefix")? {
+        predicates.push(Box::new(move |item| {
+            item_ident(item).is_some_and(|(_, i)| i.to_string().starts_with(&prefix))
+        }));
+    }
+    if let Some(suffix) = fields.string("name_suffix")? {
+        predicates.push(Box::new(move |item| {
+            item_ident(item).is_some_and(|(_, i)| i.to_string().ends_with(&suffix))
+        }));
+    }
+    if fields.contains("reexports_only_in") {
+        predicates.push(Box::new(is_reexport));
+    }
+    Ok(predicates)
+}
+
+fn module(rule: Rule<'static>, fields: &mut Fields<'_>) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.string("module")? {
+        Some(module) => rule.in_module(&module),
+        None => rule,
+    })
+}
+
+fn fn_spec(rule: Rule<'static>, fields: &mut Fields<'_>) -> Result<Rule<'static>, CheckResult> {
+    let mut spec = None::<FnSpec>;
+    if let Some(vis) = fields.string("vis")? {
+        let vis = syn::parse_str::<Visibility>(&vis).map_err(|e| fields.invalid(&e.to_string()))?;
+        spec = Some(spec.unwrap_or_default().with_vis(vis));
+    }
+    if let Some(return_type) = fields.string("return_type")? {
+        spec = Some(spec.unwrap_or_default().with_return_type(&return_type));
+    }
+    if let Some(signature) = fields.string("signature")? {
+        spec = Some(spec.unwrap_or_default().with_signature(&signature));
+    }
+    if let Some(attrs) = fields.strings("attrs")? {
+        spec = Some(spec.unwrap_or_default().with_attrs(attrs));
+    }
+    if let Some(receiver) = fields.string("receiver")? {
+        spec = Some(spec.unwrap_or_default().with_receiver(&receiver));
+    }
+    if let Some(max) = fields.limit("max_complexity")? {
+        spec = Some(spec.unwrap_or_default().with_max_complexity(max));
+    }
+    Ok(match spec {
+        Some(spec) => rule.must(move |item| match item {
+            Item::Impl(impl_) => CheckResult::any(impl_.items.iter().map(|i| match i {
+                ImplItem::Method(method) => spec.check(method),
+                _ => CheckResult::Success,
+            })),
+            item => spec.check(item),
+        }),
+        None => rule,
+    })
+}
+
+fn asyncness(rule: Rule<'static>, fields: &mut Fields<'_>) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.boolean("async")? {
+        Some(asyncness) => rule.must(move |item| match item {
+            Item::Fn(f) => {
+                CheckResult::compare_named("async", asyncness, f.sig.asyncness.is_some())
+            }
+            _ => CheckResult::missing("fn"),
+        }),
+        None => rule,
+    })
+}
+
+fn async_receiver_rule(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.string("async_receiver")? {
+        Some(receiver) => rule.must(move |item| async_receiver(item, &receiver)),
+        None => rule,
+    })
+}
+
+fn error_impls_rule(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.toggle("error_impls", true, &[])? {
+        true => rule.must_crate(error_impls),
+        false => rule,
+    })
+}
+
+fn from_impls_rule(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.string("from_impls")? {
+        Some(error) => rule.must_crate(move |ast, items| from_impls(ast, items, &error)),
+        None => rule,
+    })
+}
+
+fn accessors_rule(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.string("accessors")? {
+        Some(pattern) => rule.must_crate(move |ast, items| accessors(ast, items, &pattern)),
+        None => rule,
+    })
+}
+
+fn instrument(rule: Rule<'static>, fields: &mut Fields<'_>) -> Result<Rule<'static>, CheckResult> {
+    if !fields.toggle("instrument", true, &["instrument_args"])? {
+        return Ok(rule);
+    }
+    let args = fields.strings("instrument_args")?.unwrap_or_default();
+    Ok(rule.must(move |item| instrumented(item, "tracing::instrument", &args)))
+}
+
+fn object_safety(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.toggle("object_safe", true, &[])? {
+        true => rule.must(object_safe),
+        false => rule,
+    })
+}
+
+fn async_fns(rule: Rule<'static>, fields: &mut Fields<'_>) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.toggle("async_fns", false, &[])? {
+        true => rule.must(no_async),
+        false => rule,
+    })
+}
+
+fn reexports_only_in(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.strings("reexports_only_in")? {
+        Some(modules) => modules
+            .iter()
+            .fold(rule, |rule, m| rule.except_in(m))
+            .must(reexports),
+        None => rule,
+    })
+}
+
+fn dependencies(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.strings("must_not_depend_on")? {
+        Some(dependencies) if !dependencies.is_empty() => rule.must_crate_item(move |item| {
+            CheckResult::any(dependencies.iter().map(|d| no_dependency(item, d)))
+        }),
+        _ => rule,
+    })
+}
+
+fn docs(rule: Rule<'static>, fields: &mut Fields<'_>) -> Result<Rule<'static>, CheckResult> {
+    let exempt_attributes = fields.strings("exempt_attributes")?;
+    let exempt_paths = fields.strings("exempt_paths")?;
+    let policy = match fields.string("require_docs")?.as_deref() {
+        None => match (&exempt_attributes, &exempt_paths) {
+            (Some(_), _) => {
+                return Err(fields.invalid("'exempt_attributes' requires 'require_docs'"))
+            }
+            (_, Some(_)) => return Err(fields.invalid("'exempt_paths' requires 'require_docs'")),
+            (None, None) => return Ok(rule),
+        },
+        Some("items") => DocPolicy::new(),
+        Some("members") => DocPolicy::new().with_members(),
+        Some(other) => {
+            return Err(fields.invalid(&format!("unknown doc requirement '{other}'")));
+        }
+    };
+    let policy = exempt_attributes
+        .unwrap_or_default()
+        .iter()
+        .fold(policy, |policy, a| policy.exempt_attr(a));
+    let policy = exempt_paths
+        .unwrap_or_default()
+        .iter()
+        .fold(policy, |policy, p| policy.exempt_path(p));
+    Ok(rule.must_crate_item(move |item| policy.check(item)))
+}
+
+fn crate_docs(rule: Rule<'static>, fields: &mut Fields<'_>) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.strings("crate_docs")? {
+        Some(sections) => rule.must_crate(move |ast, _| {
+            ast.has_crate_docs(&sections.iter().map(String::as_str).collect::<Vec<_>>())
+        }),
+        None => rule,
+    })
+}
+
+fn feature_gates(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.toggle("feature_gates", false, &[])? {
+        true => rule.must_crate(|ast, _| no_feature_gates(ast)),
+        false => rule,
+    })
+}
+
+fn forbidden_vis(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.string("forbid_vis")? {
+        Some(vis) => {
+            let vis = vis.split_whitespace().collect::<String>();
+            rule.must(move |item| forbid_vis(item, &vis))
+        }
+        None => rule,
+    })
+}
+
+fn private(rule: Rule<'static>, fields: &mut Fields<'_>) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.toggle("private", true, &[])? {
+        true => rule.must(require_private),
+        false => rule,
+    })
+}
+
+fn static_mut(rule: Rule<'static>, fields: &mut Fields<'_>) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.toggle("static_mut", false, &[])? {
+        true => rule.must(no_static_mut),
+        false => rule,
+    })
+}
+
+fn unsafe_code(rule: Rule<'static>, fields: &mut Fields<'_>) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.toggle("unsafe_code", false, &[])? {
+        true => rule
+            .must(no_unsafe)
+            .must_crate(|ast, _| ast.forbids_unsafe()),
+        false => rule,
+    })
+}
+
+fn thin_main_rule(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    let max_statements = fields.limit("max_statements")?;
+    Ok(match (fields.string("thin_main")?, max_statements) {
+        (Some(lib), max_statements) => {
+            let max_statements = max_statements.unwrap_or(usize::MAX);
+            rule.must_crate(move |ast, _| thin_main(ast, &lib, max_statements))
+        }
+        (None, Some(_)) => return Err(fields.invalid("'max_statements' requires 'thin_main'")),
+        (None, None) => rule,
+    })
+}
+
+fn test_pattern(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.string("test_pattern")? {
+        Some(pattern) => rule.must_crate(move |ast, items| test_coverage(ast, items, &pattern)),
+        None => rule,
+    })
+}
+
+fn allow_attrs(rule: Rule<'static>, fields: &mut Fields<'_>) -> Result<Rule<'static>, CheckResult> {
+    if !fields.toggle("allow_attrs", false, &["allowed_lints"])? {
+        return Ok(rule);
+    }
+    let allowed_lints = fields.strings("allowed_lints")?.unwrap_or_default();
+    Ok(rule.must_crate_item(move |item| no_allow(item, &allowed_lints)))
+}
+
+fn naming_conventions(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    if !fields.toggle("naming_conventions", true, &["exempt_names"])? {
+        return Ok(rule);
+    }
+    let exempt_names = fields.strings("exempt_names")?.unwrap_or_default();
+    Ok(rule.must(move |item| naming(item, &exempt_names)))
+}
+
+fn extern_crates(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    if !fields.toggle("extern_crates", false, &["allow_crates"])? {
+        return Ok(rule);
+    }
+    let allow_crates = fields.strings("allow_crates")?.unwrap_or_default();
+    Ok(rule.must(move |item| no_extern_crate(item, &allow_crates)))
+}
+
+fn max_items_rule(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.limit("max_items")? {
+        Some(max) => rule.must_crate(move |_, items| max_items(items, max)),
+        None => rule,
+    })
+}
+
+fn max_impl_fns_rule(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    Ok(match fields.limit("max_impl_fns")? {
+        Some(max) => rule.must(move |item| max_impl_fns(item, max)),
+        None => rule,
+    })
+}
+
+fn impl_trait(rule: Rule<'static>, fields: &mut Fields<'_>) -> Result<Rule<'static>, CheckResult> {
+    let required = match fields.string("impl_trait_returns")?.as_deref() {
+        None => return Ok(rule),
+        Some("required") => true,
+        Some("forbidden") => false,
+        Some(_) => {
+            return Err(fields.invalid("'impl_trait_returns' must be 'required' or 'forbidden'"));
+        }
+    };
+    Ok(rule.must(move |item| impl_trait_returns(item, required)))
+}
+
+fn trait_objects(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    if !fields.toggle("trait_objects", false, &["allow_types"])? {
+        return Ok(rule);
+    }
+    let allow_types = fields
+        .strings("allow_types")?
+        .unwrap_or_default()
+        .iter()
+        .map(|t| parse_str::<Type>("type", t))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rule.must(move |item| no_pub_trait_objects(item, &allow_types)))
+}
+
+fn panics(rule: Rule<'static>, fields: &mut Fields<'_>) -> Result<Rule<'static>, CheckResult> {
+    if !fields.toggle("panics", false, &["allow_panic_attr"])? {
+        return Ok(rule);
+    }
+    let allow_panic_attr = fields
+        .string("allow_panic_attr")?
+        .unwrap_or_else(|| String::from("allow_panic"));
+    Ok(rule.must_crate(move |ast, items| panic_free(ast, items, &allow_panic_attr)))
+}
+
+fn fallible_rule(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    if !fields.toggle("fallible", true, &["exempt_fns"])? {
+        return Ok(rule);
+    }
+    let exempt_fns = fields.strings("exempt_fns")?.unwrap_or_default();
+    Ok(rule.must(move |item| fallible(item, &exempt_fns)))
+}
+
+fn glob_imports(
+    rule: Rule<'static>,
+    fields: &mut Fields<'_>,
+) -> Result<Rule<'static>, CheckResult> {
+    if !fields.toggle("glob_imports", false, &["allow_globs"])? {
+        return Ok(rule);
+    }
+    let allow_globs = fields.strings("allow_globs")?.unwrap_or_default();
+    Ok(rule.must(move |item| no_glob_imports(item, &allow_globs)))
+}
+
+fn invalid(subject: &str, error: &str) -> CheckResult {
+    CheckResult::Failure(vec![Failure::invalid(subject, error)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crate_ast::CrateAst;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_from_toml() -> Result<(), TestError> {
+        let dir = TestDir::new(&[
+            (
+                "src/lib.rs",
+                r#"
+extern crate alloc;
+extern crate proc_macro;
+
+mod api {
+    #[handler]
+    pub async fn list() -> Response { todo!() }
+
+    #[handler]
+    fn create() -> Response { todo!() }
+}
+
+#[allow(dead_code)]
+fn get_internal() {}
+
+use crate::prelude::*;
+use std::collections::*;
+pub use api::list;
+mod reexports { pub use super::api::create; }
+"#,
+            ),
+            (
+                "rules.toml",
+                r#"
+# Handlers
+[[rule]]
+name = "handlers are pub async fns"
+module = "crate::api"
+kind = "fn"
+attribute = "handler"
+vis = "pub"
+async = true
+return_type = "Response"
+
+[[rule]]
+name = "no glob imports"
+kind = "use"
+glob_imports = false
+allow_globs = ["prelude"]
+
+[[rule]]
+name = "no extern crates"
+extern_crates = false
+allow_crates = ["proc_macro"]
+
+[[rule]]
+name = "re-exports only from the root"
+reexports_only_in = ["crate"]
+
+[[rule]]
+name = "api does not use reexports"
+module = "crate::api"
+must_not_depend_on = ["crate::reexports"]
+
+[[rule]]
+name = "naming conventions"
+naming_conventions = true
+exempt_names = ["get_internal"]
+
+[[rule]]
+name = "public api is documented"
+require_docs = "items"
+exempt_paths = ["crate::api"]
+
+[[rule]]
+name = "no allow attributes"
+allow_attrs = false
+allowed_lints = ["dead_code"]
+
+[[rule]]
+name = "getters are tested"
+name_prefix = "get_"
+test_pattern = "test_{name}"
+
+[[rule]]
+name = "no static mut"
+static_mut = false
+
+[[rule]]
+name = "api is fallible"
+module = "crate::api"
+fallible = true
+exempt_fns = ["new"]
+
+[[rule]]
+name = "no pub(super)"
+forbid_vis = "pub(super)"
+
+[[rule]]
+name = "reexports are private"
+module = "crate::reexports"
+private = true
+
+[[rule]]
+name = "api is instrumented"
+module = "crate::api"
+instrument = true
+instrument_args = ["skip(req)"]
+
+[[rule]]
+name = "api never panics"
+module = "crate::api"
+panics = false
+
+[[rule]]
+name = "getters are public"
+severity = "warning"
+name_prefix = "get_"
+vis = "pub"
+"#,
+            ),
+        ])?;
+        let ast = CrateAst::load(&dir)?;
+        let rules = Rules::load(dir.join("rules.toml"))?;
+
+        let report = rules.check(&ast);
+        let results = report.results().collect::<Vec<_>>();
+        assert_eq!(results[0].0, "handlers are pub async fns");
+        assert_eq!(results[0].1.failure_count(), 2);
+        assert_eq!(
+            results[1].1.failures()[0].message(),
+            "Unexpected glob import 'std::collections::*'"
+        );
+        assert_eq!(
+            results[2].1.failures()[0].message(),
+            "Unexpected extern crate 'alloc'"
+        );
+        assert_eq!(results[3].1.failures()[0].path, vec!["crate::reexports"]);
+        assert_eq!(results[3].1.failure_count(), 1);
+        assert!(results[4].1.as_bool());
+        assert!(results[5].1.as_bool());
+        assert!(results[6].1.as_bool());
+        assert!(results[7].1.as_bool());
+        assert!(results[8].1.as_bool());
+        assert!(results[9].1.as_bool());
+        assert_eq!(
+            results[10].1.failures()[0].message(),
+            "Expected return type of fn 'list' to be 'Result<_, _>', got 'Response'"
+        );
+        assert!(results[11].1.as_bool());
+        assert_eq!(
+            results[12].1.failures()[0].message(),
+            "Unexpected pub use 'super :: api :: create'"
+        );
+        assert_eq!(
+            results[13].1.failures()[0].message(),
+            "Missing attribute 'tracing::instrument'"
+        );
+        assert_eq!(
+            results[13].1.failures()[0].path,
+            vec!["crate::api", "fn list"]
+        );
+        assert_eq!(results[14].1.failure_count(), 2);
+        assert_eq!(results[15].1.warnings().count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_complexity() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"
+pub fn simple() {}
+
+fn branchy(x: u32) -> u32 {
+    if x > 1 { if x > 2 { 2 } else { 1 } } else { 0 }
+}
+
+struct Store;
+
+impl Store {
+    pub fn get(&self, x: u32) -> u32 {
+        match x { 0 => 0, 1 => 1, _ => 2 }
+    }
+}
+"#,
+        )])?;
+        let rules = Rules::from_toml(
+            r#"
+[[rule]]
+name = "fns are simple"
+kind = "fn"
+max_complexity = 2
+
+[[rule]]
+name = "methods are simple"
+kind = "impl"
+max_complexity = 2
+
+[[rule]]
+name = "public fns are simple"
+kind = "fn"
+name_prefix = "simple"
+vis = "pub"
+max_complexity = 1
+"#,
+        )
+        .map_err(|e| e.to_string())?;
+
+        let report = rules.check(&CrateAst::load(&dir)?);
+        let results = report.results().collect::<Vec<_>>();
+        assert_eq!(
+            results[0].1.failures()[0].message(),
+            "Expected complexity to be 'at most 2', got '3'"
+        );
+        assert_eq!(results[0].1.failures()[0].path, vec!["crate", "fn branchy"]);
+        assert_eq!(results[1].1.failures()[0].path, vec!["crate", "fn get"]);
+        assert!(results[2].1.as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_toml_invalid() {
+        let results = Rules::from_toml("[[rule]]\nname = \"x\"\nunknown = 1\n").err();
+        assert_eq!(
+            results.unwrap().into_failures()[0].message(),
+            "Invalid rule at line 1: unknown key 'unknown'"
+        );
+        assert!(Rules::from_toml("[[rule]]\nkind = \"fn\"\n").is_err());
+        assert_eq!(
+            Rules::load("rules.yaml")
+                .err()
+                .map(|e| e.into_failures()[0].message()),
+            Some(
+                "Invalid rule file 'rules.yaml': YAML rule files are not supported, use TOML"
+                    .to_string()
+            )
+        );
+        assert!(Rules::from_toml(
+            "[[rule]]\nname = \"x\"\nthin_main = \"app\"\nmax_statements = -1\n"
+        )
+        .is_err());
+        assert!(Rules::from_toml(
+            "[[rule]]\nname = \"x\"\nthin_main = \"app\"\nmax_statements = 3\n"
+        )
+        .is_ok());
+        assert_eq!(
+            Rules::from_toml("[[rule]]\nname = \"x\"\nmax_statements = 3\n")
+                .err()
+                .map(|e| e.into_failures()[0].message()),
+            Some("Invalid rule at line 1: 'max_statements' requires 'thin_main'".to_string())
+        );
+        assert!(Rules::from_toml("[[rule]]\nname = \"x\"\nmax_items = 0\n").is_ok());
+        assert!(
+            Rules::from_toml("[[rule]]\nname = \"x\"\nimpl_trait_returns = \"forbidden\"\n")
+                .is_ok()
+        );
+        assert!(
+            Rules::from_toml("[[rule]]\nname = \"x\"\nimpl_trait_returns = \"sometimes\"\n")
+                .is_err()
+        );
+        assert!(Rules::from_toml(
+            "[[rule]]\nname = \"x\"\ntrait_objects = false\nallow_types = [\"Box<dyn Error>\"]\n"
+        )
+        .is_ok());
+        assert!(Rules::from_toml(
+            "[[rule]]\nname = \"x\"\ntrait_objects = false\nallow_types = [\"Box<\"]\n"
+        )
+        .is_err());
+        assert_eq!(
+            Rules::from_toml("[[rule]]\nname = \"x\"\nmax_items = \"3\"\n")
+                .err()
+                .map(|e| e.into_failures()[0].message()),
+            Some("Invalid rule at line 1: 'max_items' must be a non-negative integer".to_string())
+        );
+    }
+
+    #[test]
+    fn test_from_toml_modifiers() {
+        let message = |source: &str| {
+            Rules::from_toml(source)
+                .err()
+                .map(|e| e.into_failures()[0].message())
+        };
+
+        assert_eq!(
+            message("[[rule]]\nname = \"x\"\nallow_crates = [\"alloc\"]\n"),
+            Some(
+                "Invalid rule at line 1: 'allow_crates' requires 'extern_crates = false'"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            message("[[rule]]\nname = \"x\"\nexempt_names = [\"get\"]\n"),
+            Some(
+                "Invalid rule at line 1: 'exempt_names' requires 'naming_conventions = true'"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            message("[[rule]]\nname = \"x\"\nextern_crates = true\n"),
+            Some(
+                "Invalid rule at line 1: 'extern_crates = true' has no effect, only 'extern_crates = false' is supported"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            message("[[rule]]\nname = \"x\"\nexempt_paths = [\"crate::tests\"]\n"),
+            Some("Invalid rule at line 1: 'exempt_paths' requires 'require_docs'".to_string())
+        );
+        assert_eq!(
+            message("[[rule]]\nname = \"x\"\nkind = \"fn\"\nmodule = \"api\"\n"),
+            Some("Invalid rule at line 1: rule has no constraints".to_string())
+        );
+        assert_eq!(
+            message("[rule]\nname = \"x\"\n"),
+            Some(
+                "Invalid table at line 1: unknown table [rule], rules are declared with [[rule]]"
+                    .to_string()
+            )
+        );
+        assert!(Rules::from_toml(
+            "[[rule]]\nname = 'x'\nkind = \"fn\"\nattrs = [\n  \"inline\",\n  \"must_use\",\n]\n"
+        )
+        .is_ok());
+    }
+}
@@ -0,0 +1,702 @@
+use crate::crate_ast::{CrateAst, CrateItem};
+use crate::failure::{Failure, Location};
+use crate::function::FnSpec;
+use crate::imports::{normalize_path, resolve, Import};
+use crate::item_trait::HasTrait;
+use crate::normalize::with_crate_name;
+use crate::report::Report;
+use crate::utils::{attr_paths, CheckResult};
+use quote::ToTokens;
+use syn::visit::{self, Visit};
+use syn::{Attribute, Item, ItemMod, Path, Visibility};
+
+pub type ItemSelector<'s> = Box<dyn Fn(&Item) -> bool + 's>;
+pub type ItemAssertion<'s> = Box<dyn Fn(&Item) -> CheckResult + 's>;
+pub type CrateItemAssertion<'s> = Box<dyn Fn(&CrateItem<'_>) -> CheckResult + 's>;
+pub type CrateAssertion<'s> = Box<dyn Fn(&CrateAst, &[CrateItem<'_>]) -> CheckResult + 's>;
+
+pub struct Rule<'s> {
+    name: String,
+    modules: Vec<String>,
+    excluded: Vec<String>,
+    selector: ItemSelector<'s>,
+    assertions: Vec<ItemAssertion<'s>>,
+    item_assertions: Vec<CrateItemAssertion<'s>>,
+    crate_assertions: Vec<CrateAssertion<'s>>,
+    warning: bool,
+}
+
+impl<'s> Rule<'s> {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            modules: Default::default(),
+            excluded: Default::default(),
+            selector: Box::new(|_| true),
+            assertions: Default::default(),
+            item_assertions: Default::default(),
+            crate_assertions: Default::default(),
+            warning: false,
+        }
+    }
+
+    pub fn no_glob_imports<I, S>(allow: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let allow = allow
+            .into_iter()
+            .map(|a| a.as_ref().to_string())
+            .collect::<Vec<_>>();
+        Self::new("no glob imports")
+            .select(|item| matches!(item, Item::Use(_)))
+            .must(move |item| no_glob_imports(item, &allow))
+    }
+
+    pub fn no_extern_crates<I, S>(allow: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let allow = allow
+            .into_iter()
+            .map(|a| a.as_ref().to_string())
+            .collect::<Vec<_>>();
+        Self::new("no extern crates")
+            .select(|item| matches!(item, Item::ExternCrate(_)))
+            .must(move |item| no_extern_crate(item, &allow))
+    }
+
+    pub fn object_safe_traits() -> Self {
+        Self::new("traits are object safe")
+            .select(|item| matches!(item, Item::Trait(_)))
+            .must(object_safe)
+    }
+
+    pub fn reexports_only_in<I, S>(modules: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        modules
+            .into_iter()
+            .fold(Self::new("re-export policy"), |rule, m| {
+                rule.except_in(m.as_ref())
+            })
+            .select(is_reexport)
+            .must(reexports)
+    }
+
+    pub fn must_not_depend_on(module: &str, dependency: &str) -> Self {
+        let dependency = normalize_path(dependency);
+        Self::new(&format!("{module} must not depend on {dependency}"))
+            .in_module(module)
+            .must_crate_item(move |item| no_dependency(item, &dependency))
+    }
+
+    pub fn crate_docs<I, S>(sections: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let sections = sections
+            .into_iter()
+            .map(|s| s.as_ref().to_string())
+            .collect::<Vec<_>>();
+        Self::new("crate is documented").must_crate(move |ast, _| {
+            ast.has_crate_docs(&sections.iter().map(String::as_str).collect::<Vec<_>>())
+        })
+    }
+
+    pub fn select<F>(self, selector: F) -> Self
+    where
+        F: Fn(&Item) -> bool + 's,
+    {
+        let previous = self.selector;
+        Self {
+            selector: Box::new(move |item| previous(item) && selector(item)),
+            ..self
+        }
+    }
+
+    pub fn in_module(mut self, module: &str) -> Self {
+        self.modules.push(module.to_string());
+        self
+    }
+
+    pub fn except_in(mut self, module: &str) -> Self {
+        self.excluded.push(module.to_string());
+        self
+    }
+
+    pub fn with_attr(self, path: &str) -> Self {
+        let path = path.to_string();
+        self.select(move |item| has_attr(item, &path))
+    }
+
+    pub fn must<F>(mut self, assertion: F) -> Self
+    where
+        F: Fn(&Item) -> CheckResult + 's,
+    {
+        self.assertions.push(Box::new(assertion));
+        self
+    }
+
+    pub fn must_crate_item<F>(mut self, assertion: F) -> Self
+    where
+        F: Fn(&CrateItem<'_>) -> CheckResult + 's,
+    {
+        self.item_assertions.push(Box::new(assertion));
+        self
+    }
+
+    pub fn must_crate<F>(mut self, assertion: F) -> Self
+    where
+        F: Fn(&CrateAst, &[CrateItem<'_>]) -> CheckResult + 's,
+    {
+        self.crate_assertions.push(Box::new(assertion));
+        self
+    }
+
+    pub fn must_match(self, spec: FnSpec) -> Self {
+        self.must(move |item| spec.check(item))
+    }
+
+    pub(crate) fn has_assertions(&self) -> bool {
+        !self.assertions.is_empty()
+            || !self.item_assertions.is_empty()
+            || !self.crate_assertions.is_empty()
+    }
+
+    pub fn as_warning(self) -> Self {
+        Self {
+            warning: true,
+            ..self
+        }
+    }
+
+    pub fn check(&self, ast: &CrateAst) -> CheckResult {
+        let items = ast
+            .items()
+            .into_iter()
+            .filter(|i| self.modules.iter().all(|m| in_module(&i.module, m)))
+            .filter(|i| !self.excluded.contains(&i.module))
+            .filter(|i| (self.selector)(i.item))
+            .collect::<Vec<_>>();
+        CheckResult::any(
+            items
+                .iter()
+                .map(|i| self.check_item(i))
+                .chain(self.crate_assertions.iter().map(|a| a(ast, &items))),
+        )
+    }
+
+    fn check_item(&self, item: &CrateItem<'_>) -> CheckResult {
+        item.scope(CheckResult::any(
+            self.assertions
+                .iter()
+                .map(|a| a(item.item))
+                .chain(self.item_assertions.iter().map(|a| a(item))),
+        ))
+    }
+}
+
+#[derive(Default)]
+pub struct Rules<'s> {
+    rules: Vec<Rule<'s>>,
+    crate_name: Option<String>,
+}
+
+impl<'s> Rules<'s> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_rule(mut self, rule: Rule<'s>) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    pub fn with_crate_name(self, crate_name: &str) -> Self {
+        Self {
+            crate_name: Some(crate_name.to_string()),
+            ..self
+        }
+    }
+
+    pub fn check(&self, ast: &CrateAst) -> Report {
+        match &self.crate_name {
+            Some(crate_name) => with_crate_name(crate_name, || self.check_rules(ast)),
+            None => self.check_rules(ast),
+        }
+    }
+
+    fn check_rules(&self, ast: &CrateAst) -> Report {
+        let mut report = Report::new();
+        for rule in &self.rules {
+            if rule.warning {
+                report.warn(&rule.name, rule.check(ast));
+            } else {
+                report.check(&rule.name, rule.check(ast));
+            }
+        }
+        report
+    }
+}
+
+pub(crate) fn no_glob_imports(item: &Item, allow: &[String]) -> CheckResult {
+    let imports = match item {
+        Item::Use(item_use) => Import::of(item_use),
+        _ => return CheckResult::Success,
+    };
+    CheckResult::any(
+        imports
+            .into_iter()
+            .filter(|i| i.glob && !allow.iter().any(|a| glob_allowed(&i.path, a)))
+            .map(|i| {
+                CheckResult::Failure(vec![Failure::unexpected(&format!("glob import '{i}'"))])
+            }),
+    )
+}
+
+pub(crate) fn object_safe(item: &Item) -> CheckResult {
+    item.has_object_safety(&true)
+        .in_item(HasTrait::breadcrumb(item), None)
+}
+
+pub(crate) fn no_extern_crate(item: &Item, allow: &[String]) -> CheckResult {
+    match item {
+        Item::ExternCrate(krate) if !allow.iter().any(|a| krate.ident == a) => {
+            CheckResult::Failure(vec![Failure::unexpected(&format!(
+                "extern crate '{}'",
+                krate.ident
+            ))
+            .at(Some(Location::of(krate)))])
+        }
+        _ => CheckResult::Success,
+    }
+}
+
+pub(crate) fn no_dependency(item: &CrateItem<'_>, dependency: &str) -> CheckResult {
+    let mut paths = Vec::new();
+    match item.item {
+        Item::Use(item_use) => paths.extend(
+            Import::of(item_use)
+                .into_iter()
+                .map(|i| (i.path, Location::of(item_use))),
+        ),
+        Item::Mod(_) => (),
+        item => PathVisitor(&mut paths).visit_item(item),
+    }
+
+    let failures = paths
+        .into_iter()
+        .map(|(path, location)| (resolve(&path, &item.module), location))
+        .filter(|(path, _)| in_module(path, dependency))
+        .map(|(path, location)| {
+            Failure::unexpected(&format!("dependency on '{path}'")).at(Some(location))
+        })
+        .collect::<Vec<_>>();
+    failures.into()
+}
+
+struct PathVisitor<'a>(&'a mut Vec<(String, Location)>);
+
+impl<'ast> Visit<'ast> for PathVisitor<'_> {
+    fn visit_item_mod(&mut self, _: &'ast ItemMod) {}
+
+    fn visit_path(&mut self, path: &'ast Path) {
+        let rendered = path
+            .segments
+            .iter()
+            .map(|s| s.ident.to_token_stream().to_string())
+            .collect::<Vec<_>>()
+            .join("::");
+        self.0.push((rendered, Location::of(path)));
+        visit::visit_path(self, path);
+    }
+}
+
+pub(crate) fn is_reexport(item: &Item) -> bool {
+    matches!(item, Item::Use(item_use) if matches!(item_use.vis, Visibility::Public(_)))
+}
+
+pub(crate) fn reexports(item: &Item) -> CheckResult {
+    match item {
+        Item::Use(item_use) if is_reexport(item) => {
+            CheckResult::any(Import::of(item_use).into_iter().map(|i| {
+                CheckResult::Failure(vec![Failure::unexpected(&format!("re-export '{i}'"))])
+            }))
+        }
+        _ => CheckResult::Success,
+    }
+}
+
+fn glob_allowed(path: &str, allow: &str) -> bool {
+    path == allow
+        || path
+            .strip_suffix(allow)
+            .is_some_and(|rest| rest.ends_with("::"))
+}
+
+fn in_module(module: &str, prefix: &str) -> bool {
+    module == prefix
+        || module
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with("::"))
+}
+
+pub(crate) fn item_kind(item: &Item) -> &'static str {
+    match item {
+        Item::Const(_) => "const",
+        Item::Enum(_) => "enum",
+        Item::ExternCrate(_) => "extern crate",
+        Item::Fn(_) => "fn",
+        Item::ForeignMod(_) => "extern",
+        Item::Impl(_) => "impl",
+        Item::Macro(_) | Item::Macro2(_) => "macro",
+        Item::Mod(_) => "mod",
+        Item::Static(_) => "static",
+        Item::Struct(_) => "struct",
+        Item::Trait(_) => "trait",
+        Item::TraitAlias(_) => "trait alias",
+        Item::Type(_) => "type",
+        Item::Union(_) => "union",
+        Item::Use(_) => "use",
+        _ => "item",
+    }
+}
+
+pub(crate) fn has_attr(item: &Item, path: &str) -> bool {
+    item_attrs(item).is_some_and(|attrs| attr_paths(attrs).contains(path))
+}
+
+pub(crate) fn item_attrs(item: &Item) -> Option<&[Attribute]> {
+    Some(match item {
+        Item::Const(i) => &i.attrs,
+        Item::Enum(i) => &i.attrs,
+        Item::ExternCrate(i) => &i.attrs,
+        Item::Fn(i) => &i.attrs,
+        Item::ForeignMod(i) => &i.attrs,
+        Item::Impl(i) => &i.attrs,
+        Item::Macro(i) => &i.attrs,
+        Item::Macro2(i) => &i.attrs,
+        Item::Mod(i) => &i.attrs,
+        Item::Static(i) => &i.attrs,
+        Item::Struct(i) => &i.attrs,
+        Item::Trait(i) => &i.attrs,
+        Item::TraitAlias(i) => &i.attrs,
+        Item::Type(i) => &i.attrs,
+        Item::Union(i) => &i.attrs,
+        Item::Use(i) => &i.attrs,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::item_ident;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_rules() -> Result<(), TestError> {
+        let dir = TestDir::new(&[
+            ("src/lib.rs", "mod handlers;\n"),
+            (
+                "src/handlers.rs",
+                r#"
+#[handler]
+pub async fn list() -> Response { todo!() }
+
+#[handler]
+async fn create() -> Response { todo!() }
+
+fn helper() -> u32 { 1 }
+"#,
+            ),
+        ])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let report = Rules::new()
+            .with_rule(
+                Rule::new("handlers are pub async fns returning Response")
+                    .with_attr("handler")
+                    .must_match(FnSpec::new().with_vis(syn::parse_quote!(pub)))
+                    .must(|item| match item {
+                        Item::Fn(f) if f.sig.asyncness.is_some() => CheckResult::Success,
+                        _ => CheckResult::missing("async"),
+                    })
+                    .must_match(FnSpec::new().with_return_type("Response")),
+            )
+            .with_rule(
+                Rule::new("helpers return Response")
+                    .select(|item| matches!(item, Item::Fn(f) if f.attrs.is_empty()))
+                    .must_match(FnSpec::new().with_return_type("Response"))
+                    .as_warning(),
+            )
+            .check(&ast);
+
+        assert!(!report.as_bool());
+        let results = report.results().collect::<Vec<_>>();
+        let failures = results[0].1.failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].path, vec!["crate::handlers", "fn create"]);
+        let location = failures[0].location.clone().unwrap();
+        assert_eq!(location.line, 5);
+        assert!(location.file.unwrap().ends_with("handlers.rs"));
+        assert!(results[1].1.as_bool());
+        assert_eq!(results[1].1.warnings().count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_selectors_compose() -> Result<(), TestError> {
+        let dir = TestDir::new(&[
+            ("src/lib.rs", "mod api;\n#[handler]\nfn root() {}\n"),
+            (
+                "src/api.rs",
+                "mod v1;\n#[handler]\nfn list() {}\nfn helper() {}\n#[handler]\nstruct Marker;\n",
+            ),
+            ("src/api/v1.rs", "#[handler]\nfn legacy() {}\n"),
+        ])?;
+        let ast = CrateAst::load(&dir)?;
+        let selected = |rule: Rule<'_>| {
+            rule.must(|item| match item_ident(item) {
+                Some((_, ident)) => CheckResult::missing(&ident.to_string()),
+                None => CheckResult::Success,
+            })
+            .check(&ast)
+            .into_failures()
+            .iter()
+            .map(|f| format!("{}: {}", f.path.join(" > "), f.message()))
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            selected(
+                Rule::new("handlers")
+                    .with_attr("handler")
+                    .select(|item| matches!(item, Item::Fn(_)))
+                    .in_module("crate::api")
+            ),
+            vec!["crate::api: Missing list", "crate::api::v1: Missing legacy"]
+        );
+        assert_eq!(
+            selected(
+                Rule::new("nested")
+                    .in_module("crate::api")
+                    .in_module("crate::api::v1")
+                    .with_attr("handler")
+            ),
+            vec!["crate::api::v1: Missing legacy"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rules_crate_name() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            "pub fn load() -> my_crate::Model { todo!() }\n",
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let rules = || {
+            Rules::new().with_rule(
+                Rule::new("loaders return models")
+                    .must_match(FnSpec::new().with_return_type("crate::Model")),
+            )
+        };
+        assert!(!rules().check(&ast).as_bool());
+        assert!(rules().with_crate_name("my-crate").check(&ast).as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_glob_imports() -> Result<(), TestError> {
+        let dir = TestDir::new(&[
+            ("src/lib.rs", "use std::io::prelude::*;\nuse crate::prelude::*;\nmod api {\n    use super::{models::*, Context};\n}\n"),
+        ])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let results = Rule::no_glob_imports(["prelude"]).check(&ast);
+        let failures = results.into_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(
+            failures[0].message(),
+            "Unexpected glob import 'super::models::*'"
+        );
+        assert_eq!(failures[0].path, vec!["crate::api"]);
+        assert_eq!(failures[0].location.as_ref().map(|l| l.line), Some(4));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_extern_crates() -> Result<(), TestError> {
+        let dir = TestDir::new(&[
+            ("src/lib.rs", "extern crate proc_macro;\nextern crate serde as json;\nmod inner {\n    extern crate alloc;\n}\n"),
+        ])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let failures = Rule::no_extern_crates(["proc_macro"])
+            .check(&ast)
+            .into_failures();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Unexpected extern crate 'serde'".to_string(), Some(2)),
+                ("Unexpected extern crate 'alloc'".to_string(), Some(4)),
+            ]
+        );
+        assert_eq!(failures[1].path, vec!["crate::inner"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_object_safe_traits() -> Result<(), TestError> {
+        let dir = TestDir::new(&[
+            ("src/lib.rs", "pub trait Handler { fn handle(&self); }\nmod store {\n    pub trait Store { fn open() -> Self; }\n}\n"),
+        ])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let failures = Rule::object_safe_traits().check(&ast).into_failures();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(
+            failures[0].message(),
+            "Unexpected method 'open' without receiver"
+        );
+        assert_eq!(failures[0].path, vec!["crate::store", "trait Store"]);
+        assert_eq!(failures[0].location.as_ref().map(|l| l.line), Some(3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reexports_only_in() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"
+pub use api::Client;
+pub mod prelude { pub use crate::api::{Client, Error}; }
+mod api {
+    pub(crate) use crate::util::Helper;
+    pub use crate::util::{Helper as Exported, Other};
+    pub mod nested { pub use super::Client; }
+}
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let failures = Rule::reexports_only_in(["crate", "crate::prelude"])
+            .check(&ast)
+            .into_failures();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "Unexpected re-export 'crate::util::Helper as Exported'".to_string(),
+                    Some(6)
+                ),
+                (
+                    "Unexpected re-export 'crate::util::Other'".to_string(),
+                    Some(6)
+                ),
+                ("Unexpected re-export 'super::Client'".to_string(), Some(7)),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_must_not_depend_on() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"
+mod http { pub struct Request; }
+mod domain {
+    use super::http;
+    use crate::util::Clock;
+    pub fn handle(r: Vec<crate::http::Request>) -> Clock {
+        let clock = Clock::new();
+        clock
+    }
+    mod inner {
+        fn call() { super::super::http::Request; }
+    }
+}
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let rule = Rule::must_not_depend_on("crate::domain", "crate::http");
+        let failures = rule.check(&ast).into_failures();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|f| (
+                    f.path.join(" > "),
+                    f.message(),
+                    f.location.as_ref().map(|l| l.line)
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "crate::domain".to_string(),
+                    "Unexpected dependency on 'crate::http'".to_string(),
+                    Some(4)
+                ),
+                (
+                    "crate::domain".to_string(),
+                    "Unexpected dependency on 'crate::http::Request'".to_string(),
+                    Some(6)
+                ),
+                (
+                    "crate::domain::inner".to_string(),
+                    "Unexpected dependency on 'crate::http::Request'".to_string(),
+                    Some(11)
+                ),
+            ]
+        );
+        assert!(Rule::must_not_depend_on("crate::http", "crate::domain")
+            .check(&ast)
+            .as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_crate_docs() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            "//! Client.\n//!\n//! # Examples\n\npub fn run() {}\n",
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        assert!(Rule::crate_docs(["Examples"]).check(&ast).as_bool());
+        let failures = Rule::crate_docs(["Examples", "Errors"])
+            .check(&ast)
+            .into_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].message(), "Missing doc section 'Errors'");
+        assert!(failures[0].path[0].ends_with("lib.rs"));
+
+        Ok(())
+    }
+}
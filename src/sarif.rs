@@ -0,0 +1,149 @@
+use crate::failure::{Failure, Severity};
+use crate::report::Report;
+use crate::utils::CheckResult;
+use std::{fs, io, path::Path};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+pub struct SarifReport {
+    tool: String,
+    results: Vec<(String, Failure)>,
+}
+
+impl SarifReport {
+    pub fn new(tool: &str) -> Self {
+        Self {
+            tool: tool.to_string(),
+            results: Default::default(),
+        }
+    }
+
+    pub fn from_report(tool: &str, report: &Report) -> Self {
+        let mut sarif = Self::new(tool);
+        for (name, result) in report.results() {
+            sarif.add(name, result);
+        }
+        sarif
+    }
+
+    pub fn add(&mut self, rule_id: &str, result: &CheckResult) -> &mut Self {
+        self.results.extend(
+            result
+                .failures()
+                .iter()
+                .map(|f| (rule_id.to_string(), f.clone())),
+        );
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut rule_ids = Vec::new();
+        for (rule_id, _) in &self.results {
+            if !rule_ids.contains(&rule_id.as_str()) {
+                rule_ids.push(rule_id.as_str());
+            }
+        }
+        let rules = rule_ids
+            .iter()
+            .map(|id| format!("{{\"id\":\"{}\"}}", escape(id)))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let results = self
+            .results
+            .iter()
+            .map(|(rule_id, failure)| {
+                let level = match failure.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                };
+                let message = if failure.path.is_empty() {
+                    failure.message()
+                } else {
+                    format!("{}: {}", failure.path.join(" > "), failure.message())
+                };
+                let locations = match &failure.location {
+                    Some(location) => {
+                        let artifact = match &location.file {
+                            Some(file) => {
+                                format!("\"artifactLocation\":{{\"uri\":\"{}\"}},", escape(file))
+                            }
+                            None => String::new(),
+                        };
+                        format!(
+                            ",\"locations\":[{{\"physicalLocation\":{{{artifact}\"region\":{{\"startLine\":{},\"startColumn\":{}}}}}}}]",
+                            location.line, location.column
+                        )
+                    }
+                    None => String::new(),
+                };
+                format!(
+                    "{{\"ruleId\":\"{}\",\"level\":\"{level}\",\"message\":{{\"text\":\"{}\"}}{locations}}}",
+                    escape(rule_id),
+                    escape(&message)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!(
+            "{{\"$schema\":\"{SCHEMA}\",\"version\":\"2.1.0\",\"runs\":[{{\"tool\":{{\"driver\":{{\"name\":\"{}\",\"rules\":[{rules}]}}}},\"results\":[{results}]}}]}}",
+            escape(&self.tool)
+        )
+    }
+
+    pub fn write<P>(&self, path: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        fs::write(path, self.to_json())
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_to_json() -> Result<(), TestError> {
+        let file: syn::File = syn::parse_str("fn main() {}")?;
+        let result = file
+            .items
+            .has_fn()
+            .with_name("run")
+            .check()
+            .in_file("src/main.rs");
+
+        let mut sarif = SarifReport::new("syn-assert");
+        sarif
+            .add("has-run-fn", &result)
+            .add("docs", &CheckResult::missing("\"docs\"").as_warning());
+
+        assert_eq!(
+            sarif.to_json(),
+            r#"{"$schema":"https://json.schemastore.org/sarif-2.1.0.json","version":"2.1.0","runs":[{"tool":{"driver":{"name":"syn-assert","rules":[{"id":"has-run-fn"},{"id":"docs"}]}},"results":[{"ruleId":"has-run-fn","level":"error","message":{"text":"fn main: Expected name to be 'run', got 'main'"},"locations":[{"physicalLocation":{"artifactLocation":{"uri":"src/main.rs"},"region":{"startLine":1,"startColumn":1}}}]},{"ruleId":"docs","level":"warning","message":{"text":"Missing \"docs\""}}]}]}"#
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use crate::file::HasItems;
+use crate::function::{FnSpec, HasFn};
+use crate::report::Report;
+use syn::Item;
+
+pub type Selector<'s> = Box<dyn Fn(&Item) -> bool + 's>;
+
+pub struct AssertionSet<'s> {
+    name: String,
+    selector: Selector<'s>,
+    specs: Vec<(String, FnSpec)>,
+}
+
+impl<'s> AssertionSet<'s> {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            selector: Box::new(|item| matches!(item, Item::Fn(_))),
+            specs: Default::default(),
+        }
+    }
+
+    pub fn select<F>(self, selector: F) -> Self
+    where
+        F: Fn(&Item) -> bool + 's,
+    {
+        Self {
+            selector: Box::new(selector),
+            ..self
+        }
+    }
+
+    pub fn with_spec(mut self, name: &str, spec: FnSpec) -> Self {
+        self.specs.push((name.to_string(), spec));
+        self
+    }
+
+    pub fn apply<T>(&self, items: &T) -> Report
+    where
+        T: HasItems,
+    {
+        let mut report = Report::new();
+        self.apply_to(items, &mut report);
+        report
+    }
+
+    pub fn apply_to<T>(&self, items: &T, report: &mut Report)
+    where
+        T: HasItems,
+    {
+        for item in items.walk_items() {
+            if !(self.selector)(item) {
+                continue;
+            }
+            let subject = item.breadcrumb().unwrap_or_else(|| "item".to_string());
+            for (name, spec) in &self.specs {
+                report.check(
+                    &format!("{} > {name} > {subject}", self.name),
+                    spec.check(item),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_apply() -> Result<(), TestError> {
+        let file: syn::File = syn::parse_str(
+            r#"
+            pub async fn get_user() -> Response { Response }
+            async fn get_item() -> Response { Response }
+            fn helper() {}
+        "#,
+        )?;
+
+        let report = AssertionSet::new("handlers")
+            .select(
+                |item| matches!(item, Item::Fn(f) if f.sig.ident.to_string().starts_with("get_")),
+            )
+            .with_spec("public", FnSpec::new().with_vis(syn::parse_quote!(pub)))
+            .with_spec(
+                "returns response",
+                FnSpec::new().with_return_type("Response"),
+            )
+            .apply(&file);
+
+        assert_eq!((report.passed(), report.failed()), (3, 1));
+        assert_eq!(
+            report.render(false).lines().nth(3),
+            Some("[FAIL] handlers > public > fn get_item")
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use crate::cfg::CfgSet;
+use crate::failure::{Failure, Location};
+use crate::file::HasItems;
+use crate::imports::ImportScope;
+use crate::utils::{Check, CheckResult};
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use syn::spanned::Spanned;
+use syn::{File, Item};
+
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub path: PathBuf,
+    pub source: String,
+    pub file: File,
+    cfg: Option<CfgSet>,
+}
+
+pub fn from_path<P>(path: P) -> Result<SourceFile, CheckResult>
+where
+    P: AsRef<Path>,
+{
+    SourceFile::load(path)
+}
+
+impl SourceFile {
+    pub fn load<P>(path: P) -> Result<Self, CheckResult>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_path_buf();
+        let name = path.display().to_string();
+        let source = fs::read_to_string(&path).map_err(|e| {
+            CheckResult::Failure(vec![Failure::invalid(
+                &format!("file '{name}'"),
+                &e.to_string(),
+            )])
+        })?;
+        Self::parse(path, source)
+    }
+
+    pub fn parse(path: PathBuf, source: String) -> Result<Self, CheckResult> {
+        let name = path.display().to_string();
+        match syn::parse_file(&source) {
+            Ok(file) => Ok(Self {
+                path,
+                source,
+                file,
+                cfg: None,
+            }),
+            Err(e) => Err(CheckResult::Failure(vec![Failure::invalid(
+                &format!("file '{name}'"),
+                &e.to_string(),
+            )
+            .at(Some(Location::from_span(e.span())))])
+            .in_file(&name)),
+        }
+    }
+
+    pub fn with_cfg(self, cfg: &CfgSet) -> Self {
+        Self {
+            cfg: Some(cfg.clone()),
+            ..self
+        }
+    }
+
+    pub fn cfg(&self) -> Option<&CfgSet> {
+        self.cfg.as_ref()
+    }
+
+    pub fn name(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    pub fn location<T>(&self, t: &T) -> Location
+    where
+        T: Spanned,
+    {
+        Location {
+            file: Some(self.name()),
+            ..Location::of(t)
+        }
+    }
+
+    pub fn line(&self, line: usize) -> Option<&str> {
+        line.checked_sub(1)
+            .and_then(|line| self.source.lines().nth(line))
+    }
+
+    pub fn check<C>(&self, check: C) -> CheckResult
+    where
+        C: Check,
+    {
+        check.check().in_file(&self.name())
+    }
+}
+
+impl Deref for SourceFile {
+    type Target = File;
+
+    fn deref(&self) -> &Self::Target {
+        &self.file
+    }
+}
+
+impl HasItems for SourceFile {
+    fn walk_items(&self) -> Vec<&Item> {
+        match &self.cfg {
+            Some(cfg) => cfg.walk_items(&self.file.items),
+            None => self.file.walk_items(),
+        }
+    }
+
+    fn walk_scopes(&self) -> Vec<(ImportScope, Vec<&Item>)> {
+        match &self.cfg {
+            Some(cfg) => cfg.walk_scopes(&self.file.items),
+            None => self.file.walk_scopes(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_from_path() -> Result<(), TestError> {
+        let dir = TestDir::new(&[("api.rs", "pub struct Api;\n\nfn handler() {}\n")])?;
+        let path = dir.join("api.rs");
+        let source = from_path(&path).unwrap();
+
+        assert_eq!(source.line(3), Some("fn handler() {}"));
+        assert_eq!(source.location(&source.items[1]).line, 3);
+
+        let results = source.check(
+            source.items[1..]
+                .to_vec()
+                .has_fn()
+                .with_vis(syn::parse_quote!(pub)),
+        );
+        let failures = results.into_failures();
+        assert_eq!(
+            failures[0].to_string(),
+            format!(
+                "fn handler: Expected visibility to be 'pub', got 'private' (at {}:3:1)",
+                path.display()
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_path_invalid() {
+        let results = from_path("/nonexistent/api.rs").unwrap_err();
+        assert_eq!(results.failures()[0].kind, crate::FailureKind::Invalid);
+
+        let results = SourceFile::parse("api.rs".into(), "fn (".to_string()).unwrap_err();
+        assert_eq!(
+            results.failures()[0]
+                .location
+                .as_ref()
+                .and_then(|l| l.file.as_deref()),
+            Some("api.rs")
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{env, fs, io, process};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+pub(crate) struct TestDir {
+    path: PathBuf,
+}
+
+impl TestDir {
+    pub(crate) fn new(files: &[(&str, &str)]) -> io::Result<Self> {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let dir = Self {
+            path: env::temp_dir().join(format!("syn-assert-{}-{id}", process::id())),
+        };
+        fs::create_dir_all(&dir.path)?;
+        for (path, contents) in files {
+            dir.write(path, contents)?;
+        }
+        Ok(dir)
+    }
+
+    pub(crate) fn write(&self, path: &str, contents: &str) -> io::Result<()> {
+        let path = self.path.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)
+    }
+
+    pub(crate) fn join<P>(&self, path: P) -> PathBuf
+    where
+        P: AsRef<Path>,
+    {
+        self.path.join(path)
+    }
+}
+
+impl AsRef<Path> for TestDir {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_test_dir() -> Result<(), TestError> {
+        let path = {
+            let dir = TestDir::new(&[("src/lib.rs", "mod a;\n"), ("src/a.rs", "")])?;
+            let other = TestDir::new(&[])?;
+            assert_ne!(dir.as_ref(), other.as_ref());
+            assert_eq!(fs::read_to_string(dir.join("src/lib.rs"))?, "mod a;\n");
+            dir.as_ref().to_path_buf()
+        };
+        assert!(!path.exists());
+
+        Ok(())
+    }
+}
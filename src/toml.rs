@@ -0,0 +1,350 @@
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    String(String),
+    Integer(i64),
+    Bool(bool),
+    Array(Vec<Value>),
+    Table(Vec<(String, Value)>),
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Table {
+    pub(crate) line: usize,
+    pub(crate) entries: Vec<(String, Value)>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Document {
+    pub(crate) root: Table,
+    pub(crate) tables: Vec<(String, Table)>,
+    pub(crate) arrays: Vec<(String, Table)>,
+}
+
+enum Section {
+    Root,
+    Table,
+    Array,
+}
+
+impl Document {
+    pub(crate) fn parse(source: &str) -> Result<Self, String> {
+        let mut document = Document::default();
+        let mut section = Section::Root;
+        let mut lines = source.lines().enumerate();
+        while let Some((i, line)) = lines.next() {
+            let line_no = i + 1;
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix("[[").and_then(|l| l.strip_suffix("]]")) {
+                document
+                    .arrays
+                    .push((header_name(header, line_no)?, Table::at(line_no)));
+                section = Section::Array;
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let name = header_name(header, line_no)?;
+                if document.tables.iter().any(|(n, _)| *n == name) {
+                    return Err(format!("line {line_no}: duplicate table [{name}]"));
+                }
+                document.tables.push((name, Table::at(line_no)));
+                section = Section::Table;
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {line_no}: expected 'key = value'"))?;
+            let mut value = value.trim().to_string();
+            while depth(&value)? > 0 {
+                let (_, next) = lines
+                    .next()
+                    .ok_or_else(|| format!("line {line_no}: unterminated array"))?;
+                value.push(' ');
+                value.push_str(strip_comment(next).trim());
+            }
+            let value = parse_value(&value).map_err(|e| format!("line {line_no}: {e}"))?;
+            let table = match section {
+                Section::Root => &mut document.root,
+                Section::Table => &mut document.tables.last_mut().unwrap().1,
+                Section::Array => &mut document.arrays.last_mut().unwrap().1,
+            };
+            table.entries.push((parse_key(key)?, value));
+        }
+        Ok(document)
+    }
+
+    pub(crate) fn tables<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Table> {
+        self.arrays
+            .iter()
+            .filter(move |(n, _)| n == name)
+            .map(|(_, t)| t)
+    }
+}
+
+impl Table {
+    fn at(line: usize) -> Self {
+        Self {
+            line,
+            ..Default::default()
+        }
+    }
+}
+
+fn header_name(header: &str, line_no: usize) -> Result<String, String> {
+    parse_key(header).map_err(|e| format!("line {line_no}: {e}"))
+}
+
+fn parse_key(key: &str) -> Result<String, String> {
+    let key = key.trim();
+    if let Some(inner) = key.strip_prefix('"').and_then(|k| k.strip_suffix('"')) {
+        return unescape(inner);
+    }
+    if let Some(inner) = key.strip_prefix('\'').and_then(|k| k.strip_suffix('\'')) {
+        return Ok(inner.to_string());
+    }
+    match !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        true => Ok(key.to_string()),
+        false => Err(format!("unsupported key '{key}'")),
+    }
+}
+
+fn scan<F>(s: &str, mut f: F) -> Result<(), String>
+where
+    F: FnMut(usize, char) -> bool,
+{
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, c) => {
+                if !f(i, c) {
+                    return Ok(());
+                }
+            }
+        }
+        escaped = false;
+    }
+    match quote {
+        Some(_) => Err("unterminated string".to_string()),
+        None => Ok(()),
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut end = line.len();
+    let _ = scan(line, |i, c| match c {
+        '#' => {
+            end = i;
+            false
+        }
+        _ => true,
+    });
+    &line[..end]
+}
+
+fn depth(value: &str) -> Result<i32, String> {
+    let mut depth = 0;
+    scan(value, |_, c| {
+        match c {
+            '[' | '{' => depth += 1,
+            ']' | '}' => depth -= 1,
+            _ => {}
+        }
+        true
+    })?;
+    Ok(depth)
+}
+
+fn parse_value(value: &str) -> Result<Value, String> {
+    if let Some(inner) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        return split_top_level(inner)?
+            .into_iter()
+            .map(|item| parse_value(item.trim()))
+            .collect::<Result<_, _>>()
+            .map(Value::Array);
+    }
+    if let Some(inner) = value.strip_prefix('{').and_then(|v| v.strip_suffix('}')) {
+        return split_top_level(inner)?
+            .into_iter()
+            .map(|entry| {
+                let (key, value) = entry
+                    .split_once('=')
+                    .ok_or_else(|| format!("expected 'key = value' in '{}'", entry.trim()))?;
+                Ok((parse_key(key)?, parse_value(value.trim())?))
+            })
+            .collect::<Result<_, String>>()
+            .map(Value::Table);
+    }
+    if let Some(inner) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        return unescape(inner).map(Value::String);
+    }
+    if let Some(inner) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        return match inner.contains('\'') {
+            true => Err(format!("unsupported value '{value}'")),
+            false => Ok(Value::String(inner.to_string())),
+        };
+    }
+    match value {
+        "true" => Ok(Value::Bool(true)),
+        "false" => Ok(Value::Bool(false)),
+        _ => value
+            .parse()
+            .map(Value::Integer)
+            .map_err(|_| format!("unsupported value '{value}'")),
+    }
+}
+
+fn split_top_level(inner: &str) -> Result<Vec<&str>, String> {
+    let mut items = Vec::new();
+    let (mut start, mut depth) = (0, 0);
+    scan(inner, |i, c| {
+        match c {
+            '[' | '{' => depth += 1,
+            ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        true
+    })?;
+    items.push(&inner[start..]);
+    Ok(items.into_iter().filter(|i| !i.trim().is_empty()).collect())
+}
+
+fn unescape(s: &str) -> Result<String, String> {
+    let mut out = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('"') => out.push('"'),
+            Some('\\') => out.push('\\'),
+            Some(c) => return Err(format!("unsupported escape '\\{c}'")),
+            None => return Err("unterminated escape".to_string()),
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let document = Document::parse(
+            r#"
+            version = 1 # trailing comment
+
+            [[rule]]
+            name = "handlers # are pub"
+            attrs = ["inline", "must_use",]
+            async = true
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            document.root.entries,
+            vec![("version".to_string(), Value::Integer(1))]
+        );
+        let rule = document.tables("rule").next().unwrap();
+        assert_eq!(rule.line, 4);
+        assert_eq!(
+            rule.entries,
+            vec![
+                (
+                    "name".to_string(),
+                    Value::String("handlers # are pub".to_string())
+                ),
+                (
+                    "attrs".to_string(),
+                    Value::Array(vec![
+                        Value::String("inline".to_string()),
+                        Value::String("must_use".to_string())
+                    ])
+                ),
+                ("async".to_string(), Value::Bool(true)),
+            ]
+        );
+
+        assert!(Document::parse("name = nope").is_err());
+        assert!(Document::parse("names = [\"a\",\n\"b\"").is_err());
+        assert!(Document::parse("[rule]\n[rule]").is_err());
+    }
+
+    #[test]
+    fn test_parse_multiline() {
+        let document = Document::parse(
+            r#"
+            [settings]
+            paths = 'C:\src' # literal
+
+            [[rule]]
+            attrs = [
+                "inline", # hot path
+                'must_use',
+                ["nested", "]"],
+            ]
+            limits = { max_items = 10, kind = "fn" }
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            document.tables,
+            vec![(
+                "settings".to_string(),
+                Table {
+                    line: 2,
+                    entries: vec![("paths".to_string(), Value::String("C:\\src".to_string()))],
+                }
+            )]
+        );
+        let rule = document.tables("rule").next().unwrap();
+        assert_eq!(
+            rule.entries,
+            vec![
+                (
+                    "attrs".to_string(),
+                    Value::Array(vec![
+                        Value::String("inline".to_string()),
+                        Value::String("must_use".to_string()),
+                        Value::Array(vec![
+                            Value::String("nested".to_string()),
+                            Value::String("]".to_string())
+                        ]),
+                    ])
+                ),
+                (
+                    "limits".to_string(),
+                    Value::Table(vec![
+                        ("max_items".to_string(), Value::Integer(10)),
+                        ("kind".to_string(), Value::String("fn".to_string())),
+                    ])
+                ),
+            ]
+        );
+    }
+}
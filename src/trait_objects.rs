@@ -0,0 +1,181 @@
+use crate::failure::{Failure, Location};
+use crate::rules::Rule;
+use crate::type_alias::types_match;
+use crate::utils::{parse_str, CheckResult};
+use quote::ToTokens;
+use syn::visit::{self, Visit};
+use syn::{Fields, FnArg, ImplItem, Item, ReturnType, Signature, Type, Visibility};
+
+impl<'s> Rule<'s> {
+    pub fn no_pub_trait_objects<I, S>(allow: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let allow = allow
+            .into_iter()
+            .map(|a| parse_str::<Type>("type", a.as_ref()))
+            .collect::<Result<Vec<_>, _>>();
+        Self::new("no trait objects in public api").must(move |item| match &allow {
+            Ok(allow) => no_pub_trait_objects(item, allow),
+            Err(failure) => failure.clone(),
+        })
+    }
+}
+
+pub(crate) fn no_pub_trait_objects(item: &Item, allow: &[Type]) -> CheckResult {
+    let mut visitor = TraitObjectVisitor {
+        allow,
+        context: String::new(),
+        failures: Vec::new(),
+    };
+    match item {
+        Item::Fn(func) if is_pub(&func.vis) => visitor.visit_sig(&func.sig),
+        Item::Impl(impl_) if impl_.trait_.is_none() => {
+            for i in &impl_.items {
+                if let ImplItem::Method(m) = i {
+                    if is_pub(&m.vis) {
+                        visitor.visit_sig(&m.sig);
+                    }
+                }
+            }
+        }
+        Item::Struct(item) if is_pub(&item.vis) => {
+            let fields = match &item.fields {
+                Fields::Named(fields) => fields.named.iter().collect(),
+                Fields::Unnamed(fields) => fields.unnamed.iter().collect(),
+                Fields::Unit => Vec::new(),
+            };
+            for (index, field) in fields.into_iter().enumerate() {
+                if is_pub(&field.vis) {
+                    let name = match &field.ident {
+                        Some(ident) => ident.to_string(),
+                        None => index.to_string(),
+                    };
+                    visitor.visit_in(
+                        &field.ty,
+                        &format!("field '{name}' of struct '{}'", item.ident),
+                    );
+                }
+            }
+        }
+        _ => (),
+    }
+    visitor.failures.into()
+}
+
+struct TraitObjectVisitor<'a> {
+    allow: &'a [Type],
+    context: String,
+    failures: Vec<Failure>,
+}
+
+impl TraitObjectVisitor<'_> {
+    fn visit_sig(&mut self, sig: &Signature) {
+        let context = format!("fn '{}'", sig.ident);
+        for input in &sig.inputs {
+            if let FnArg::Typed(arg) = input {
+                self.visit_in(&arg.ty, &context);
+            }
+        }
+        if let ReturnType::Type(_, ty) = &sig.output {
+            self.visit_in(ty, &context);
+        }
+    }
+
+    fn visit_in(&mut self, ty: &Type, context: &str) {
+        self.context = context.to_string();
+        self.visit_type(ty);
+    }
+}
+
+impl<'ast> Visit<'ast> for TraitObjectVisitor<'_> {
+    fn visit_type(&mut self, ty: &'ast Type) {
+        if self.allow.iter().any(|allowed| types_match(allowed, ty)) {
+            return;
+        }
+        if let Type::TraitObject(object) = ty {
+            self.failures.push(
+                Failure::unexpected(&format!(
+                    "trait object '{}' in {}",
+                    object.to_token_stream(),
+                    self.context
+                ))
+                .at(Some(Location::of(object))),
+            );
+            return;
+        }
+        visit::visit_type(self, ty);
+    }
+}
+
+fn is_pub(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crate_ast::CrateAst;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_no_pub_trait_objects() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"pub fn load(path: &dyn AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> { todo!() }
+fn private(f: &dyn Fn()) {}
+pub struct Server {
+    pub handler: Box<dyn Handler + Send>,
+    state: Box<dyn Any>,
+}
+pub struct Wrapper(pub Vec<Box<dyn Display>>);
+impl Server {
+    pub fn with(&self, f: impl Fn()) -> Option<&dyn Any> { None }
+}
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let failures = Rule::no_pub_trait_objects(["Box<dyn Error>"])
+            .check(&ast)
+            .into_failures();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "Unexpected trait object 'dyn AsRef < Path >' in fn 'load'".to_string(),
+                    Some(1)
+                ),
+                (
+                    "Unexpected trait object 'dyn Handler + Send' in field 'handler' of struct 'Server'"
+                        .to_string(),
+                    Some(4)
+                ),
+                (
+                    "Unexpected trait object 'dyn Display' in field '0' of struct 'Wrapper'"
+                        .to_string(),
+                    Some(7)
+                ),
+                (
+                    "Unexpected trait object 'dyn Any' in fn 'with'".to_string(),
+                    Some(9)
+                ),
+            ]
+        );
+        assert_eq!(
+            Rule::no_pub_trait_objects(Vec::<&str>::new())
+                .check(&ast)
+                .failure_count(),
+            5
+        );
+
+        Ok(())
+    }
+}
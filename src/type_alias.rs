@@ -0,0 +1,464 @@
+use crate::failure::{Failure, Location};
+use crate::names::NameOptions;
+use crate::types::types_eq;
+use crate::utils::{
+    attr_paths, check_option, compare_attrs, generic_names, parse_str, unexpected_extras, Check,
+    CheckResult,
+};
+use crate::visibility::compare_vis;
+use quote::ToTokens;
+use syn::{
+    Attribute, GenericArgument, Item, ItemType, Path, PathArguments, Type, TypeParamBound,
+    TypeParen, Visibility,
+};
+
+pub trait HasTypeAlias {
+    fn has_type_alias(&self) -> AssertTypeAlias<'_, Self>
+    where
+        Self: Sized,
+    {
+        AssertTypeAlias::new(self)
+    }
+
+    fn breadcrumb(&self) -> Option<String> {
+        None
+    }
+
+    fn location(&self) -> Option<Location> {
+        None
+    }
+
+    fn has_name(&self, name: &str) -> CheckResult;
+    fn has_exact_name(&self, name: &str) -> CheckResult;
+    fn has_name_with(&self, name: &str, options: &NameOptions) -> CheckResult;
+    fn has_vis(&self, vis: &Visibility) -> CheckResult;
+    fn has_attrs(&self, attrs: &[String]) -> CheckResult;
+    fn has_attr_values(&self, attrs: &[Attribute]) -> CheckResult;
+    fn has_target(&self, target: &str) -> CheckResult;
+    fn has_only_attrs(&self, attrs: &[String]) -> CheckResult;
+    fn has_only_generics(&self, generics: &[String]) -> CheckResult;
+}
+
+impl HasTypeAlias for ItemType {
+    fn breadcrumb(&self) -> Option<String> {
+        Some(format!("type {}", self.ident))
+    }
+
+    fn location(&self) -> Option<Location> {
+        Some(Location::of(self))
+    }
+
+    fn has_name(&self, name: &str) -> CheckResult {
+        CheckResult::compare_ident("name", name, &self.ident)
+    }
+
+    fn has_exact_name(&self, name: &str) -> CheckResult {
+        CheckResult::compare_named("name", name, &self.ident)
+    }
+
+    fn has_name_with(&self, name: &str, options: &NameOptions) -> CheckResult {
+        CheckResult::compare_ident_with("name", name, &self.ident, options)
+    }
+
+    fn has_vis(&self, vis: &Visibility) -> CheckResult {
+        compare_vis(vis, &self.vis)
+    }
+
+    fn has_attrs(&self, attrs: &[String]) -> CheckResult {
+        CheckResult::contains_display(attr_paths(&self.attrs), attrs)
+    }
+
+    fn has_attr_values(&self, attrs: &[Attribute]) -> CheckResult {
+        compare_attrs(&self.attrs, attrs)
+    }
+
+    fn has_target(&self, target: &str) -> CheckResult {
+        let expected = match parse_str::<Type>("type", target) {
+            Ok(expected) => expected,
+            Err(failure) => return failure,
+        };
+        if types_match(&expected, &self.ty) {
+            CheckResult::Success
+        } else {
+            CheckResult::Failure(vec![Failure::mismatch(
+                "alias target",
+                &expected.to_token_stream().to_string(),
+                &self.ty.to_token_stream().to_string(),
+            )])
+        }
+    }
+
+    fn has_only_attrs(&self, attrs: &[String]) -> CheckResult {
+        unexpected_extras("attribute", attr_paths(&self.attrs), attrs)
+    }
+
+    fn has_only_generics(&self, generics: &[String]) -> CheckResult {
+        unexpected_extras("generic", generic_names(&self.generics), generics)
+    }
+}
+
+macro_rules! hastypealias_item {
+    ($v:ident, $t: ty) => {
+        paste::paste! {
+            fn [<has_ $v>](&self, $v: $t) -> CheckResult {
+                match self {
+                    Item::Type(alias) => alias.[<has_ $v>]($v),
+                    _ => CheckResult::missing(stringify!($v)),
+                }
+            }
+        }
+    };
+}
+
+impl HasTypeAlias for Item {
+    fn breadcrumb(&self) -> Option<String> {
+        match self {
+            Item::Type(alias) => alias.breadcrumb(),
+            _ => None,
+        }
+    }
+
+    fn location(&self) -> Option<Location> {
+        Some(Location::of(self))
+    }
+
+    hastypealias_item!(name, &str);
+    hastypealias_item!(exact_name, &str);
+
+    fn has_name_with(&self, name: &str, options: &NameOptions) -> CheckResult {
+        match self {
+            Item::Type(alias) => alias.has_name_with(name, options),
+            _ => CheckResult::missing("name"),
+        }
+    }
+
+    hastypealias_item!(vis, &Visibility);
+    hastypealias_item!(attrs, &[String]);
+    hastypealias_item!(attr_values, &[Attribute]);
+    hastypealias_item!(target, &str);
+    hastypealias_item!(only_attrs, &[String]);
+    hastypealias_item!(only_generics, &[String]);
+}
+
+macro_rules! hastypealias_vec {
+    ($v: ident, $t: ty) => {
+        paste::paste! {
+            fn [<has_ $v>](&self, $v: $t) -> CheckResult {
+                CheckResult::any(self.iter().map(|f| f.[<has_ $v>](&$v).in_item(f.breadcrumb(), f.location())))
+            }
+        }
+    };
+}
+
+impl<T> HasTypeAlias for Vec<T>
+where
+    T: HasTypeAlias,
+{
+    hastypealias_vec!(name, &str);
+    hastypealias_vec!(exact_name, &str);
+
+    fn has_name_with(&self, name: &str, options: &NameOptions) -> CheckResult {
+        CheckResult::any(self.iter().map(|t| {
+            t.has_name_with(name, options)
+                .in_item(t.breadcrumb(), t.location())
+        }))
+    }
+
+    hastypealias_vec!(vis, &Visibility);
+    hastypealias_vec!(attrs, &[String]);
+    hastypealias_vec!(attr_values, &[Attribute]);
+    hastypealias_vec!(target, &str);
+    hastypealias_vec!(only_attrs, &[String]);
+    hastypealias_vec!(only_generics, &[String]);
+}
+
+#[must_use]
+pub struct AssertTypeAlias<'s, T> {
+    t: &'s T,
+    label: Option<&'s str>,
+    strict: bool,
+    name: Option<&'s str>,
+    exact_name: Option<&'s str>,
+    name_options: NameOptions,
+    vis: Option<Visibility>,
+    attrs: Vec<String>,
+    attr_values: Vec<Attribute>,
+    target: Option<&'s str>,
+}
+
+impl<'s, T> AssertTypeAlias<'s, T> {
+    pub fn new(t: &'s T) -> Self {
+        Self {
+            t,
+            label: Default::default(),
+            strict: Default::default(),
+            name: Default::default(),
+            exact_name: Default::default(),
+            name_options: Default::default(),
+            vis: Default::default(),
+            attrs: Default::default(),
+            attr_values: Default::default(),
+            target: Default::default(),
+        }
+    }
+
+    pub fn labeled(self, label: &'s str) -> Self {
+        Self {
+            label: Some(label),
+            ..self
+        }
+    }
+
+    pub fn strict(self) -> Self {
+        Self {
+            strict: true,
+            ..self
+        }
+    }
+
+    pub fn with_name(self, name: &'s str) -> Self {
+        Self {
+            name: Some(name),
+            ..self
+        }
+    }
+
+    pub fn with_exact_name(self, exact_name: &'s str) -> Self {
+        Self {
+            exact_name: Some(exact_name),
+            ..self
+        }
+    }
+
+    pub fn with_name_options(self, name_options: NameOptions) -> Self {
+        Self {
+            name_options,
+            ..self
+        }
+    }
+
+    pub fn with_vis(self, vis: Visibility) -> Self {
+        Self {
+            vis: Some(vis),
+            ..self
+        }
+    }
+
+    pub fn with_attrs<I, S>(self, attrs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            attrs: attrs.into_iter().map(|a| a.as_ref().to_string()).collect(),
+            ..self
+        }
+    }
+
+    pub fn with_attr(mut self, attr: Attribute) -> Self {
+        self.attr_values.push(attr);
+        self
+    }
+
+    pub fn aliases(self, target: &'s str) -> Self {
+        Self {
+            target: Some(target),
+            ..self
+        }
+    }
+}
+
+impl<'s, T> AssertTypeAlias<'s, T>
+where
+    T: HasTypeAlias,
+{
+    fn is_empty(&self) -> bool {
+        !self.strict
+            && self.name.is_none()
+            && self.exact_name.is_none()
+            && self.vis.is_none()
+            && self.target.is_none()
+            && self.attrs.is_empty()
+            && self.attr_values.is_empty()
+    }
+
+    fn check_strict(&self) -> CheckResult {
+        let mut attrs = self.attrs.clone();
+        attrs.extend(attr_paths(&self.attr_values));
+        self.t.has_only_attrs(&attrs) + self.t.has_only_generics(&[])
+    }
+}
+
+impl<'s, T> Check for AssertTypeAlias<'s, T>
+where
+    T: HasTypeAlias,
+{
+    fn check(self) -> CheckResult {
+        let (breadcrumb, location) = (self.t.breadcrumb(), self.t.location());
+        let label = self.label;
+        let empty = match self.is_empty() {
+            true => CheckResult::missing("constraints in type alias assertion"),
+            false => CheckResult::Success,
+        };
+        let strict = match self.strict {
+            true => self.check_strict(),
+            false => CheckResult::Success,
+        };
+        let results = (empty
+            + strict
+            + self.name.map_or(CheckResult::Success, |name| {
+                self.t.has_name_with(name, &self.name_options)
+            })
+            + check_option!(self, exact_name)
+            + check_option!(self, vis)
+            + check_option!(self, target)
+            + self.t.has_attrs(&self.attrs)
+            + self.t.has_attr_values(&self.attr_values))
+        .in_item(breadcrumb, location);
+        match label {
+            Some(label) => results.within(label),
+            None => results,
+        }
+    }
+}
+
+pub(crate) fn types_match(expected: &Type, actual: &Type) -> bool {
+    match (expected, actual) {
+        (Type::Infer(_), _) => true,
+        (Type::Paren(TypeParen { elem, .. }), actual) => types_match(elem, actual),
+        (expected, Type::Paren(TypeParen { elem, .. })) => types_match(expected, elem),
+        (Type::Group(group), actual) => types_match(&group.elem, actual),
+        (expected, Type::Group(group)) => types_match(expected, &group.elem),
+        (Type::Path(expected), Type::Path(actual))
+            if expected.qself.is_none() && actual.qself.is_none() =>
+        {
+            paths_match(&expected.path, &actual.path)
+        }
+        (Type::Reference(expected), Type::Reference(actual)) => {
+            expected.mutability.is_some() == actual.mutability.is_some()
+                && (expected.lifetime.is_none()
+                    || expected.lifetime.as_ref().is_some_and(|l| l.ident == "_")
+                    || expected.lifetime == actual.lifetime)
+                && types_match(&expected.elem, &actual.elem)
+        }
+        (Type::Slice(expected), Type::Slice(actual)) => types_match(&expected.elem, &actual.elem),
+        (Type::Array(expected), Type::Array(actual)) => {
+            types_match(&expected.elem, &actual.elem)
+                && expected.len.to_token_stream().to_string()
+                    == actual.len.to_token_stream().to_string()
+        }
+        (Type::Ptr(expected), Type::Ptr(actual)) => {
+            expected.mutability.is_some() == actual.mutability.is_some()
+                && types_match(&expected.elem, &actual.elem)
+        }
+        (Type::TraitObject(expected), Type::TraitObject(actual)) => {
+            expected.bounds.len() == actual.bounds.len()
+                && expected
+                    .bounds
+                    .iter()
+                    .zip(&actual.bounds)
+                    .all(|(e, a)| match (e, a) {
+                        (TypeParamBound::Trait(e), TypeParamBound::Trait(a)) => {
+                            paths_match(&e.path, &a.path)
+                        }
+                        (e, a) => {
+                            e.to_token_stream().to_string() == a.to_token_stream().to_string()
+                        }
+                    })
+        }
+        (Type::Tuple(expected), Type::Tuple(actual)) => {
+            expected.elems.len() == actual.elems.len()
+                && expected
+                    .elems
+                    .iter()
+                    .zip(&actual.elems)
+                    .all(|(e, a)| types_match(e, a))
+        }
+        (expected, actual) => types_eq(expected, actual),
+    }
+}
+
+fn paths_match(expected: &Path, actual: &Path) -> bool {
+    expected
+        .segments
+        .iter()
+        .rev()
+        .zip(actual.segments.iter().rev())
+        .all(|(e, a)| {
+            e.ident == a.ident
+                && match (&e.arguments, &a.arguments) {
+                    (PathArguments::None, PathArguments::None) => true,
+                    (PathArguments::AngleBracketed(e), PathArguments::AngleBracketed(a)) => {
+                        e.args.len() == a.args.len()
+                            && e.args.iter().zip(&a.args).all(|(e, a)| match (e, a) {
+                                (GenericArgument::Type(e), GenericArgument::Type(a)) => {
+                                    types_match(e, a)
+                                }
+                                (e, a) => {
+                                    e.to_token_stream().to_string()
+                                        == a.to_token_stream().to_string()
+                                }
+                            })
+                    }
+                    (e, a) => e.to_token_stream().to_string() == a.to_token_stream().to_string(),
+                }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_aliases() -> Result<(), TestError> {
+        let alias: syn::Item =
+            syn::parse_str("pub type Result<T, E = Error> = std::result::Result<T, Error>;")?;
+
+        let results = alias
+            .has_type_alias()
+            .with_name("Result")
+            .with_vis(syn::parse_quote!(pub))
+            .aliases("Result<T, crate::Error>")
+            .check();
+        assert!(results.as_bool());
+        assert!(alias
+            .has_type_alias()
+            .aliases("Result<_, _>")
+            .check()
+            .as_bool());
+        assert!(!alias
+            .has_type_alias()
+            .aliases("core::result::Result<T, Error>")
+            .check()
+            .as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_aliases_fail() -> Result<(), TestError> {
+        let alias: syn::ItemType = syn::parse_str("type Handler = Box<dyn Fn(&str) -> u8>;")?;
+
+        assert!(alias
+            .has_type_alias()
+            .aliases("Box<dyn Fn(&str) -> u8>")
+            .check()
+            .as_bool());
+
+        let results = alias
+            .has_type_alias()
+            .aliases("Box<dyn Fn(&str) -> u16>")
+            .check();
+        let failures = results.into_failures();
+        assert_eq!(
+            failures[0].message(),
+            "Expected alias target to be 'Box < dyn Fn (& str) -> u16 >', got 'Box < dyn Fn (& str) -> u8 >'"
+        );
+        assert_eq!(failures[0].path, vec!["type Handler"]);
+
+        Ok(())
+    }
+}
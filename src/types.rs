@@ -0,0 +1,170 @@
+use crate::explain;
+use crate::imports::ImportScope;
+use crate::normalize::{crate_name, normalize};
+use crate::utils::{parse_str, CheckResult};
+use quote::ToTokens;
+use syn::punctuated::Punctuated;
+use syn::visit_mut::{self, VisitMut};
+use syn::{AngleBracketedGenericArguments, Ident, Path, Type};
+
+pub(crate) fn types_eq(expected: &Type, actual: &Type) -> bool {
+    types_eq_in(expected, actual, &ImportScope::default())
+}
+
+pub(crate) fn types_eq_in(expected: &Type, actual: &Type, imports: &ImportScope) -> bool {
+    canonical(expected, imports) == canonical(actual, imports)
+}
+
+pub(crate) fn compare_types(expected: &str, actual: &Type) -> CheckResult {
+    compare_types_in(expected, actual, &ImportScope::default())
+}
+
+pub(crate) fn compare_types_in(
+    expected: &str,
+    actual: &Type,
+    imports: &ImportScope,
+) -> CheckResult {
+    let expected = match parse_str::<Type>("type", expected) {
+        Ok(expected) => expected,
+        Err(failure) => return failure,
+    };
+    if types_eq_in(&expected, actual, imports) {
+        explain::record(|| {
+            format!(
+                "type: '{}' == '{}'",
+                expected.to_token_stream(),
+                actual.to_token_stream()
+            )
+        });
+        return CheckResult::Success;
+    }
+    CheckResult::compare_named(
+        "type",
+        normalize(expected.to_token_stream()),
+        normalize(actual.to_token_stream()),
+    )
+}
+
+fn canonical(ty: &Type, imports: &ImportScope) -> Type {
+    let mut ty = ty.clone();
+    Canonicalize {
+        crate_name: crate_name(),
+        imports,
+    }
+    .visit_type_mut(&mut ty);
+    ty
+}
+
+struct Canonicalize<'a> {
+    crate_name: Option<String>,
+    imports: &'a ImportScope,
+}
+
+impl VisitMut for Canonicalize<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        loop {
+            let inner = match ty {
+                Type::Group(group) => (*group.elem).clone(),
+                Type::Paren(paren) => (*paren.elem).clone(),
+                _ => break,
+            };
+            *ty = inner;
+        }
+        if let Type::Tuple(tuple) = ty {
+            if tuple.elems.len() > 1 {
+                strip_trailing(&mut tuple.elems);
+            }
+        }
+        if let Type::TraitObject(object) = ty {
+            strip_trailing(&mut object.bounds);
+        }
+        if let Type::ImplTrait(impl_trait) = ty {
+            strip_trailing(&mut impl_trait.bounds);
+        }
+        visit_mut::visit_type_mut(self, ty);
+    }
+
+    fn visit_path_mut(&mut self, path: &mut Path) {
+        self.imports.resolve(path);
+        if let Some(name) = &self.crate_name {
+            if path.segments.len() > 1 && path.segments[0].ident == name {
+                let span = path.segments[0].ident.span();
+                path.leading_colon = None;
+                path.segments[0].ident = Ident::new("crate", span);
+            }
+        }
+        visit_mut::visit_path_mut(self, path);
+    }
+
+    fn visit_angle_bracketed_generic_arguments_mut(
+        &mut self,
+        args: &mut AngleBracketedGenericArguments,
+    ) {
+        args.colon2_token = None;
+        strip_trailing(&mut args.args);
+        visit_mut::visit_angle_bracketed_generic_arguments_mut(self, args);
+    }
+}
+
+fn strip_trailing<T, P>(punctuated: &mut Punctuated<T, P>) {
+    if punctuated.trailing_punct() {
+        if let Some(last) = punctuated.pop() {
+            punctuated.push_value(last.into_value());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::function::HasFn;
+    use crate::normalize::with_crate_name;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_types_eq() -> Result<(), TestError> {
+        let equal = [
+            ("Vec<u8>", "Vec::<u8>"),
+            ("HashMap<K, V>", "HashMap<K, V,>"),
+            ("Box<dyn Fn() + Send>", "Box<(dyn Fn() + Send)>"),
+            ("(u8, u16)", "(u8, u16,)"),
+            ("&'a str", "&'a (str)"),
+        ];
+        for (expected, actual) in equal {
+            let (expected, actual) = (syn::parse_str(expected)?, syn::parse_str(actual)?);
+            assert!(types_eq(&expected, &actual), "{expected:?} != {actual:?}");
+        }
+
+        let different = [
+            ("u8", "(u8,)"),
+            ("Vec<u8>", "Vec<u16>"),
+            ("&str", "&mut str"),
+        ];
+        for (expected, actual) in different {
+            let (expected, actual) = (syn::parse_str(expected)?, syn::parse_str(actual)?);
+            assert!(!types_eq(&expected, &actual));
+        }
+
+        let (expected, actual) = (
+            syn::parse_str("Vec<crate::model::User>")?,
+            syn::parse_str("Vec<::my_crate::model::User>")?,
+        );
+        assert!(!types_eq(&expected, &actual));
+        assert!(with_crate_name("my_crate", || types_eq(&expected, &actual)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_return_type_structural() -> Result<(), TestError> {
+        let func: syn::ItemFn =
+            syn::parse_str("fn load() -> Result<Vec::<u8>, (Error),> { todo!() }")?;
+
+        assert!(func.has_return_type("Result<Vec<u8>, Error>").as_bool());
+        assert!(!func.has_return_type("Result<Vec<u16>, Error>").as_bool());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+use crate::crate_ast::CrateAst;
+use crate::failure::{Failure, Location};
+use crate::rules::Rule;
+use crate::utils::CheckResult;
+use syn::visit::{self, Visit};
+use syn::{
+    ExprUnsafe, Item, ItemImpl, ItemMod, ItemStatic, ItemTrait, Meta, NestedMeta, Signature,
+};
+
+impl<'s> Rule<'s> {
+    pub fn no_static_mut() -> Self {
+        Self::new("no static mut").must(no_static_mut)
+    }
+
+    pub fn no_unsafe() -> Self {
+        Self::new("no unsafe code")
+            .must(no_unsafe)
+            .must_crate(|ast, _| ast.forbids_unsafe())
+    }
+}
+
+impl CrateAst {
+    pub fn forbids_unsafe(&self) -> CheckResult {
+        let root = match self.files().next() {
+            Some((_, root)) => root,
+            None => return CheckResult::missing("crate root"),
+        };
+        let found =
+            root.attrs
+                .iter()
+                .filter(|a| a.path.is_ident("forbid"))
+                .any(|a| match a.parse_meta() {
+                    Ok(Meta::List(list)) => list.nested.iter().any(
+                        |n| matches!(n, NestedMeta::Meta(m) if m.path().is_ident("unsafe_code")),
+                    ),
+                    _ => false,
+                });
+
+        if found {
+            CheckResult::Success
+        } else {
+            CheckResult::missing("#![forbid(unsafe_code)]").within(&root.name())
+        }
+    }
+}
+
+pub(crate) fn no_unsafe(item: &Item) -> CheckResult {
+    let mut visitor = UnsafeVisitor(Vec::new());
+    visitor.visit_item(item);
+    visitor.0.into()
+}
+
+pub(crate) fn no_static_mut(item: &Item) -> CheckResult {
+    let mut visitor = StaticMutVisitor(Vec::new());
+    visitor.visit_item(item);
+    visitor.0.into()
+}
+
+struct StaticMutVisitor(Vec<Failure>);
+
+impl<'ast> Visit<'ast> for StaticMutVisitor {
+    fn visit_item_mod(&mut self, _: &'ast ItemMod) {}
+
+    fn visit_item_static(&mut self, item: &'ast ItemStatic) {
+        if let Some(mutability) = &item.mutability {
+            self.0.push(
+                Failure::unexpected(&format!("static mut '{}'", item.ident))
+                    .at(Some(Location::of(mutability))),
+            );
+        }
+        visit::visit_item_static(self, item);
+    }
+}
+
+struct UnsafeVisitor(Vec<Failure>);
+
+impl UnsafeVisitor {
+    fn push(&mut self, what: &str, location: Location) {
+        self.0
+            .push(Failure::unexpected(&format!("unsafe {what}")).at(Some(location)));
+    }
+}
+
+impl<'ast> Visit<'ast> for UnsafeVisitor {
+    fn visit_item_mod(&mut self, _: &'ast ItemMod) {}
+
+    fn visit_signature(&mut self, sig: &'ast Signature) {
+        if let Some(unsafety) = &sig.unsafety {
+            self.push(&format!("fn '{}'", sig.ident), Location::of(unsafety));
+        }
+        visit::visit_signature(self, sig);
+    }
+
+    fn visit_expr_unsafe(&mut self, expr: &'ast ExprUnsafe) {
+        self.push("block", Location::of(&expr.unsafe_token));
+        visit::visit_expr_unsafe(self, expr);
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast ItemImpl) {
+        if let Some(unsafety) = &item.unsafety {
+            self.push("impl", Location::of(unsafety));
+        }
+        visit::visit_item_impl(self, item);
+    }
+
+    fn visit_item_trait(&mut self, item: &'ast ItemTrait) {
+        if let Some(unsafety) = &item.unsafety {
+            self.push(&format!("trait '{}'", item.ident), Location::of(unsafety));
+        }
+        visit::visit_item_trait(self, item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::HasItems;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_no_unsafe() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"#![deny(missing_docs)]
+pub unsafe fn raw() {}
+fn safe() { let x = unsafe { raw() }; }
+unsafe trait Marker {}
+unsafe impl Send for Handle {}
+mod inner { impl Handle { unsafe fn get(&self) {} } }
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+        dir.write("src/lib.rs", "#![forbid(unsafe_code)]\nfn safe() {}\n")?;
+        let safe = CrateAst::load(&dir)?;
+
+        let failures = Rule::no_unsafe().check(&ast).into_failures();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Missing #![forbid(unsafe_code)]".to_string(), None),
+                ("Unexpected unsafe fn 'raw'".to_string(), Some(2)),
+                ("Unexpected unsafe block".to_string(), Some(3)),
+                ("Unexpected unsafe trait 'Marker'".to_string(), Some(4)),
+                ("Unexpected unsafe impl".to_string(), Some(5)),
+                ("Unexpected unsafe fn 'get'".to_string(), Some(6)),
+            ]
+        );
+        assert!(Rule::no_unsafe().check(&safe).as_bool());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_static_mut() -> Result<(), TestError> {
+        let file: syn::File = syn::parse_str(
+            r#"static NAME: &str = "x";
+static mut COUNTER: u32 = 0;
+fn run() {
+    static mut CACHE: u8 = 0;
+}
+mod inner { pub static mut STATE: bool = false; }
+"#,
+        )?;
+
+        let failures = file.has_no_static_mut().into_failures();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Unexpected static mut 'COUNTER'".to_string(), Some(2)),
+                ("Unexpected static mut 'CACHE'".to_string(), Some(4)),
+                ("Unexpected static mut 'STATE'".to_string(), Some(6)),
+            ]
+        );
+
+        Ok(())
+    }
+}
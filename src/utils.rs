@@ -1,14 +1,32 @@
-use std::{collections::HashSet, fmt::Debug, hash::Hash, ops::Add};
+use crate::color::{self, paint, Style};
+use crate::diff::Diff;
+use crate::explain::{self, Verbosity};
+use crate::failure::{Failure, FailureKind, Location};
+use crate::names::NameOptions;
+use crate::normalize::normalize;
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::ToTokens;
+use std::{
+    collections::{BTreeSet, HashSet},
+    error,
+    fmt::{self, Debug, Display},
+    hash::Hash,
+    io,
+    ops::Add,
+};
+use syn::{
+    ext::IdentExt, parse::Parse, Attribute, GenericParam, Generics, Ident, Meta, NestedMeta, Path,
+};
 
 pub trait Check {
     fn check(self) -> CheckResult;
 }
 
 #[must_use]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum CheckResult {
     Success,
-    Failure(Vec<String>),
+    Failure(Vec<Failure>),
 }
 
 impl CheckResult {
@@ -18,9 +36,48 @@ impl CheckResult {
         E: Debug,
     {
         if actual == expected {
+            explain::record(|| format!("'{expected:?}' == '{actual:?}'"));
             Self::Success
         } else {
-            Self::Failure(vec![format!("Expected '{expected:?}', got '{actual:?}'")])
+            Self::Failure(vec![Failure::mismatch(
+                "",
+                &format!("{expected:?}"),
+                &format!("{actual:?}"),
+            )])
+        }
+    }
+
+    pub fn compare_named<A, E>(subject: &str, expected: E, actual: A) -> Self
+    where
+        A: Display,
+        E: Display,
+    {
+        let (expected, actual) = (expected.to_string(), actual.to_string());
+        if actual == expected {
+            explain::record(|| format!("{subject}: '{expected}' == '{actual}'"));
+            Self::Success
+        } else {
+            Self::Failure(vec![Failure::mismatch(subject, &expected, &actual)])
+        }
+    }
+
+    pub fn compare_ident(subject: &str, expected: &str, actual: &Ident) -> Self {
+        Self::compare_ident_with(subject, expected, actual, &NameOptions::default())
+    }
+
+    pub fn compare_ident_with(
+        subject: &str,
+        expected: &str,
+        actual: &Ident,
+        options: &NameOptions,
+    ) -> Self {
+        let expected = expected.strip_prefix("r#").unwrap_or(expected);
+        let actual = actual.unraw().to_string();
+        if options.apply(expected) == options.apply(&actual) {
+            explain::record(|| format!("{subject}: '{expected}' == '{actual}'"));
+            Self::Success
+        } else {
+            Self::Failure(vec![Failure::mismatch(subject, expected, &actual)])
         }
     }
 
@@ -28,14 +85,31 @@ impl CheckResult {
     where
         C: Contains<T>,
         T: PartialEq + Debug,
+    {
+        Self::contains_rendered(actual, expected, |e| format!("{e:?}"))
+    }
+
+    pub fn contains_display<C, T>(actual: C, expected: &[T]) -> Self
+    where
+        C: Contains<T>,
+        T: PartialEq + Display,
+    {
+        Self::contains_rendered(actual, expected, T::to_string)
+    }
+
+    fn contains_rendered<C, T, R>(actual: C, expected: &[T], render: R) -> Self
+    where
+        C: Contains<T>,
+        R: Fn(&T) -> String,
     {
         let failures = expected
             .iter()
             .filter_map(|e| {
                 if actual.contains(e) {
+                    explain::record(|| format!("found '{}'", render(e)));
                     None
                 } else {
-                    Some(format!("Missing '{e:?}'"))
+                    Some(Failure::missing(&format!("'{}'", render(e))))
                 }
             })
             .collect::<Vec<_>>();
@@ -59,25 +133,163 @@ impl CheckResult {
     }
 
     pub fn missing(name: &str) -> Self {
-        CheckResult::Failure(vec![format!("Missing {}", name)])
+        CheckResult::Failure(vec![Failure::missing(name)])
+    }
+
+    pub fn within(self, segment: &str) -> Self {
+        match self {
+            Self::Success => Self::Success,
+            Self::Failure(failures) => {
+                Self::Failure(failures.into_iter().map(|f| f.within(segment)).collect())
+            }
+        }
+    }
+
+    pub fn at(self, location: Option<Location>) -> Self {
+        match self {
+            Self::Success => Self::Success,
+            Self::Failure(failures) => Self::Failure(
+                failures
+                    .into_iter()
+                    .map(|f| f.at(location.clone()))
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn in_file(self, file: &str) -> Self {
+        match self {
+            Self::Success => Self::Success,
+            Self::Failure(failures) => {
+                Self::Failure(failures.into_iter().map(|f| f.in_file(file)).collect())
+            }
+        }
+    }
+
+    pub fn in_item(self, breadcrumb: Option<String>, location: Option<Location>) -> Self {
+        let result = self.at(location);
+        match breadcrumb {
+            Some(breadcrumb) => result.within(&breadcrumb),
+            None => result,
+        }
     }
 
     pub fn and(self, other: Self) -> Self {
-        let mut self_failures: Vec<_> = self.into();
-        let other_failures: Vec<_> = other.into();
+        let mut self_failures: Vec<Failure> = self.into();
+        let other_failures: Vec<Failure> = other.into();
 
         self_failures.extend(other_failures);
         self_failures.into()
     }
 
+    #[track_caller]
+    pub fn assert(self) {
+        if !self.as_bool() {
+            panic!("{}", self.render(color::enabled(&io::stderr())));
+        }
+    }
+
+    #[track_caller]
+    pub fn assert_msg(self, msg: &str) {
+        if !self.as_bool() {
+            panic!("{msg}: {}", self.render(color::enabled(&io::stderr())));
+        }
+    }
+
+    pub fn as_warning(self) -> Self {
+        match self {
+            Self::Success => Self::Success,
+            Self::Failure(failures) => {
+                Self::Failure(failures.into_iter().map(Failure::as_warning).collect())
+            }
+        }
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Failure> {
+        self.failures().iter().filter(|f| !f.is_warning())
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Failure> {
+        self.failures().iter().filter(|f| f.is_warning())
+    }
+
+    pub fn failures(&self) -> &[Failure] {
+        match self {
+            CheckResult::Success => &[],
+            CheckResult::Failure(failures) => failures,
+        }
+    }
+
+    pub fn failure_count(&self) -> usize {
+        self.failures().len()
+    }
+
+    pub fn failures_of_kind(&self, kind: FailureKind) -> impl Iterator<Item = &Failure> {
+        self.failures().iter().filter(move |f| f.kind == kind)
+    }
+
+    pub fn into_failures(self) -> Vec<Failure> {
+        self.into()
+    }
+
+    pub fn render(&self, color: bool) -> String {
+        self.render_with(color, Verbosity::Normal)
+    }
+
+    pub fn render_with(&self, color: bool, verbosity: Verbosity) -> String {
+        match self {
+            CheckResult::Success => "All checks passed".to_string(),
+            CheckResult::Failure(failures) => {
+                let warnings = self.warnings().count();
+                let mut rendered = if warnings == 0 {
+                    format!("{} check(s) failed:", failures.len())
+                } else {
+                    format!(
+                        "{} check(s) failed, {warnings} warning(s):",
+                        failures.len() - warnings
+                    )
+                };
+                for failure in failures {
+                    rendered.push_str(&format!("\n  - {}", failure.render_with(color, verbosity)));
+                    if let Some(snippet) =
+                        failure.location.as_ref().and_then(|l| l.snippet.as_ref())
+                    {
+                        rendered.push_str(&paint(
+                            &format!("\n      | {snippet}"),
+                            Style::Context,
+                            color,
+                        ));
+                    }
+                    if verbosity == Verbosity::Explain {
+                        rendered.push_str(&failure.render_sides(color, "      "));
+                    }
+                }
+                rendered
+            }
+        }
+    }
+
     pub fn as_bool(&self) -> bool {
-        match *self {
-            CheckResult::Success => true,
-            CheckResult::Failure(_) => false,
+        self.errors().next().is_none()
+    }
+
+    pub fn into_result(self) -> Result<(), SynAssertError> {
+        if self.as_bool() {
+            Ok(())
+        } else {
+            Err(SynAssertError {
+                failures: self.into(),
+            })
         }
     }
 }
 
+impl Check for CheckResult {
+    fn check(self) -> CheckResult {
+        self
+    }
+}
+
 impl Add for CheckResult {
     type Output = Self;
 
@@ -86,7 +298,45 @@ impl Add for CheckResult {
     }
 }
 
-impl From<CheckResult> for Vec<String> {
+impl Display for CheckResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(false))
+    }
+}
+
+impl error::Error for CheckResult {}
+
+#[derive(Debug, Clone)]
+pub struct SynAssertError {
+    failures: Vec<Failure>,
+}
+
+impl SynAssertError {
+    pub fn failures(&self) -> &[Failure] {
+        &self.failures
+    }
+
+    pub fn into_failures(self) -> Vec<Failure> {
+        self.failures
+    }
+}
+
+impl Display for SynAssertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let result = CheckResult::Failure(self.failures.clone());
+        write!(f, "{}", result.render(false))
+    }
+}
+
+impl error::Error for SynAssertError {}
+
+impl From<SynAssertError> for CheckResult {
+    fn from(err: SynAssertError) -> Self {
+        Self::Failure(err.failures)
+    }
+}
+
+impl From<CheckResult> for Vec<Failure> {
     fn from(checkresult: CheckResult) -> Self {
         match checkresult {
             CheckResult::Success => Default::default(),
@@ -95,8 +345,38 @@ impl From<CheckResult> for Vec<String> {
     }
 }
 
+impl From<CheckResult> for Vec<String> {
+    fn from(checkresult: CheckResult) -> Self {
+        Vec::<Failure>::from(checkresult)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+}
+
 impl From<Vec<String>> for CheckResult {
     fn from(failures: Vec<String>) -> Self {
+        failures
+            .into_iter()
+            .map(Failure::from)
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+impl From<Vec<Failure>> for CheckResult {
+    fn from(mut failures: Vec<Failure>) -> Self {
+        failures.sort_by_cached_key(|f| {
+            (
+                f.location
+                    .as_ref()
+                    .map(|l| (l.file.clone(), l.line, l.column)),
+                f.path.clone(),
+                f.message(),
+            )
+        });
+        failures.dedup();
+
         if failures.is_empty() {
             Self::Success
         } else {
@@ -118,6 +398,15 @@ where
     }
 }
 
+impl<T> Contains<T> for BTreeSet<T>
+where
+    T: Ord,
+{
+    fn contains(&self, value: &T) -> bool {
+        BTreeSet::contains(self, value)
+    }
+}
+
 impl<T> Contains<T> for &[T]
 where
     T: PartialEq,
@@ -140,3 +429,372 @@ macro_rules! check_option {
 }
 
 pub(crate) use check_option;
+
+pub(crate) fn attr_paths(attrs: &[Attribute]) -> BTreeSet<String> {
+    attrs
+        .iter()
+        .map(|a| {
+            a.path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::")
+        })
+        .collect()
+}
+
+pub(crate) fn generic_names(generics: &Generics) -> Vec<String> {
+    generics
+        .params
+        .iter()
+        .map(|p| match p {
+            GenericParam::Type(t) => t.ident.to_string(),
+            GenericParam::Lifetime(l) => l.lifetime.to_string(),
+            GenericParam::Const(c) => c.ident.to_string(),
+        })
+        .collect()
+}
+
+pub(crate) fn unexpected_extras<I>(kind: &str, actual: I, expected: &[String]) -> CheckResult
+where
+    I: IntoIterator<Item = String>,
+{
+    actual
+        .into_iter()
+        .filter(|a| !expected.contains(a))
+        .map(|a| Failure::unexpected(&format!("{kind} '{a}'")))
+        .collect::<Vec<_>>()
+        .into()
+}
+
+pub(crate) fn missing_names<I>(kind: &str, actual: I, expected: &[String]) -> CheckResult
+where
+    I: IntoIterator<Item = String>,
+{
+    let actual = actual.into_iter().collect::<Vec<_>>();
+    expected
+        .iter()
+        .filter(|e| !actual.contains(e))
+        .map(|e| Failure::missing(&format!("{kind} '{e}'")))
+        .collect::<Vec<_>>()
+        .into()
+}
+
+pub(crate) fn compare_attrs(actual: &[Attribute], expected: &[Attribute]) -> CheckResult {
+    CheckResult::any(expected.iter().map(|attr| {
+        let expected_str = normalize(attr.to_token_stream()).to_string();
+        if actual
+            .iter()
+            .any(|a| normalize(a.to_token_stream()).to_string() == expected_str)
+        {
+            explain::record(|| format!("found '{expected_str}'"));
+            return CheckResult::Success;
+        }
+        match actual.iter().find(|a| a.path == attr.path) {
+            Some(candidate) => compare_tokens("attribute", &attr.to_token_stream(), candidate),
+            None => CheckResult::missing(&format!("attribute '{expected_str}'")),
+        }
+    }))
+}
+
+pub(crate) fn compare_attr_args(actual: &[Attribute], path: &str, args: &[String]) -> CheckResult {
+    let expected_path = match parse_str::<Path>("attribute path", path) {
+        Ok(expected_path) => expected_path,
+        Err(failure) => return failure,
+    };
+    let attr = match actual
+        .iter()
+        .find(|a| paths_overlap(&a.path, &expected_path))
+    {
+        Some(attr) => attr,
+        None => return CheckResult::missing(&format!("attribute '{path}'")),
+    };
+    let nested = match attr.parse_meta() {
+        Ok(Meta::List(list)) => list.nested.into_iter().collect(),
+        _ => Vec::new(),
+    };
+    CheckResult::any(args.iter().map(|arg| {
+        let expected = match parse_str::<NestedMeta>("attribute argument", arg) {
+            Ok(expected) => expected,
+            Err(failure) => return failure,
+        };
+        if nested.iter().any(|actual| nested_covers(actual, &expected)) {
+            explain::record(|| format!("found '{arg}' in attribute '{path}'"));
+            CheckResult::Success
+        } else {
+            CheckResult::missing(&format!("argument '{arg}' of attribute '{path}'"))
+                .at(Some(Location::of(attr)))
+        }
+    }))
+}
+
+fn paths_overlap(actual: &Path, expected: &Path) -> bool {
+    actual
+        .segments
+        .iter()
+        .rev()
+        .zip(expected.segments.iter().rev())
+        .all(|(a, e)| a.ident == e.ident)
+}
+
+fn nested_covers(actual: &NestedMeta, expected: &NestedMeta) -> bool {
+    match (actual, expected) {
+        (NestedMeta::Meta(Meta::List(actual)), NestedMeta::Meta(Meta::List(expected)))
+            if actual.path == expected.path =>
+        {
+            expected
+                .nested
+                .iter()
+                .all(|e| actual.nested.iter().any(|a| nested_covers(a, e)))
+        }
+        (actual, expected) => {
+            actual.to_token_stream().to_string() == expected.to_token_stream().to_string()
+        }
+    }
+}
+
+pub(crate) fn parse_str<T>(kind: &str, s: &str) -> Result<T, CheckResult>
+where
+    T: Parse,
+{
+    syn::parse_str(s).map_err(|e| {
+        CheckResult::Failure(vec![Failure::invalid(
+            &format!("{kind} '{s}'"),
+            &e.to_string(),
+        )])
+    })
+}
+
+pub(crate) fn compare_parsed<T>(kind: &str, expected: &str, actual: &T) -> CheckResult
+where
+    T: Parse + ToTokens,
+{
+    match parse_str::<T>(kind, expected) {
+        Ok(expected) => CheckResult::compare_named(
+            kind,
+            normalize(expected.to_token_stream()),
+            normalize(actual.to_token_stream()),
+        ),
+        Err(failure) => failure,
+    }
+}
+
+pub(crate) fn compare_tokens<T>(subject: &str, expected: &TokenStream, actual: &T) -> CheckResult
+where
+    T: ToTokens,
+{
+    let (expected, actual) = (
+        normalize(expected.clone()),
+        normalize(actual.to_token_stream()),
+    );
+    let (expected_str, actual_str) = (expected.to_string(), actual.to_string());
+    if expected_str == actual_str {
+        explain::record(|| format!("{subject}: '{expected_str}' == '{actual_str}'"));
+        CheckResult::Success
+    } else {
+        CheckResult::Failure(vec![Failure {
+            diff: Some(Diff::tokens(expected, actual)),
+            ..Failure::mismatch(subject, &expected_str, &actual_str)
+        }])
+    }
+}
+
+pub(crate) fn flatten_tokens(tokens: TokenStream, out: &mut Vec<String>) {
+    for tt in tokens {
+        match tt {
+            TokenTree::Group(g) => {
+                let (open, close) = match g.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::None => ("", ""),
+                };
+                out.push(open.to_string());
+                flatten_tokens(g.stream(), out);
+                out.push(close.to_string());
+            }
+            tt => out.push(tt.to_string()),
+        }
+    }
+}
+
+pub(crate) fn tokens_contain(haystack: TokenStream, needle: TokenStream) -> bool {
+    let mut haystack_tokens = Vec::new();
+    flatten_tokens(normalize(haystack), &mut haystack_tokens);
+    let mut needle_tokens = Vec::new();
+    flatten_tokens(normalize(needle), &mut needle_tokens);
+    haystack_tokens.retain(|t| !t.is_empty());
+    needle_tokens.retain(|t| !t.is_empty());
+
+    needle_tokens.is_empty()
+        || haystack_tokens
+            .windows(needle_tokens.len())
+            .any(|w| w == needle_tokens.as_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let results = CheckResult::Failure(vec![
+            Failure::mismatch("", "main", "other"),
+            Failure::missing("'inline'"),
+        ]);
+
+        assert_eq!(
+            results.render(false),
+            "2 check(s) failed:\n  - Expected 'main', got 'other'\n  - Missing 'inline'"
+        );
+        assert_eq!(CheckResult::Success.to_string(), "All checks passed");
+    }
+
+    #[test]
+    fn test_compare_rendering() {
+        let message = |result: CheckResult| result.into_failures()[0].message();
+
+        assert_eq!(
+            message(CheckResult::compare("main", "other")),
+            "Expected '\"main\"', got '\"other\"'"
+        );
+        assert_eq!(
+            message(CheckResult::contains(BTreeSet::from(["inline"]), &["cold"])),
+            "Missing '\"cold\"'"
+        );
+        assert_eq!(
+            message(CheckResult::contains_display(
+                BTreeSet::from(["inline"]),
+                &["cold"]
+            )),
+            "Missing 'cold'"
+        );
+        assert_eq!(
+            message(CheckResult::compare_named("name", "main", "other")),
+            "Expected name to be 'main', got 'other'"
+        );
+    }
+
+    #[test]
+    fn test_render_verbosity() {
+        let results = compare_tokens(
+            "signature",
+            &quote::quote! { fn fetch() -> u32 },
+            &quote::quote! { fn fetch() -> u64 },
+        );
+
+        assert_eq!(
+            results.render(false),
+            "1 check(s) failed:\n  - Mismatched signature: ... ( ) -> [-u32-] {+u64+}"
+        );
+        assert_eq!(
+            results.render_with(false, Verbosity::Explain),
+            "1 check(s) failed:\n  - Mismatched signature\n      < ... ( ) -> u32\n      > ... ( ) -> u64"
+        );
+    }
+
+    #[test]
+    fn test_failures() {
+        let results = CheckResult::Failure(vec![
+            Failure::mismatch("", "main", "other"),
+            Failure::missing("'inline'"),
+            Failure::missing("name"),
+        ]);
+
+        assert_eq!(results.failure_count(), 3);
+        assert_eq!(results.failures_of_kind(FailureKind::Missing).count(), 2);
+        assert_eq!(results.failures()[0].expected.as_deref(), Some("main"));
+        assert!(CheckResult::Success.failures().is_empty());
+    }
+
+    #[test]
+    fn test_warning() {
+        let results = CheckResult::missing("doc comment").as_warning() + CheckResult::Success;
+
+        assert!(results.as_bool());
+        assert_eq!(results.warnings().count(), 1);
+        assert_eq!(
+            results.render(false),
+            "0 check(s) failed, 1 warning(s):\n  - warning: Missing doc comment"
+        );
+        results.clone().assert();
+
+        let results = results + CheckResult::missing("name");
+        assert!(!results.as_bool());
+        assert_eq!(results.errors().count(), 1);
+    }
+
+    #[test]
+    fn test_ordering() {
+        let location = |line| Location {
+            file: None,
+            line,
+            column: 1,
+            snippet: None,
+        };
+        let results = CheckResult::Failure(vec![Failure::missing("b").at(Some(location(3)))])
+            + CheckResult::Failure(vec![Failure::missing("a").at(Some(location(3)))])
+            + CheckResult::Failure(vec![Failure::missing("c").at(Some(location(1)))])
+            + CheckResult::missing("d")
+            + CheckResult::missing("d");
+
+        assert_eq!(
+            Vec::<String>::from(results),
+            vec![
+                "Missing d".to_string(),
+                "Missing c (at 1:1)".to_string(),
+                "Missing a (at 3:1)".to_string(),
+                "Missing b (at 3:1)".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_assert() {
+        CheckResult::Success.assert();
+        CheckResult::Success.assert_msg("should not panic");
+    }
+
+    #[test]
+    #[should_panic(expected = "1 check(s) failed:\n  - Missing name")]
+    fn test_assert_fail() {
+        CheckResult::missing("name").assert();
+    }
+
+    #[test]
+    #[should_panic(expected = "handler exists: 1 check(s) failed:\n  - Missing name")]
+    fn test_assert_msg_fail() {
+        CheckResult::missing("name").assert_msg("handler exists");
+    }
+
+    #[test]
+    fn test_error() {
+        fn fails() -> Result<(), Box<dyn error::Error>> {
+            Err(CheckResult::missing("name"))?;
+            Ok(())
+        }
+
+        let err = fails().unwrap_err();
+        assert_eq!(err.to_string(), "1 check(s) failed:\n  - Missing name");
+    }
+
+    #[test]
+    fn test_into_result() {
+        fn fails() -> Result<(), Box<dyn error::Error>> {
+            (CheckResult::missing("name") + CheckResult::missing("vis").as_warning())
+                .into_result()?;
+            Ok(())
+        }
+
+        assert!(CheckResult::missing("docs")
+            .as_warning()
+            .into_result()
+            .is_ok());
+        let err = fails().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "1 check(s) failed, 1 warning(s):\n  - Missing name\n  - warning: Missing vis"
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use crate::explain;
+use crate::failure::{Failure, Location};
+use crate::utils::CheckResult;
+use proc_macro2::TokenStream;
+use syn::parse::Parse;
+use syn::{Block, Expr, File, Item, Type};
+
+pub fn parses_as<T>(subject: &str, tokens: &TokenStream) -> CheckResult
+where
+    T: Parse,
+{
+    match syn::parse2::<T>(tokens.clone()) {
+        Ok(_) => {
+            explain::record(|| format!("parses as {subject}"));
+            CheckResult::Success
+        }
+        Err(e) => CheckResult::Failure(vec![
+            Failure::invalid(subject, &e.to_string()).at(Some(Location::from_span(e.span())))
+        ]),
+    }
+}
+
+pub fn parses_as_file(tokens: &TokenStream) -> CheckResult {
+    parses_as::<File>("file", tokens)
+}
+
+pub fn parses_as_item(tokens: &TokenStream) -> CheckResult {
+    parses_as::<Item>("item", tokens)
+}
+
+pub fn parses_as_expr(tokens: &TokenStream) -> CheckResult {
+    parses_as::<Expr>("expr", tokens)
+}
+
+pub fn parses_as_block(tokens: &TokenStream) -> CheckResult {
+    parses_as::<Block>("block", tokens)
+}
+
+pub fn parses_as_type(tokens: &TokenStream) -> CheckResult {
+    parses_as::<Type>("type", tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::quote;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_parses_as() -> Result<(), TestError> {
+        let tokens = quote! { fn main() {} };
+        assert!(parses_as_file(&tokens).as_bool());
+        assert!(parses_as_item(&tokens).as_bool());
+        assert!(!parses_as_expr(&tokens).as_bool());
+
+        assert!(parses_as_block(&quote! { { 1 + 1 } }).as_bool());
+        assert!(parses_as_type(&quote! { Vec<u8> }).as_bool());
+
+        let tokens: TokenStream = "struct Foo {\n    id: u64\n    name: String\n}".parse()?;
+        let failures = parses_as_item(&tokens).into_failures();
+        assert_eq!(
+            failures[0].to_string(),
+            "Invalid item: expected `,` (at 3:5)"
+        );
+
+        Ok(())
+    }
+}
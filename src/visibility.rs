@@ -0,0 +1,169 @@
+use crate::explain;
+use crate::failure::{Failure, Location};
+use crate::harness::item_ident;
+use crate::rules::Rule;
+use crate::utils::CheckResult;
+use quote::ToTokens;
+use syn::{ImplItem, Item, Visibility};
+
+impl<'s> Rule<'s> {
+    pub fn forbid_vis(vis: &str) -> Self {
+        let vis = normalize_vis(vis);
+        Self::new(&format!("no {vis} items")).must(move |item| forbid_vis(item, &vis))
+    }
+
+    pub fn private_in(module: &str) -> Self {
+        Self::new(&format!("items in {module} are private"))
+            .in_module(module)
+            .must(require_private)
+    }
+}
+
+pub(crate) fn forbid_vis(item: &Item, vis: &str) -> CheckResult {
+    visibilities(item)
+        .into_iter()
+        .filter(|(_, _, v)| normalize_vis(&v.to_token_stream().to_string()) == vis)
+        .map(|(kind, name, v)| violation(kind, &name, v))
+        .collect::<Vec<_>>()
+        .into()
+}
+
+pub(crate) fn require_private(item: &Item) -> CheckResult {
+    visibilities(item)
+        .into_iter()
+        .filter(|(_, _, v)| !matches!(v, Visibility::Inherited))
+        .map(|(kind, name, v)| violation(kind, &name, v))
+        .collect::<Vec<_>>()
+        .into()
+}
+
+fn violation(kind: &str, name: &str, vis: &Visibility) -> Failure {
+    let rendered = normalize_vis(&vis.to_token_stream().to_string());
+    Failure::unexpected(&format!("{rendered} {kind} '{name}'")).at(Some(Location::of(vis)))
+}
+
+pub(crate) fn compare_vis(expected: &Visibility, actual: &Visibility) -> CheckResult {
+    let (expected_str, actual_str) = (render_vis(expected), render_vis(actual));
+    if expected == actual {
+        explain::record(|| format!("visibility: '{expected_str}' == '{actual_str}'"));
+        CheckResult::Success
+    } else {
+        CheckResult::Failure(vec![Failure::mismatch(
+            "visibility",
+            &expected_str,
+            &actual_str,
+        )])
+    }
+}
+
+fn render_vis(vis: &Visibility) -> String {
+    match vis {
+        Visibility::Inherited => "private".to_string(),
+        vis => normalize_vis(&vis.to_token_stream().to_string()),
+    }
+}
+
+fn normalize_vis(vis: &str) -> String {
+    vis.split_whitespace().collect()
+}
+
+fn visibilities(item: &Item) -> Vec<(&'static str, String, &Visibility)> {
+    let mut out = Vec::new();
+    let vis = match item {
+        Item::Const(i) => Some(&i.vis),
+        Item::Enum(i) => Some(&i.vis),
+        Item::ExternCrate(i) => Some(&i.vis),
+        Item::Fn(i) => Some(&i.vis),
+        Item::Mod(i) => Some(&i.vis),
+        Item::Static(i) => Some(&i.vis),
+        Item::Struct(i) => Some(&i.vis),
+        Item::Trait(i) => Some(&i.vis),
+        Item::TraitAlias(i) => Some(&i.vis),
+        Item::Type(i) => Some(&i.vis),
+        Item::Union(i) => Some(&i.vis),
+        Item::Use(i) => Some(&i.vis),
+        _ => None,
+    };
+    if let Some(vis) = vis {
+        let (kind, name) = match item_ident(item) {
+            Some((kind, ident)) => (kind, ident.to_string()),
+            None => ("item", item.to_token_stream().to_string()),
+        };
+        let name = match item {
+            Item::Use(item_use) => item_use.tree.to_token_stream().to_string(),
+            _ => name,
+        };
+        out.push((
+            if matches!(item, Item::Use(_)) {
+                "use"
+            } else {
+                kind
+            },
+            name,
+            vis,
+        ));
+    }
+    if let Item::Impl(impl_) = item {
+        if impl_.trait_.is_none() {
+            out.extend(impl_.items.iter().filter_map(|i| match i {
+                ImplItem::Method(m) => Some(("fn", m.sig.ident.to_string(), &m.vis)),
+                ImplItem::Const(c) => Some(("const", c.ident.to_string(), &c.vis)),
+                ImplItem::Type(t) => Some(("type", t.ident.to_string(), &t.vis)),
+                _ => None,
+            }));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crate_ast::CrateAst;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_visibility_rules() -> Result<(), TestError> {
+        let dir = TestDir::new(&[(
+            "src/lib.rs",
+            r#"
+pub(super) fn up() {}
+mod internal {
+    fn ok() {}
+    pub struct Leaked;
+    impl Leaked { pub(crate) fn get(&self) {} pub(super) fn put(&self) {} }
+}
+"#,
+        )])?;
+        let ast = CrateAst::load(&dir)?;
+
+        let messages = |rule: Rule| {
+            rule.check(&ast)
+                .into_failures()
+                .iter()
+                .map(|f| (f.message(), f.location.as_ref().map(|l| l.line)))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            messages(Rule::forbid_vis("pub(super)")),
+            vec![
+                ("Unexpected pub(super) fn 'up'".to_string(), Some(2)),
+                ("Unexpected pub(super) fn 'put'".to_string(), Some(6)),
+            ]
+        );
+        assert_eq!(
+            messages(Rule::private_in("crate::internal")),
+            vec![
+                ("Unexpected pub struct 'Leaked'".to_string(), Some(5)),
+                ("Unexpected pub(crate) fn 'get'".to_string(), Some(6)),
+                ("Unexpected pub(super) fn 'put'".to_string(), Some(6)),
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,353 @@
+use crate::cfg::CfgSet;
+use crate::crate_ast::{CrateAst, CrateItem};
+use crate::failure::Failure;
+use crate::file::HasItems;
+use crate::imports::ImportScope;
+use crate::json::Json;
+use crate::utils::CheckResult;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use syn::Item;
+
+#[derive(Debug, Clone)]
+pub struct Target {
+    pub name: String,
+    pub kind: String,
+    pub ast: CrateAst,
+}
+
+#[derive(Debug, Clone)]
+pub struct Member {
+    pub name: String,
+    pub manifest_path: PathBuf,
+    pub targets: Vec<Target>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    pub members: Vec<Member>,
+}
+
+impl Workspace {
+    pub fn load() -> Result<Self, CheckResult> {
+        Self::load_from(".")
+    }
+
+    pub fn load_from<P>(dir: P) -> Result<Self, CheckResult>
+    where
+        P: AsRef<Path>,
+    {
+        Self::load_cfg(dir, None)
+    }
+
+    pub fn load_with<P>(dir: P, cfg: &CfgSet) -> Result<Self, CheckResult>
+    where
+        P: AsRef<Path>,
+    {
+        Self::load_cfg(dir, Some((cfg, true)))
+    }
+
+    pub fn load_without_default_features<P>(dir: P, cfg: &CfgSet) -> Result<Self, CheckResult>
+    where
+        P: AsRef<Path>,
+    {
+        Self::load_cfg(dir, Some((cfg, false)))
+    }
+
+    fn load_cfg<P>(dir: P, cfg: Option<(&CfgSet, bool)>) -> Result<Self, CheckResult>
+    where
+        P: AsRef<Path>,
+    {
+        let output = Command::new("cargo")
+            .args(["metadata", "--format-version", "1", "--no-deps"])
+            .current_dir(dir.as_ref())
+            .output()
+            .map_err(|e| invalid(&e.to_string()))?;
+        if !output.status.success() {
+            return Err(invalid(String::from_utf8_lossy(&output.stderr).trim()));
+        }
+
+        let metadata =
+            Json::parse(&String::from_utf8_lossy(&output.stdout)).map_err(|e| invalid(&e))?;
+        Self::from_metadata(&metadata, cfg)
+    }
+
+    fn from_metadata(metadata: &Json, cfg: Option<(&CfgSet, bool)>) -> Result<Self, CheckResult> {
+        let mut members = Vec::new();
+        let mut failures = Vec::new();
+        let packages = metadata
+            .get("packages")
+            .map(Json::as_array)
+            .unwrap_or_default();
+
+        for package in packages {
+            let name = package
+                .get("name")
+                .and_then(Json::as_str)
+                .unwrap_or_default();
+            let manifest_path = package
+                .get("manifest_path")
+                .and_then(Json::as_str)
+                .unwrap_or_default();
+            let cfg = cfg.map(|(cfg, default_features)| {
+                member_cfg(package.get("features"), cfg, default_features)
+            });
+            let mut targets = Vec::new();
+            for target in package
+                .get("targets")
+                .map(Json::as_array)
+                .unwrap_or_default()
+            {
+                let kind = target
+                    .get("kind")
+                    .map(Json::as_array)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(Json::as_str)
+                    .find(|k| matches!(*k, "lib" | "proc-macro" | "bin"));
+                let root = target.get("src_path").and_then(Json::as_str);
+                let (Some(kind), Some(root)) = (kind, root) else {
+                    continue;
+                };
+                let target_name = target
+                    .get("name")
+                    .and_then(Json::as_str)
+                    .unwrap_or_default();
+                let ast = match &cfg {
+                    Some(cfg) => CrateAst::from_root_with(root, cfg),
+                    None => CrateAst::from_root(root),
+                };
+                match ast {
+                    Ok(ast) => targets.push(Target {
+                        name: target_name.to_string(),
+                        kind: kind.to_string(),
+                        ast,
+                    }),
+                    Err(failure) => failures.extend(failure.into_failures().into_iter().map(|f| {
+                        f.within(&format!("{kind} {target_name}"))
+                            .within(&format!("crate {name}"))
+                    })),
+                }
+            }
+
+            if targets.is_empty() {
+                failures.push(
+                    Failure::missing("library or binary target").within(&format!("crate {name}")),
+                );
+            } else {
+                members.push(Member {
+                    name: name.to_string(),
+                    manifest_path: manifest_path.into(),
+                    targets,
+                });
+            }
+        }
+
+        if failures.is_empty() {
+            members.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(Self { members })
+        } else {
+            Err(failures.into())
+        }
+    }
+
+    pub fn select<I, S>(self, packages: I) -> Result<Self, CheckResult>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let packages = packages
+            .into_iter()
+            .map(|p| p.as_ref().to_string())
+            .collect::<Vec<_>>();
+        if let Some(unknown) = packages.iter().find(|p| self.member(p).is_none()) {
+            return Err(CheckResult::missing(&format!("package '{unknown}'")));
+        }
+        if packages.is_empty() {
+            return Ok(self);
+        }
+
+        Ok(Self {
+            members: self
+                .members
+                .into_iter()
+                .filter(|m| packages.contains(&m.name))
+                .collect(),
+        })
+    }
+
+    pub fn member(&self, name: &str) -> Option<&Member> {
+        self.members.iter().find(|m| m.name == name)
+    }
+
+    pub fn items(&self) -> Vec<(&str, CrateItem<'_>)> {
+        self.members
+            .iter()
+            .flat_map(|m| {
+                m.targets
+                    .iter()
+                    .flat_map(move |t| t.ast.items().into_iter().map(move |i| (m.name.as_str(), i)))
+            })
+            .collect()
+    }
+}
+
+impl HasItems for Workspace {
+    fn walk_items(&self) -> Vec<&Item> {
+        self.members
+            .iter()
+            .flat_map(|m| &m.targets)
+            .flat_map(|t| t.ast.walk_items())
+            .collect()
+    }
+
+    fn walk_scopes(&self) -> Vec<(ImportScope, Vec<&Item>)> {
+        self.members
+            .iter()
+            .flat_map(|m| &m.targets)
+            .flat_map(|t| t.ast.walk_scopes())
+            .collect()
+    }
+}
+
+fn member_cfg(features: Option<&Json>, cfg: &CfgSet, default_features: bool) -> CfgSet {
+    let mut pending = cfg.features().map(String::from).collect::<Vec<_>>();
+    if default_features {
+        pending.push("default".to_string());
+    }
+    let mut enabled = BTreeSet::new();
+    while let Some(feature) = pending.pop() {
+        if !enabled.insert(feature.clone()) {
+            continue;
+        }
+        let implied = features
+            .and_then(|f| f.get(&feature))
+            .map(Json::as_array)
+            .unwrap_or_default();
+        for entry in implied.iter().filter_map(Json::as_str) {
+            match entry.split_once('/') {
+                _ if entry.starts_with("dep:") => {}
+                Some((dep, _)) if dep.ends_with('?') => {}
+                Some((dep, _)) => pending.push(dep.to_string()),
+                None => pending.push(entry.to_string()),
+            }
+        }
+    }
+    cfg.clone().with_features(enabled)
+}
+
+fn invalid(error: &str) -> CheckResult {
+    CheckResult::Failure(vec![Failure::invalid("cargo metadata", error)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use std::error;
+
+    type TestError = Box<dyn error::Error>;
+
+    #[test]
+    fn test_load() -> Result<(), TestError> {
+        let dir = TestDir::new(&[
+            (
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"core\", \"derive\"]\nresolver = \"2\"\n",
+            ),
+            (
+                "core/Cargo.toml",
+                "[package]\nname = \"demo-core\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+            ),
+            ("core/src/lib.rs", "pub struct Engine;\nimpl HasItems for Engine {}\n"),
+            ("core/src/main.rs", "fn main() {}\n"),
+            ("core/src/bin/tool.rs", "fn main() {}\n"),
+            (
+                "derive/Cargo.toml",
+                "[package]\nname = \"demo-derive\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[lib]\nproc-macro = true\n",
+            ),
+            ("derive/src/lib.rs", "pub fn expand() {}\n"),
+        ])?;
+        let workspace = Workspace::load_from(&dir)?;
+
+        assert_eq!(
+            workspace
+                .members
+                .iter()
+                .map(|m| m.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["demo-core", "demo-derive"]
+        );
+        let member = workspace.member("demo-core").unwrap();
+        assert_eq!(
+            member
+                .targets
+                .iter()
+                .map(|t| (t.kind.as_str(), t.name.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("lib", "demo_core"), ("bin", "demo-core"), ("bin", "tool"),]
+        );
+        assert!(member.targets[0]
+            .ast
+            .implements("HasItems", "Engine")
+            .as_bool());
+        assert_eq!(
+            workspace.member("demo-derive").unwrap().targets[0].kind,
+            "proc-macro"
+        );
+        assert!(workspace
+            .items()
+            .iter()
+            .any(|(member, item)| *member == "demo-derive"
+                && matches!(item.item, Item::Fn(f) if f.sig.ident == "expand")));
+
+        let workspace = workspace.select(["demo-derive"])?;
+        assert_eq!(workspace.members.len(), 1);
+        assert!(workspace.select(["unknown"]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_metadata_targets() -> Result<(), TestError> {
+        let dir = TestDir::new(&[
+            ("src/lib.rs", "pub struct Api;\n"),
+            ("src/main.rs", "fn main() {}\n"),
+            ("build.rs", "fn main() {}\n"),
+        ])?;
+        let target = |kind: &str, name: &str, path: &str| {
+            format!(
+                r#"{{"kind": ["{kind}"], "name": "{name}", "src_path": {:?}}}"#,
+                dir.join(path).display().to_string()
+            )
+        };
+        let metadata = Json::parse(&format!(
+            r#"{{"packages": [{{"name": "app", "manifest_path": "Cargo.toml", "targets": [{}, {}, {}]}}]}}"#,
+            target("lib", "app", "src/lib.rs"),
+            target("bin", "app", "src/main.rs"),
+            target("custom-build", "build-script-build", "build.rs"),
+        ))?;
+
+        let workspace = Workspace::from_metadata(&metadata, None).map_err(|e| e.to_string())?;
+        let member = workspace.member("app").unwrap();
+        assert_eq!(
+            member
+                .targets
+                .iter()
+                .map(|t| t.kind.as_str())
+                .collect::<Vec<_>>(),
+            vec!["lib", "bin"]
+        );
+        assert!(workspace
+            .walk_items()
+            .iter()
+            .any(|item| matches!(item, Item::Fn(f) if f.sig.ident == "main")));
+        assert!(workspace
+            .walk_items()
+            .iter()
+            .any(|item| matches!(item, Item::Struct(s) if s.ident == "Api")));
+
+        Ok(())
+    }
+}